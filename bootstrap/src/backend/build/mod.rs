@@ -4,13 +4,23 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
-const EC_FILES: [&str; 6] = [
+const EC_FILES: [&str; 16] = [
     "core_bool.h",
     "core_bool.c",
+    "core_intrinsics_fs.h",
+    "core_intrinsics_fs.c",
     "core_intrinsics_io.h",
     "core_intrinsics_io.c",
     "core_intrinsics_math.h",
     "core_intrinsics_math.c",
+    "core_intrinsics_process.h",
+    "core_intrinsics_process.c",
+    "core_intrinsics_random.h",
+    "core_intrinsics_random.c",
+    "core_intrinsics_string.h",
+    "core_intrinsics_string.c",
+    "core_intrinsics_time.h",
+    "core_intrinsics_time.c",
 ];
 
 pub fn build(name: &str, c_code: &str) -> io::Result<()> {
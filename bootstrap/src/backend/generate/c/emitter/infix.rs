@@ -6,15 +6,15 @@ impl Emitter {
         self.emit_expression(&expression.left);
         match expression.operator {
             InfixOperator::Add => self.emit_token("+"),
-            InfixOperator::Subtract => unimplemented!(),
-            InfixOperator::Multiply => unimplemented!(),
-            InfixOperator::Divide => unimplemented!(),
-            InfixOperator::Modulo => unimplemented!(),
+            InfixOperator::Subtract => self.emit_token("-"),
+            InfixOperator::Multiply => self.emit_token("*"),
+            InfixOperator::Divide => self.emit_token("/"),
+            InfixOperator::Modulo => self.emit_token("%"),
             InfixOperator::Equal => self.emit_token("=="),
             InfixOperator::NotEqual => self.emit_token("!="),
-            InfixOperator::LessThan => unimplemented!(),
-            InfixOperator::GreaterThan => unimplemented!(),
-            InfixOperator::Assign => unimplemented!(),
+            InfixOperator::LessThan => self.emit_token("<"),
+            InfixOperator::GreaterThan => self.emit_token(">"),
+            InfixOperator::Assign => self.emit_token("="),
         }
 
         self.emit_expression(&expression.right);
@@ -16,7 +16,7 @@ impl Generator {
         &mut self,
         node: &DeclareFunctionNode,
     ) -> c::generator::Result<DeclareFunctionNode> {
-        unimplemented!("{node:#?}")
+        Err(c::generator::Error::Unsupported("DeclareFunction"))
     }
 
     pub(crate) fn generate_call_function(
@@ -72,16 +72,27 @@ impl Generator {
     ) -> c::generator::Result<Vec<Statement>> {
         let mut result = vec![];
 
-        let std = self.string_table.get(node.package.segments[0]).to_string();
-        let io = self.string_table.get(node.package.segments[1]).to_string();
-        let function = self.string_table.get(node.function.0).to_string();
+        let segments: Vec<&str> = node
+            .package
+            .segments
+            .iter()
+            .map(|segment| self.string_table.get(*segment))
+            .collect();
+        let function = self.string_table.get(node.function.0);
+
+        // intrinsics are implemented by the sysroot, everything else is named after its package
+        let identifier = if segments.starts_with(&["core", "intrinsics"]) {
+            self.intrinsics.symbol(function)?.to_string()
+        } else {
+            format!("{}_{function}", segments.join("_"))
+        };
 
         let (statements, arguments) = self.generate_call_arguments(&node.arguments)?;
         result.extend(statements);
 
         result.push(CallFunction(CallFunctionStatement {
             indent: Indent::none(),
-            identifier: format!("{std}_{io}_{function}"),
+            identifier,
             arguments: arguments.into(),
             result: None,
         }));
@@ -152,7 +163,7 @@ impl Generator {
                 continue;
             }
 
-            unimplemented!("{arg:#?}")
+            return Err(c::generator::Error::Unsupported(arg.kind()));
         }
 
         Ok((statements, arguments))
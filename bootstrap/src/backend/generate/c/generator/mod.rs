@@ -1,3 +1,4 @@
+use std::fmt;
 use std::ops::Deref;
 use std::vec;
 
@@ -12,10 +13,12 @@ use crate::backend::generate::c::{
     IncludeLocalDirectiveNode, IncludeSystemDirectiveNode, Indent, ReturnFromFunctionStatement,
     Statement, VariableExpression,
 };
+use crate::backend::intrinsic;
+use crate::backend::intrinsic::Intrinsics;
 use crate::common::node::Node;
 use crate::common::StringTable;
 use crate::frontend;
-use crate::frontend::ast::{AstDefineTypeNode, AstNode, AstTreeNode};
+use crate::frontend::ast::{AstDefineTypeNode, AstTreeNode};
 
 mod block;
 mod control;
@@ -26,15 +29,36 @@ mod scope;
 mod string;
 mod variable;
 
-pub enum Error {}
+#[derive(Debug)]
+pub enum Error {
+    Intrinsic(intrinsic::Error),
+    Unsupported(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Intrinsic(err) => write!(f, "{err}"),
+            Error::Unsupported(kind) => write!(f, "{kind} is not supported by c generation yet"),
+        }
+    }
+}
+
+impl From<intrinsic::Error> for Error {
+    fn from(value: intrinsic::Error) -> Self {
+        Self::Intrinsic(value)
+    }
+}
 
 type Result<T> = core::result::Result<T, Error>;
 
-pub(crate) fn generate(ctx: frontend::Context, ast: frontend::Ast) -> Result<Vec<c::Node>> {
+pub(crate) fn generate(mut ctx: frontend::Context, ast: frontend::Ast) -> Result<Vec<c::Node>> {
+    let intrinsics = Intrinsics::new(&mut ctx, false);
     let mut generator = Generator {
         string_table: ctx.string_table,
         // type_table: ctx.type_table,
         scope: Scope::new(),
+        intrinsics,
         directives: Vec::new(),
         function_declarations: Vec::new(),
         function_definitions: Vec::new(),
@@ -49,6 +73,7 @@ pub(crate) struct Generator {
     string_table: StringTable,
     // type_table: TypeTable,
     scope: Scope,
+    intrinsics: Intrinsics,
     //
     directives: Vec<DirectiveNode>,
     function_declarations: Vec<DeclareFunctionNode>,
@@ -77,10 +102,30 @@ impl Generator {
                 indent: Indent::none(),
                 path: "stdbool.h".to_string(),
             }),
+            IncludeLocalDirective(IncludeLocalDirectiveNode {
+                indent: Indent::none(),
+                path: "core_intrinsics_fs.h".to_string(),
+            }),
             IncludeLocalDirective(IncludeLocalDirectiveNode {
                 indent: Indent::none(),
                 path: "core_intrinsics_io.h".to_string(),
             }),
+            IncludeLocalDirective(IncludeLocalDirectiveNode {
+                indent: Indent::none(),
+                path: "core_intrinsics_process.h".to_string(),
+            }),
+            IncludeLocalDirective(IncludeLocalDirectiveNode {
+                indent: Indent::none(),
+                path: "core_intrinsics_random.h".to_string(),
+            }),
+            IncludeLocalDirective(IncludeLocalDirectiveNode {
+                indent: Indent::none(),
+                path: "core_intrinsics_string.h".to_string(),
+            }),
+            IncludeLocalDirective(IncludeLocalDirectiveNode {
+                indent: Indent::none(),
+                path: "core_intrinsics_time.h".to_string(),
+            }),
             IncludeLocalDirective(IncludeLocalDirectiveNode {
                 indent: Indent::none(),
                 path: "std_io.h".to_string(),
@@ -115,15 +160,46 @@ impl Generator {
                 .map(|df| c::Node::DeclareFunction(df)),
         );
 
+        // std::process::args() reads what main got
+        let mut main_statements = vec![Statement::CallFunction(CallFunctionStatement {
+            indent: Indent::none(),
+            identifier: "core_intrinsics_process_init".to_string(),
+            arguments: vec![
+                Expression::Variable(VariableExpression {
+                    indent: Indent::none(),
+                    identifier: "argc".to_string(),
+                }),
+                Expression::Variable(VariableExpression {
+                    indent: Indent::none(),
+                    identifier: "argv".to_string(),
+                }),
+            ]
+            .into_boxed_slice(),
+            result: None,
+        })];
+        main_statements.extend(self.main_statements);
+
         result.push(DefineFunction(DefineFunctionNode {
             indent: Indent::none(),
             identifier: "main".to_string(),
-            arguments: vec![].into_boxed_slice(),
+            arguments: vec![
+                DefineFunctionArgumentNode {
+                    indent: Indent::none(),
+                    identifier: "argc".to_string(),
+                    ty: "int".to_string(),
+                },
+                DefineFunctionArgumentNode {
+                    indent: Indent::none(),
+                    identifier: "argv".to_string(),
+                    ty: "char **".to_string(),
+                },
+            ]
+            .into_boxed_slice(),
             ty: "int".to_string(),
 
             statements: BlockStatement {
                 indent: Indent::none(),
-                statements: self.main_statements,
+                statements: main_statements,
             },
         }));
 
@@ -157,8 +233,6 @@ impl Generator {
                 let stmts = self.generate_block(node)?;
                 self.main_statements.push(Statement::Block(stmts));
             }
-            Node::BreakLoop(_) => unimplemented!(),
-            Node::Calculate(_) => unimplemented!(),
             Node::CallFunctionOfObject(node) => {
                 // FIXME use object tid to resolve type name
 
@@ -172,13 +246,7 @@ impl Generator {
                         ),
                         arguments: Box::new([Expression::Variable(VariableExpression {
                             indent: Indent::none(),
-                            identifier: format!(
-                                "&{}",
-                                self.scope
-                                    .get_variable(&node.object)
-                                    .unwrap()
-                                    .to_string(&self.string_table)
-                            ),
+                            identifier: format!("&{}", self.variable(&node.object)?),
                         })]),
                         result: None,
                     }))
@@ -187,20 +255,10 @@ impl Generator {
                 let statements = self.generate_call_function_of_package(node)?;
                 self.main_statements.extend(statements);
             }
-            Node::CallFunction(node) => unimplemented!(),
-            Node::CallFunctionWithLambda(_) => unimplemented!(),
-            Node::ExportPackage(_) => unimplemented!(),
-            Node::ReturnFromFunction(_) => unimplemented!(),
-            Node::ContinueLoop(_) => unimplemented!(),
-            Node::Compare(_) => unimplemented!(),
             Node::If(node) => {
                 let stmts = self.generate_if(node)?;
                 self.main_statements.extend(stmts);
             }
-            Node::AccessVariable(_) => unimplemented!(),
-            Node::AccessVariableOfObject(_) => unimplemented!(),
-            Node::AccessVariableOfSelf(_) => unimplemented!(),
-            Node::Loop(_) => unimplemented!(),
             Node::DeclareVariable(node) => {
                 let stmts = self.generate_declare_variable(node)?;
                 self.main_statements.extend(stmts);
@@ -233,8 +291,6 @@ impl Generator {
                 //     statements,
                 // });
             }
-            Node::DeclareExternalFunction(_) => unimplemented!(),
-            Node::DeclarePackage(_) => unimplemented!(),
             Node::DeclareType(node) => {
                 self.struct_declarations.push(DeclareStructNode {
                     indent: Indent::none(),
@@ -266,7 +322,6 @@ impl Generator {
                     fields: fields.into_boxed_slice(),
                 })
             }
-            Node::InstantiateType(_) => unimplemented!(),
             Node::DefineType(AstDefineTypeNode {
                 r#type,
                 modifiers,
@@ -304,11 +359,10 @@ impl Generator {
                     })
                 }
             }
-            Node::InterpolateString(_) => unimplemented!(),
             Node::LiteralBoolean(_) => {}
             Node::LiteralNumber(_) => {}
             Node::LiteralString(_) => {}
-            AstNode::Marker(_) => {}
+            _ => return Err(Error::Unsupported(node.kind())),
         };
         Ok(())
     }
@@ -316,13 +370,8 @@ impl Generator {
     pub(crate) fn generate_statements(&mut self, node: &AstTreeNode) -> Result<Vec<c::Statement>> {
         match node.node() {
             Node::Block(node) => Ok(vec![Statement::Block(self.generate_block(node)?)]),
-            Node::BreakLoop(_) => unimplemented!(),
-            Node::Calculate(_) => unimplemented!(),
-            Node::CallFunctionOfObject(_) => unimplemented!(),
             Node::CallFunctionOfPackage(node) => self.generate_call_function_of_package(node),
             Node::CallFunction(node) => self.generate_call_function(node),
-            Node::CallFunctionWithLambda(_) => unimplemented!(),
-            Node::ExportPackage(_) => unimplemented!(),
             Node::ReturnFromFunction(node) => {
                 let mut result = vec![];
 
@@ -340,13 +389,7 @@ impl Generator {
 
                 Ok(result)
             }
-            Node::ContinueLoop(_) => unimplemented!(),
-            Node::Compare(_) => unimplemented!(),
             Node::If(node) => self.generate_if(node),
-            Node::AccessVariable(_) => unimplemented!(),
-            Node::AccessVariableOfObject(_) => unimplemented!(),
-            Node::AccessVariableOfSelf(_) => unimplemented!(),
-            Node::Loop(_) => unimplemented!(),
             Node::DeclareVariable(node) => self.generate_declare_variable(node),
             Node::DeclareFunction(node) => {
                 let func_ident = self.string_table.get(node.function.0).to_string();
@@ -376,18 +419,9 @@ impl Generator {
                 //     statements,
                 // });
                 // Ok(vec![])
-                todo!()
+                Err(Error::Unsupported("DeclareFunction"))
             }
-            Node::DeclareExternalFunction(_) => unimplemented!(),
-            Node::DeclarePackage(_) => unimplemented!(),
-            Node::DeclareType(_) => unimplemented!(),
-            Node::InstantiateType(_) => unimplemented!(),
-            Node::DefineType(_) => unimplemented!(),
-            Node::InterpolateString(_) => unimplemented!(),
-            Node::LiteralBoolean(_) => unimplemented!(),
-            Node::LiteralNumber(_) => unimplemented!(),
-            Node::LiteralString(_) => unimplemented!(),
-            AstNode::Marker(_) => unreachable!(),
+            _ => Err(Error::Unsupported(node.kind())),
         }
     }
 
@@ -441,7 +475,7 @@ impl Generator {
                     }),
                 ))
             }
-            _ => unimplemented!("{:#?}", node),
+            _ => Err(Error::Unsupported(node.kind())),
         }
     }
 }
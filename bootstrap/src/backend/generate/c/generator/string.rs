@@ -141,10 +141,7 @@ impl Generator {
                             indent: Indent::none(),
                             identifier: format!(
                                 "{}.{}",
-                                self.scope
-                                    .get_variable(object)
-                                    .unwrap()
-                                    .to_string(&self.string_table),
+                                self.variable(object)?,
                                 self.string_table.get(variable.0)
                             ),
                         }),
@@ -200,7 +197,7 @@ impl Generator {
 
                 temp_variables.push(result_temp);
             } else {
-                unimplemented!("{node:#?}")
+                return Err(c::generator::Error::Unsupported(node.kind()));
             }
         }
 
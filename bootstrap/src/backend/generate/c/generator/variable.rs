@@ -7,7 +7,7 @@ use crate::backend::generate::c::{
 use crate::common::node::Node;
 use crate::common::node::Node::{LiteralBoolean, LiteralNumber, LiteralString};
 use crate::frontend::ast::{
-    AstAccessVariableNode, AstAccessVariableOfSelfNode, AstDeclareVariableNode,
+    AstAccessVariableNode, AstAccessVariableOfSelfNode, AstDeclareVariableNode, AstIdentifier,
     AstLiteralBooleanNode, AstLiteralNumberNode, AstLiteralStringNode,
 };
 
@@ -92,7 +92,17 @@ impl Generator {
             return Ok(statements);
         }
 
-        unimplemented!("{node:#?}");
+        Err(c::generator::Error::Unsupported(node.value.kind()))
+    }
+
+    // function arguments and variables of enclosing packages are not part of the scope yet
+    pub(crate) fn variable(&self, identifier: &AstIdentifier) -> c::generator::Result<String> {
+        self.scope
+            .get_variable(identifier)
+            .map(|variable| variable.to_string(&self.string_table))
+            .ok_or(c::generator::Error::Unsupported(
+                "Variable outside of the generated scope",
+            ))
     }
 
     pub(crate) fn generate_load_value(
@@ -101,11 +111,7 @@ impl Generator {
     ) -> c::generator::Result<c::Expression> {
         Ok(c::Expression::Variable(VariableExpression {
             indent: Indent::none(),
-            identifier: self
-                .scope
-                .get_variable(&node.variable)
-                .unwrap()
-                .to_string(&self.string_table),
+            identifier: self.variable(&node.variable)?,
         }))
     }
 
@@ -1,5 +1,7 @@
 pub use node::*;
 
+use std::fmt;
+
 use crate::backend::generate::c::emitter::emit;
 use crate::backend::generate::c::generator::generate;
 use crate::frontend::Ast;
@@ -11,13 +13,21 @@ mod node;
 
 #[derive(Debug)]
 pub enum Error {
-    // generator error
+    Generator(generator::Error),
     // writer error
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Generator(err) => write!(f, "{err}"),
+        }
+    }
+}
+
 impl From<generator::Error> for Error {
     fn from(value: generator::Error) -> Self {
-        todo!()
+        Self::Generator(value)
     }
 }
 
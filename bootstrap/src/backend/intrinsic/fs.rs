@@ -0,0 +1,357 @@
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use crate::backend::intrinsic::{list, string_argument, IntrinsicType, Intrinsics, Results};
+use crate::backend::run::value::{ObjectValue, Value};
+use crate::frontend::context::Context;
+
+/// Intrinsics of core::intrinsics::fs, everything which can fail returns a result. In C those
+/// return false or NULL instead and core_intrinsics_fs_error tells why
+pub(crate) fn register(intrinsics: &mut Intrinsics, ctx: &mut Context, results: Results) {
+    intrinsics.register_symbol(
+        "read_text",
+        &[IntrinsicType::String],
+        IntrinsicType::Result,
+        "core_intrinsics_fs_read_text",
+        move |args| {
+            let path = string_argument(args, 0)?;
+            Ok(results.from(fs::read_to_string(path).map(Value::String)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "write_text",
+        &[IntrinsicType::String, IntrinsicType::String],
+        IntrinsicType::Result,
+        "core_intrinsics_fs_write_text",
+        move |args| {
+            let (path, text) = (string_argument(args, 0)?, string_argument(args, 1)?);
+            Ok(results.from(write(&path, text.as_bytes(), false)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "append_text",
+        &[IntrinsicType::String, IntrinsicType::String],
+        IntrinsicType::Result,
+        "core_intrinsics_fs_append_text",
+        move |args| {
+            let (path, text) = (string_argument(args, 0)?, string_argument(args, 1)?);
+            Ok(results.from(write(&path, text.as_bytes(), true)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "read_bytes",
+        &[IntrinsicType::String],
+        IntrinsicType::Result,
+        "core_intrinsics_fs_read_bytes",
+        move |args| {
+            let path = string_argument(args, 0)?;
+            Ok(results.from(fs::read(path).map(|bytes| {
+                list(
+                    bytes
+                        .into_iter()
+                        .map(|byte| Value::Number(byte as f64))
+                        .collect(),
+                )
+            })))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "write_bytes",
+        &[IntrinsicType::String, IntrinsicType::List],
+        IntrinsicType::Result,
+        "core_intrinsics_fs_write_bytes",
+        move |args| {
+            let path = string_argument(args, 0)?;
+            Ok(results.from(bytes(&args[1]).and_then(|bytes| write(&path, &bytes, false))))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "append_bytes",
+        &[IntrinsicType::String, IntrinsicType::List],
+        IntrinsicType::Result,
+        "core_intrinsics_fs_append_bytes",
+        move |args| {
+            let path = string_argument(args, 0)?;
+            Ok(results.from(bytes(&args[1]).and_then(|bytes| write(&path, &bytes, true))))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "create_directory",
+        &[IntrinsicType::String],
+        IntrinsicType::Result,
+        "core_intrinsics_fs_create_directory",
+        move |args| {
+            let path = string_argument(args, 0)?;
+            Ok(results.from(fs::create_dir_all(path).map(|_| Value::Unit)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "list_directory",
+        &[IntrinsicType::String],
+        IntrinsicType::Result,
+        "core_intrinsics_fs_list_directory",
+        move |args| {
+            let path = string_argument(args, 0)?;
+            Ok(results.from(list_directory(&path)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "remove_directory",
+        &[IntrinsicType::String],
+        IntrinsicType::Result,
+        "core_intrinsics_fs_remove_directory",
+        move |args| {
+            let path = string_argument(args, 0)?;
+            Ok(results.from(fs::remove_dir_all(path).map(|_| Value::Unit)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "remove_file",
+        &[IntrinsicType::String],
+        IntrinsicType::Result,
+        "core_intrinsics_fs_remove_file",
+        move |args| {
+            let path = string_argument(args, 0)?;
+            Ok(results.from(fs::remove_file(path).map(|_| Value::Unit)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "path_exists",
+        &[IntrinsicType::String],
+        IntrinsicType::Bool,
+        "core_intrinsics_fs_path_exists",
+        |args| Ok(Value::Bool(Path::new(&string_argument(args, 0)?).exists())),
+    );
+
+    intrinsics.register_symbol(
+        "is_file",
+        &[IntrinsicType::String],
+        IntrinsicType::Bool,
+        "core_intrinsics_fs_is_file",
+        |args| Ok(Value::Bool(Path::new(&string_argument(args, 0)?).is_file())),
+    );
+
+    intrinsics.register_symbol(
+        "is_directory",
+        &[IntrinsicType::String],
+        IntrinsicType::Bool,
+        "core_intrinsics_fs_is_directory",
+        |args| Ok(Value::Bool(Path::new(&string_argument(args, 0)?).is_dir())),
+    );
+
+    let properties = [
+        ctx.string_table.push_str("size"),
+        ctx.string_table.push_str("is_file"),
+        ctx.string_table.push_str("is_directory"),
+        ctx.string_table.push_str("modified"),
+    ];
+    intrinsics.register_symbol(
+        "metadata",
+        &[IntrinsicType::String],
+        IntrinsicType::Result,
+        "core_intrinsics_fs_metadata",
+        move |args| {
+            let path = string_argument(args, 0)?;
+            Ok(results.from(fs::metadata(path).map(|metadata| {
+                // seconds since the unix epoch, 0 if the platform does not know
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                    .map(|modified| modified.as_secs_f64())
+                    .unwrap_or_default();
+
+                let [size, is_file, is_directory, modified_id] = properties;
+                let mut result = ObjectValue::new();
+                result.set_property(size, Value::Number(metadata.len() as f64));
+                result.set_property(is_file, Value::Bool(metadata.is_file()));
+                result.set_property(is_directory, Value::Bool(metadata.is_dir()));
+                result.set_property(modified_id, Value::Number(modified));
+                Value::Object(result)
+            })))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "join_path",
+        &[IntrinsicType::String, IntrinsicType::String],
+        IntrinsicType::String,
+        "core_intrinsics_fs_join_path",
+        |args| {
+            let (base, path) = (string_argument(args, 0)?, string_argument(args, 1)?);
+            Ok(Value::String(
+                Path::new(&base).join(path).to_string_lossy().to_string(),
+            ))
+        },
+    );
+}
+
+fn write(path: &str, content: &[u8], append: bool) -> io::Result<Value> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)?;
+    file.write_all(content)?;
+    Ok(Value::Unit)
+}
+
+/// Names of the entries of a directory, sorted so that the result does not depend on the platform
+fn list_directory(path: &str) -> io::Result<Value> {
+    let mut names = vec![];
+    for entry in fs::read_dir(path)? {
+        names.push(entry?.file_name().to_string_lossy().to_string());
+    }
+    names.sort();
+    Ok(list(names.into_iter().map(Value::String).collect()))
+}
+
+fn bytes(value: &Value) -> io::Result<Vec<u8>> {
+    let Value::List(list) = value else {
+        panic!("not list")
+    };
+    list.0
+        .borrow()
+        .iter()
+        .map(|value| match value {
+            Value::Number(byte) if (0.0..=255.0).contains(byte) && byte.fract() == 0.0 => {
+                Ok(*byte as u8)
+            }
+            value => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} is not a byte", value.to_string()),
+            )),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::backend::intrinsic::Intrinsics;
+    use crate::backend::run::value::Value;
+    use crate::frontend::context::Context;
+
+    fn call(intrinsics: &Intrinsics, name: &str, args: &[Value]) -> Value {
+        (intrinsics.get(name).unwrap().function.0)(args).unwrap()
+    }
+
+    fn string(value: &str) -> Value {
+        Value::String(value.to_string())
+    }
+
+    fn property(ctx: &mut Context, value: &Value, name: &str) -> Value {
+        let Value::Object(object) = value else {
+            panic!("not object")
+        };
+        object
+            .get_property(&ctx.string_table.push_str(name))
+            .cloned()
+            .unwrap()
+    }
+
+    #[test]
+    fn writes_and_reads_files() {
+        let mut ctx = Context::new();
+        let intrinsics = Intrinsics::new(&mut ctx, false);
+        let dir = std::env::temp_dir().join("elodie_intrinsic_fs");
+        let _ = fs::remove_dir_all(&dir);
+        let file = dir.join("nested/file.txt").to_string_lossy().to_string();
+
+        let result = call(&intrinsics, "write_text", &[string(&file), string("a")]);
+        assert!(matches!(
+            property(&mut ctx, &result, "ok"),
+            Value::Bool(false)
+        ));
+
+        let result = call(
+            &intrinsics,
+            "create_directory",
+            &[string(&dir.join("nested").to_string_lossy())],
+        );
+        assert!(matches!(
+            property(&mut ctx, &result, "ok"),
+            Value::Bool(true)
+        ));
+
+        call(&intrinsics, "write_text", &[string(&file), string("a\n")]);
+        call(&intrinsics, "append_text", &[string(&file), string("b")]);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "a\nb");
+
+        let result = call(&intrinsics, "read_text", &[string(&file)]);
+        assert_eq!(property(&mut ctx, &result, "value").to_string(), "a\nb");
+        assert_eq!(property(&mut ctx, &result, "error").to_string(), "");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn round_trips_text_with_backslashes() {
+        let mut ctx = Context::new();
+        let intrinsics = Intrinsics::new(&mut ctx, false);
+        let dir = std::env::temp_dir().join("elodie_intrinsic_fs_backslashes");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("file.txt").to_string_lossy().to_string();
+        let copy = dir.join("copy.txt").to_string_lossy().to_string();
+        fs::write(&file, "C:\\new\\table \\n \\\\\n").unwrap();
+
+        let read = call(&intrinsics, "read_text", &[string(&file)]);
+        let text = property(&mut ctx, &read, "value");
+        call(&intrinsics, "write_text", &[string(&copy), text.clone()]);
+        call(&intrinsics, "append_text", &[string(&copy), text]);
+
+        let original = fs::read_to_string(&file).unwrap();
+        assert_eq!(fs::read_to_string(&copy).unwrap(), original.repeat(2));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reports_failures_as_results() {
+        let mut ctx = Context::new();
+        let intrinsics = Intrinsics::new(&mut ctx, false);
+
+        let result = call(&intrinsics, "read_text", &[string("/does/not/exist")]);
+        assert!(matches!(
+            property(&mut ctx, &result, "ok"),
+            Value::Bool(false)
+        ));
+        assert!(matches!(property(&mut ctx, &result, "value"), Value::Unit));
+        assert!(!property(&mut ctx, &result, "error").to_string().is_empty());
+    }
+
+    #[test]
+    fn has_symbols_of_the_sysroot() {
+        let mut ctx = Context::new();
+        let intrinsics = Intrinsics::new(&mut ctx, false);
+
+        assert_eq!(
+            intrinsics.symbol("path_exists"),
+            Ok("core_intrinsics_fs_path_exists")
+        );
+        assert_eq!(
+            intrinsics.symbol("read_text"),
+            Ok("core_intrinsics_fs_read_text")
+        );
+        assert_eq!(
+            intrinsics.symbol("metadata"),
+            Ok("core_intrinsics_fs_metadata")
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use std::io::{self, BufRead, Read, Write};
+
+use crate::backend::intrinsic::{list, string_argument, IntrinsicType, Intrinsics, Results};
+use crate::backend::run::value::Value;
+
+/// Intrinsics of core::intrinsics::io besides print, reading stdin returns a result. In C reading
+/// returns NULL instead and core_intrinsics_io_error tells why
+pub(crate) fn register(intrinsics: &mut Intrinsics, results: Results) {
+    intrinsics.register_symbol(
+        "read_line",
+        &[],
+        IntrinsicType::Result,
+        "core_intrinsics_io_read_line",
+        move |_| Ok(results.from(read_line(&mut io::stdin().lock()))),
+    );
+
+    intrinsics.register_symbol(
+        "read_all",
+        &[],
+        IntrinsicType::Result,
+        "core_intrinsics_io_read_all",
+        move |_| Ok(results.from(read_all(&mut io::stdin().lock()))),
+    );
+
+    intrinsics.register_symbol(
+        "read_lines",
+        &[],
+        IntrinsicType::Result,
+        "core_intrinsics_io_read_lines",
+        move |_| Ok(results.from(read_lines(&mut io::stdin().lock()))),
+    );
+
+    intrinsics.register_symbol(
+        "eprint",
+        &[IntrinsicType::String],
+        IntrinsicType::Unit,
+        "core_intrinsics_io_eprint",
+        |args| {
+            let mut stderr = io::stderr().lock();
+            let _ = stderr.write_all(string_argument(args, 0)?.as_bytes());
+            let _ = stderr.flush();
+            Ok(Value::Unit)
+        },
+    );
+}
+
+/// Next line without its line break, fails once the input ended
+fn read_line(input: &mut impl BufRead) -> io::Result<Value> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "end of input"));
+    }
+    Ok(Value::String(trim_line_break(line)))
+}
+
+fn read_all(input: &mut impl Read) -> io::Result<Value> {
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+    Ok(Value::String(text))
+}
+
+/// Remaining lines without their line breaks
+fn read_lines(input: &mut impl BufRead) -> io::Result<Value> {
+    let lines = input
+        .lines()
+        .map(|line| line.map(trim_line_break).map(Value::String))
+        .collect::<io::Result<Vec<_>>>()?;
+    Ok(list(lines))
+}
+
+fn trim_line_break(mut line: String) -> String {
+    if line.ends_with('\n') {
+        line.pop();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::backend::intrinsic::io::{read_all, read_line, read_lines};
+    use crate::backend::intrinsic::Intrinsics;
+    use crate::backend::run::value::Value;
+    use crate::frontend::context::Context;
+
+    #[test]
+    fn reads_lines_until_the_input_ends() {
+        let mut input = Cursor::new("first\r\nsecond\nlast");
+        assert_eq!(read_line(&mut input).unwrap().to_string(), "first");
+        assert_eq!(read_line(&mut input).unwrap().to_string(), "second");
+        assert_eq!(read_line(&mut input).unwrap().to_string(), "last");
+        assert_eq!(
+            read_line(&mut input).unwrap_err().to_string(),
+            "end of input"
+        );
+    }
+
+    #[test]
+    fn reads_remaining_input() {
+        let mut input = Cursor::new("first\nsecond\nthird\n");
+        read_line(&mut input).unwrap();
+
+        let Value::List(lines) = read_lines(&mut input).unwrap() else {
+            panic!("not list")
+        };
+        let lines: Vec<String> = lines.0.borrow().iter().map(Value::to_string).collect();
+        assert_eq!(lines, vec!["second", "third"]);
+
+        let mut input = Cursor::new("first\nsecond\n");
+        read_line(&mut input).unwrap();
+        assert_eq!(read_all(&mut input).unwrap().to_string(), "second\n");
+    }
+
+    #[test]
+    fn has_symbols_of_the_sysroot() {
+        let mut ctx = Context::new();
+        let intrinsics = Intrinsics::new(&mut ctx, false);
+
+        assert_eq!(intrinsics.symbol("eprint"), Ok("core_intrinsics_io_eprint"));
+        for name in ["read_line", "read_all", "read_lines"] {
+            assert_eq!(
+                intrinsics.symbol(name),
+                Ok(format!("core_intrinsics_io_{name}").as_str())
+            );
+        }
+    }
+}
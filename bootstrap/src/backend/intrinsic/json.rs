@@ -0,0 +1,718 @@
+use std::fmt;
+use std::fmt::Write;
+use std::rc::Rc;
+
+use crate::backend::intrinsic::{list, string_argument, Error, IntrinsicType, Intrinsics, Results};
+use crate::backend::run::value::{ListValue, ObjectValue, Value};
+use crate::common::{StringNames, StringTableId};
+use crate::frontend::context::Context;
+
+/// Intrinsics of core::intrinsics::json, only the interpreter has them. They read and build the
+/// std::json::Json values, members of objects are a std::collection::map::Map
+pub(crate) fn register(intrinsics: &mut Intrinsics, ctx: &mut Context, results: Results) {
+    let names = Names::new(ctx);
+
+    let parse_names = names.clone();
+    intrinsics.register(
+        "json_parse",
+        &[IntrinsicType::String],
+        IntrinsicType::Result,
+        move |args| {
+            Ok(results.from(
+                parse(&string_argument(args, 0)?).map(|json| parse_names.json_to_value(json)),
+            ))
+        },
+    );
+
+    let of_names = names.clone();
+    intrinsics.register(
+        "json_of",
+        &[IntrinsicType::Any],
+        IntrinsicType::Result,
+        move |args| {
+            Ok(results.from(
+                of_names
+                    .value_to_json(&args[0], 0)
+                    .map(|json| of_names.json_to_value(json)),
+            ))
+        },
+    );
+
+    let record_names = names.clone();
+    intrinsics.register(
+        "json_record",
+        &[IntrinsicType::Any, IntrinsicType::Any],
+        IntrinsicType::Result,
+        move |args| Ok(results.from(record_names.json_to_record(&args[0], &args[1]))),
+    );
+
+    intrinsics.register(
+        "json_serialize",
+        &[IntrinsicType::Any, IntrinsicType::Bool],
+        IntrinsicType::Result,
+        move |args| {
+            let Some(Value::Bool(pretty)) = args.get(1) else {
+                return Err(Error::Argument {
+                    index: 1,
+                    expected: IntrinsicType::Bool,
+                }
+                .into());
+            };
+            Ok(results.from(
+                names
+                    .value_to_json(&args[0], 0)
+                    .map(|json| Value::String(serialize(&json, *pretty))),
+            ))
+        },
+    );
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// Members in the order of the text
+    Object(Vec<(String, Json)>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ParseError {
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ConvertError {
+    /// What got passed that json has no representation for
+    Unsupported(&'static str),
+    /// Lists which contain themselves end up here as well
+    TooDeep,
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConvertError::Unsupported(what) => write!(f, "{what} can not be converted to json"),
+            ConvertError::TooDeep => write!(
+                f,
+                "values nested deeper than {MAX_DEPTH} levels can not be converted to json"
+            ),
+        }
+    }
+}
+
+/// Ids of the types and properties the json values of the language consist of, records convert
+/// by the names of their fields
+#[derive(Debug, Clone)]
+struct Names {
+    json: StringTableId,
+    kind: StringTableId,
+    value: StringTableId,
+    map: StringTableId,
+    key_list: StringTableId,
+    value_list: StringTableId,
+    strings: StringNames,
+}
+
+impl Names {
+    fn new(ctx: &mut Context) -> Self {
+        Self {
+            json: ctx.string_table.push_str("Json"),
+            kind: ctx.string_table.push_str("kind"),
+            value: ctx.string_table.push_str("value"),
+            map: ctx.string_table.push_str("Map"),
+            key_list: ctx.string_table.push_str("key_list"),
+            value_list: ctx.string_table.push_str("value_list"),
+            strings: ctx.string_table.names(),
+        }
+    }
+
+    fn json_to_value(&self, json: Json) -> Value {
+        let (kind, value) = match json {
+            Json::Null => ("null", Value::Unit),
+            Json::Bool(value) => ("bool", Value::Bool(value)),
+            Json::Number(value) => ("number", Value::Number(value)),
+            Json::String(value) => ("string", Value::String(value)),
+            Json::Array(values) => (
+                "array",
+                list(
+                    values
+                        .into_iter()
+                        .map(|json| self.json_to_value(json))
+                        .collect(),
+                ),
+            ),
+            Json::Object(members) => {
+                let (keys, values): (Vec<_>, Vec<_>) = members
+                    .into_iter()
+                    .map(|(key, json)| (Value::String(key), self.json_to_value(json)))
+                    .unzip();
+                let mut map = ObjectValue::new();
+                map.type_name = Some(self.map);
+                map.set_property(self.key_list, list(keys));
+                map.set_property(self.value_list, list(values));
+                ("object", Value::Object(map))
+            }
+        };
+
+        let mut result = ObjectValue::new();
+        result.type_name = Some(self.json);
+        result.set_property(self.kind, Value::String(kind.to_string()));
+        result.set_property(self.value, value);
+        Value::Object(result)
+    }
+
+    /// Json values stay as they are, bools, numbers, strings and Unit become json values and so
+    /// do lists and maps of them. Records become objects with a member per field, in the order of
+    /// the field names. Lists, maps and records nest at most MAX_DEPTH deep, like parsed ones
+    fn value_to_json(&self, value: &Value, depth: usize) -> Result<Json, ConvertError> {
+        let nests = match value {
+            Value::List(_) => true,
+            Value::Object(object) => object.type_name != Some(self.json),
+            _ => false,
+        };
+        if nests && depth == MAX_DEPTH {
+            return Err(ConvertError::TooDeep);
+        }
+        match value {
+            Value::Unit => Ok(Json::Null),
+            Value::Bool(value) => Ok(Json::Bool(*value)),
+            Value::Number(value) | Value::F64(value) => Ok(Json::Number(*value)),
+            Value::String(value) => Ok(Json::String(value.clone())),
+            Value::List(values) => Ok(Json::Array(
+                values
+                    .0
+                    .borrow()
+                    .iter()
+                    .map(|value| self.value_to_json(value, depth + 1))
+                    .collect::<Result<_, _>>()?,
+            )),
+            Value::Object(object) if object.type_name == Some(self.json) => {
+                self.value_to_json(object.get_property(&self.value).unwrap(), depth)
+            }
+            Value::Object(object) if object.type_name == Some(self.map) => {
+                let (keys, values) = self.members(object);
+                let (keys, values) = (keys.0.borrow(), values.0.borrow());
+                Ok(Json::Object(
+                    keys.iter()
+                        .zip(values.iter())
+                        .map(|(key, value)| match key {
+                            Value::String(key) => {
+                                Ok((key.clone(), self.value_to_json(value, depth + 1)?))
+                            }
+                            _ => Err(ConvertError::Unsupported("a key which is no string")),
+                        })
+                        .collect::<Result<_, _>>()?,
+                ))
+            }
+            Value::Object(object) => Ok(Json::Object(
+                self.fields(object)
+                    .into_iter()
+                    .map(|(_, name, value)| {
+                        Ok((name.to_string(), self.value_to_json(value, depth + 1)?))
+                    })
+                    .collect::<Result<_, _>>()?,
+            )),
+            Value::Function(_) => Err(ConvertError::Unsupported("a function")),
+            Value::Package(_) => Err(ConvertError::Unsupported("a package")),
+            Value::Tuple(_) => Err(ConvertError::Unsupported("a tuple")),
+        }
+    }
+
+    /// Copy of the record whose fields got replaced by the members of the json object with the
+    /// same name. Fields holding a record convert the same way, the others get the value of the
+    /// member like Json.field() returns it
+    fn json_to_record(&self, json: &Value, record: &Value) -> Result<Value, String> {
+        let (Value::Object(json), Value::Object(record)) = (json, record) else {
+            panic!("not object")
+        };
+        let Some(Value::String(kind)) = json.get_property(&self.kind) else {
+            panic!("not json")
+        };
+        let Some(Value::Object(members)) = json.get_property(&self.value) else {
+            return Err(format!("{kind} is not an object"));
+        };
+        let (keys, values) = self.members(members);
+        let (keys, values) = (keys.0.borrow(), values.0.borrow());
+
+        let mut result = ObjectValue::new();
+        result.type_name = record.type_name;
+        for (id, name, field) in self.fields(record) {
+            let Some(member) = keys
+                .iter()
+                .position(|key| matches!(key, Value::String(key) if **key == *name))
+                .map(|idx| &values[idx])
+            else {
+                return Err(format!("field {name} is missing"));
+            };
+            let value = match (field, member) {
+                (Value::Object(object), _) if self.is_record(object) => self
+                    .json_to_record(member, field)
+                    .map_err(|err| format!("field {name} - {err}"))?,
+                (_, Value::Object(member)) => member.get_property(&self.value).unwrap().clone(),
+                _ => panic!("not json"),
+            };
+            result.set_property(id, value);
+        }
+        Ok(Value::Object(result))
+    }
+
+    fn is_record(&self, object: &ObjectValue) -> bool {
+        object.type_name.is_some()
+            && object.type_name != Some(self.json)
+            && object.type_name != Some(self.map)
+    }
+
+    /// Ids, names and values of the fields of a record, ordered by the name
+    fn fields<'a>(&self, object: &'a ObjectValue) -> Vec<(StringTableId, Rc<str>, &'a Value)> {
+        let mut result: Vec<_> = object
+            .properties
+            .iter()
+            .map(|(key, value)| (*key, self.strings.get(key), value))
+            .collect();
+        result.sort_by(|(_, left, _), (_, right, _)| left.cmp(right));
+        result
+    }
+
+    fn members(&self, map: &ObjectValue) -> (ListValue, ListValue) {
+        let (Some(Value::List(keys)), Some(Value::List(values))) = (
+            map.get_property(&self.key_list),
+            map.get_property(&self.value_list),
+        ) else {
+            panic!("not map")
+        };
+        (keys.clone(), values.clone())
+    }
+}
+
+/// Arrays and objects nest at most this deep, the parser recurses for each level
+const MAX_DEPTH: usize = 512;
+
+fn parse(text: &str) -> Result<Json, ParseError> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        idx: 0,
+        depth: 0,
+    };
+    parser.whitespace();
+    let result = parser.value()?;
+    parser.whitespace();
+    if parser.idx < parser.chars.len() {
+        return Err(parser.error("expected end of input"));
+    }
+    Ok(result)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    idx: usize,
+    /// Arrays and objects the current char is in
+    depth: usize,
+}
+
+impl Parser {
+    fn value(&mut self) -> Result<Json, ParseError> {
+        match self.peek() {
+            Some('n') => self.literal("null", Json::Null),
+            Some('t') => self.literal("true", Json::Bool(true)),
+            Some('f') => self.literal("false", Json::Bool(false)),
+            Some('"') => Ok(Json::String(self.string()?)),
+            Some('[') => self.nested(Self::array),
+            Some('{') => self.nested(Self::object),
+            Some('-' | '0'..='9') => self.number(),
+            Some(_) => Err(self.error("expected a value")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    /// Array or object starting at the current char, unless it nests too deep
+    fn nested(
+        &mut self,
+        parse: fn(&mut Self) -> Result<Json, ParseError>,
+    ) -> Result<Json, ParseError> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error(&format!("nested deeper than {MAX_DEPTH} levels")));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn literal(&mut self, literal: &str, json: Json) -> Result<Json, ParseError> {
+        for expected in literal.chars() {
+            if self.peek() != Some(expected) {
+                return Err(self.error(&format!("expected {literal}")));
+            }
+            self.idx += 1;
+        }
+        Ok(json)
+    }
+
+    fn array(&mut self) -> Result<Json, ParseError> {
+        self.idx += 1;
+        let mut values = vec![];
+        self.whitespace();
+        if self.peek() == Some(']') {
+            self.idx += 1;
+            return Ok(Json::Array(values));
+        }
+        loop {
+            self.whitespace();
+            values.push(self.value()?);
+            self.whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(Json::Array(values)),
+                _ => return Err(self.error_before("expected , or ]")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, ParseError> {
+        self.idx += 1;
+        let mut members: Vec<(String, Json)> = vec![];
+        self.whitespace();
+        if self.peek() == Some('}') {
+            self.idx += 1;
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.error("expected a string as key"));
+            }
+            let key = self.string()?;
+            self.whitespace();
+            if self.next() != Some(':') {
+                return Err(self.error_before("expected :"));
+            }
+            self.whitespace();
+            let value = self.value()?;
+            // the last one of duplicated keys wins but keeps the position of the first one
+            match members.iter_mut().find(|(existing, _)| *existing == key) {
+                Some(member) => member.1 = value,
+                None => members.push((key, value)),
+            }
+            self.whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(Json::Object(members)),
+                _ => return Err(self.error_before("expected , or }")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.idx += 1;
+        let mut result = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(result),
+                Some('\\') => match self.next() {
+                    Some('"') => result.push('"'),
+                    Some('\\') => result.push('\\'),
+                    Some('/') => result.push('/'),
+                    Some('b') => result.push('\u{8}'),
+                    Some('f') => result.push('\u{c}'),
+                    Some('n') => result.push('\n'),
+                    Some('r') => result.push('\r'),
+                    Some('t') => result.push('\t'),
+                    Some('u') => result.push(self.unicode_escape()?),
+                    _ => return Err(self.error_before("invalid escape sequence")),
+                },
+                Some(char) if (char as u32) < 0x20 => {
+                    return Err(self.error_before("control character in string"))
+                }
+                Some(char) => result.push(char),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    /// Char of the \u escape whose u got consumed, surrogate pairs take two escapes
+    fn unicode_escape(&mut self) -> Result<char, ParseError> {
+        let high = self.hex()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error_before("invalid unicode escape"));
+        }
+        if self.next() != Some('\\') || self.next() != Some('u') {
+            return Err(self.error_before("expected low surrogate"));
+        }
+        let low = self.hex()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error_before("expected low surrogate"));
+        }
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+            .ok_or_else(|| self.error_before("invalid unicode escape"))
+    }
+
+    fn hex(&mut self) -> Result<u32, ParseError> {
+        let mut result = 0;
+        for _ in 0..4 {
+            let Some(digit) = self.next().and_then(|char| char.to_digit(16)) else {
+                return Err(self.error_before("expected 4 hex digits"));
+            };
+            result = result * 16 + digit;
+        }
+        Ok(result)
+    }
+
+    fn number(&mut self) -> Result<Json, ParseError> {
+        let start = self.idx;
+        if self.peek() == Some('-') {
+            self.idx += 1;
+        }
+        match self.peek() {
+            Some('0') => self.idx += 1,
+            Some('1'..='9') => self.digits(),
+            _ => return Err(self.error("expected a digit")),
+        }
+        if self.peek() == Some('.') {
+            self.idx += 1;
+            if !matches!(self.peek(), Some('0'..='9')) {
+                return Err(self.error("expected a digit"));
+            }
+            self.digits();
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            self.idx += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.idx += 1;
+            }
+            if !matches!(self.peek(), Some('0'..='9')) {
+                return Err(self.error("expected a digit"));
+            }
+            self.digits();
+        }
+        let text: String = self.chars[start..self.idx].iter().collect();
+        Ok(Json::Number(text.parse().unwrap()))
+    }
+
+    fn digits(&mut self) {
+        while matches!(self.peek(), Some('0'..='9')) {
+            self.idx += 1;
+        }
+    }
+
+    fn whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.idx += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.idx).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let result = self.peek();
+        if result.is_some() {
+            self.idx += 1;
+        }
+        result
+    }
+
+    /// Error at the current char
+    fn error(&self, message: &str) -> ParseError {
+        self.error_at(self.idx, message)
+    }
+
+    /// Error at the char which got consumed last
+    fn error_before(&self, message: &str) -> ParseError {
+        self.error_at(self.idx.saturating_sub(1), message)
+    }
+
+    /// Lines and columns start at 1, columns count chars
+    fn error_at(&self, idx: usize, message: &str) -> ParseError {
+        let before = &self.chars[..idx.min(self.chars.len())];
+        let line = before.iter().filter(|char| **char == '\n').count() + 1;
+        let column = before
+            .iter()
+            .rev()
+            .take_while(|char| **char != '\n')
+            .count()
+            + 1;
+        ParseError {
+            line,
+            column,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Compact without any whitespace, pretty indents by 2 spaces
+fn serialize(json: &Json, pretty: bool) -> String {
+    let mut result = String::new();
+    write_json(&mut result, json, pretty, 0);
+    result
+}
+
+fn write_json(out: &mut String, json: &Json, pretty: bool, depth: usize) {
+    match json {
+        Json::Null => out.push_str("null"),
+        Json::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+        // json has no infinity and nan
+        Json::Number(value) if !value.is_finite() => out.push_str("null"),
+        Json::Number(value) => write!(out, "{value}").unwrap(),
+        Json::String(value) => write_string(out, value),
+        Json::Array(values) => write_members(out, '[', ']', values, pretty, depth, |out, json| {
+            write_json(out, json, pretty, depth + 1)
+        }),
+        Json::Object(members) => {
+            write_members(out, '{', '}', members, pretty, depth, |out, (key, json)| {
+                write_string(out, key);
+                out.push_str(if pretty { ": " } else { ":" });
+                write_json(out, json, pretty, depth + 1)
+            })
+        }
+    }
+}
+
+fn write_members<T>(
+    out: &mut String,
+    open: char,
+    close: char,
+    members: &[T],
+    pretty: bool,
+    depth: usize,
+    write_member: impl Fn(&mut String, &T),
+) {
+    out.push(open);
+    for (idx, member) in members.iter().enumerate() {
+        if idx > 0 {
+            out.push(',');
+        }
+        if pretty {
+            out.push('\n');
+            out.push_str(&"  ".repeat(depth + 1));
+        }
+        write_member(out, member);
+    }
+    if pretty && !members.is_empty() {
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    }
+    out.push(close);
+}
+
+fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for char in value.chars() {
+        match char {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            char if (char as u32) < 0x20 => write!(out, "\\u{:04x}", char as u32).unwrap(),
+            char => out.push(char),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::intrinsic::json::{parse, serialize, ConvertError, Json, Names, MAX_DEPTH};
+    use crate::backend::intrinsic::list;
+    use crate::backend::run::value::Value;
+    use crate::frontend::context::Context;
+
+    fn error(text: &str) -> String {
+        parse(text).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn parses_values() {
+        assert_eq!(parse("null"), Ok(Json::Null));
+        assert_eq!(parse(" true "), Ok(Json::Bool(true)));
+        assert_eq!(parse("-1.5e2"), Ok(Json::Number(-150.0)));
+        assert_eq!(
+            parse(r#""a\"\n\u00e4\ud83d\ude00""#),
+            Ok(Json::String("a\"\nä😀".to_string()))
+        );
+        assert_eq!(
+            parse(r#"{"b": [1, {}], "a": null, "b": []}"#),
+            Ok(Json::Object(vec![
+                ("b".to_string(), Json::Array(vec![])),
+                ("a".to_string(), Json::Null),
+            ]))
+        );
+    }
+
+    #[test]
+    fn reports_line_and_column() {
+        assert_eq!(error(""), "line 1, column 1: unexpected end of input");
+        assert_eq!(error("[1,\n  2 3]"), "line 2, column 5: expected , or ]");
+        assert_eq!(error("{\n\"a\": tru}"), "line 2, column 9: expected true");
+        assert_eq!(error("{\"a\" 1}"), "line 1, column 6: expected :");
+        assert_eq!(error("01"), "line 1, column 2: expected end of input");
+        assert_eq!(error("\"abc"), "line 1, column 5: unterminated string");
+        assert_eq!(error("[1.]"), "line 1, column 4: expected a digit");
+    }
+
+    #[test]
+    fn limits_nesting() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(
+            error(&format!("{{\"a\":\n{}", nested(MAX_DEPTH))),
+            "line 2, column 512: nested deeper than 512 levels"
+        );
+    }
+
+    #[test]
+    fn limits_nesting_of_values() {
+        let names = Names::new(&mut Context::new());
+        let nested = |depth: usize| (0..depth).fold(Value::Unit, |value, _| list(vec![value]));
+        assert!(names.value_to_json(&nested(MAX_DEPTH), 0).is_ok());
+        assert_eq!(
+            names.value_to_json(&nested(MAX_DEPTH + 1), 0),
+            Err(ConvertError::TooDeep)
+        );
+
+        let cyclic = list(vec![]);
+        let Value::List(values) = &cyclic else {
+            unreachable!()
+        };
+        values.0.borrow_mut().push(cyclic.clone());
+        assert_eq!(
+            names.value_to_json(&cyclic, 0).unwrap_err().to_string(),
+            "values nested deeper than 512 levels can not be converted to json"
+        );
+        values.0.borrow_mut().clear();
+    }
+
+    #[test]
+    fn serializes_compact_and_pretty() {
+        let json = parse(r#"{"name": "Elodie", "tags": ["a", 1.5], "empty": {}, "n": 2}"#).unwrap();
+        assert_eq!(
+            serialize(&json, false),
+            r#"{"name":"Elodie","tags":["a",1.5],"empty":{},"n":2}"#
+        );
+        assert_eq!(
+            serialize(&json, true),
+            "{\n  \"name\": \"Elodie\",\n  \"tags\": [\n    \"a\",\n    1.5\n  ],\n  \"empty\": {},\n  \"n\": 2\n}"
+        );
+        assert_eq!(
+            serialize(&Json::String("\"\\\n\u{1}".to_string()), false),
+            r#""\"\\\n\u0001""#
+        );
+        assert_eq!(serialize(&Json::Number(f64::NAN), false), "null");
+    }
+}
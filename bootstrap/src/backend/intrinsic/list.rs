@@ -0,0 +1,296 @@
+use std::cmp::Ordering;
+
+use crate::backend::intrinsic::{list, number_argument, Error, IntrinsicType, Intrinsics, Results};
+use crate::backend::run::value::{ListValue, Value};
+
+/// Intrinsics of lists, only the interpreter has them. Positions start at 1, removing from or
+/// setting a position outside of the list returns a failed result
+pub(crate) fn register(intrinsics: &mut Intrinsics, results: Results) {
+    intrinsics.register(
+        "list_length",
+        &[IntrinsicType::List],
+        IntrinsicType::Number,
+        |args| {
+            Ok(Value::Number(
+                list_argument(args, 0)?.0.borrow().len() as f64
+            ))
+        },
+    );
+
+    intrinsics.register(
+        "list_append",
+        &[IntrinsicType::List, IntrinsicType::Any],
+        IntrinsicType::Unit,
+        |args| {
+            let arg = args.get(1).cloned().unwrap();
+            list_argument(args, 0)?.0.borrow_mut().push(arg);
+            Ok(Value::Unit)
+        },
+    );
+
+    intrinsics.register(
+        "list_get",
+        &[IntrinsicType::List, IntrinsicType::Number],
+        IntrinsicType::Any,
+        |args| {
+            let idx = number_argument(args, 1)?;
+            Ok(list_argument(args, 0)?
+                .0
+                .borrow()
+                .get(idx as usize - 1)
+                .cloned()
+                .unwrap())
+        },
+    );
+
+    intrinsics.register(
+        "list_insert",
+        &[
+            IntrinsicType::List,
+            IntrinsicType::Number,
+            IntrinsicType::Any,
+        ],
+        IntrinsicType::Unit,
+        |args| {
+            let mut values = list_argument(args, 0)?.0.borrow_mut();
+            let idx = (number_argument(args, 1)?.max(1.0) as usize - 1).min(values.len());
+            values.insert(idx, args.get(2).cloned().unwrap());
+            Ok(Value::Unit)
+        },
+    );
+
+    intrinsics.register(
+        "list_remove",
+        &[IntrinsicType::List, IntrinsicType::Number],
+        IntrinsicType::Result,
+        move |args| {
+            let mut values = list_argument(args, 0)?.0.borrow_mut();
+            let idx = number_argument(args, 1)?;
+            if idx < 1.0 || idx > values.len() as f64 {
+                return Ok(results.error(format!(
+                    "position {idx} is outside of a list of length {}",
+                    values.len()
+                )));
+            }
+            Ok(results.ok(values.remove(idx as usize - 1)))
+        },
+    );
+
+    intrinsics.register(
+        "list_set",
+        &[
+            IntrinsicType::List,
+            IntrinsicType::Number,
+            IntrinsicType::Any,
+        ],
+        IntrinsicType::Result,
+        move |args| {
+            let mut values = list_argument(args, 0)?.0.borrow_mut();
+            let idx = number_argument(args, 1)?;
+            if idx < 1.0 || idx > values.len() as f64 {
+                return Ok(results.error(format!(
+                    "position {idx} is outside of a list of length {}",
+                    values.len()
+                )));
+            }
+            let value = args.get(2).cloned().unwrap();
+            Ok(results.ok(std::mem::replace(&mut values[idx as usize - 1], value)))
+        },
+    );
+
+    intrinsics.register(
+        "list_pop",
+        &[IntrinsicType::List],
+        IntrinsicType::Result,
+        move |args| {
+            Ok(match list_argument(args, 0)?.0.borrow_mut().pop() {
+                Some(value) => results.ok(value),
+                None => results.error("list is empty"),
+            })
+        },
+    );
+
+    intrinsics.register(
+        "list_slice",
+        &[
+            IntrinsicType::List,
+            IntrinsicType::Number,
+            IntrinsicType::Number,
+        ],
+        IntrinsicType::List,
+        |args| {
+            let values = list_argument(args, 0)?.0.borrow();
+            let (from, to) = (number_argument(args, 1)?, number_argument(args, 2)?);
+            Ok(list(slice(&values, from, to)))
+        },
+    );
+
+    intrinsics.register(
+        "list_index_of",
+        &[IntrinsicType::List, IntrinsicType::Any],
+        IntrinsicType::Number,
+        |args| {
+            let value = args.get(1).unwrap();
+            Ok(Value::Number(
+                index_of(&list_argument(args, 0)?.0.borrow(), value) as f64,
+            ))
+        },
+    );
+
+    intrinsics.register(
+        "list_contains",
+        &[IntrinsicType::List, IntrinsicType::Any],
+        IntrinsicType::Bool,
+        |args| {
+            let value = args.get(1).unwrap();
+            Ok(Value::Bool(
+                index_of(&list_argument(args, 0)?.0.borrow(), value) > 0,
+            ))
+        },
+    );
+
+    intrinsics.register(
+        "list_sort",
+        &[IntrinsicType::List],
+        IntrinsicType::Unit,
+        |args| {
+            list_argument(args, 0)?.0.borrow_mut().sort_by(compare);
+            Ok(Value::Unit)
+        },
+    );
+
+    intrinsics.register(
+        "list_reverse",
+        &[IntrinsicType::List],
+        IntrinsicType::Unit,
+        |args| {
+            list_argument(args, 0)?.0.borrow_mut().reverse();
+            Ok(Value::Unit)
+        },
+    );
+}
+
+fn list_argument(args: &[Value], index: usize) -> crate::backend::run::Result<&ListValue> {
+    let Some(Value::List(list)) = args.get(index) else {
+        return Err(Error::Argument {
+            index,
+            expected: IntrinsicType::List,
+        }
+        .into());
+    };
+    Ok(list)
+}
+
+/// Values from one position up to and including the other, positions outside of the list get
+/// clamped to it
+fn slice(values: &[Value], from: f64, to: f64) -> Vec<Value> {
+    let from = from.max(1.0) as usize;
+    let to = (to.max(0.0) as usize).min(values.len());
+    if to < from {
+        return vec![];
+    }
+    values[from - 1..to].to_vec()
+}
+
+/// Position of the first equal value, 0 if the list does not contain it
+fn index_of(values: &[Value], value: &Value) -> usize {
+    values
+        .iter()
+        .position(|candidate| equals(candidate, value))
+        .map_or(0, |idx| idx + 1)
+}
+
+/// Bools, numbers and strings are equal by value, lists by their values and everything else never
+fn equals(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Bool(l), Value::Bool(r)) => l == r,
+        (Value::Number(l), Value::Number(r)) | (Value::F64(l), Value::F64(r)) => l == r,
+        (Value::String(l), Value::String(r)) => l == r,
+        (Value::List(l), Value::List(r)) => {
+            let (l, r) = (l.0.borrow(), r.0.borrow());
+            l.len() == r.len() && l.iter().zip(r.iter()).all(|(l, r)| equals(l, r))
+        }
+        _ => false,
+    }
+}
+
+/// Orders bools, numbers and strings among themselves, other values keep their order
+fn compare(left: &Value, right: &Value) -> Ordering {
+    match (left, right) {
+        (Value::Bool(l), Value::Bool(r)) => l.cmp(r),
+        (Value::Number(l), Value::Number(r)) | (Value::F64(l), Value::F64(r)) => {
+            l.partial_cmp(r).unwrap_or(Ordering::Equal)
+        }
+        (Value::String(l), Value::String(r)) => l.cmp(r),
+        _ => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::intrinsic::list;
+    use crate::backend::intrinsic::list::{compare, equals, index_of, slice};
+    use crate::backend::run::value::Value;
+
+    fn numbers(values: &[f64]) -> Vec<Value> {
+        values.iter().copied().map(Value::Number).collect()
+    }
+
+    fn to_numbers(values: &[Value]) -> Vec<f64> {
+        values
+            .iter()
+            .map(|value| match value {
+                Value::Number(number) => *number,
+                _ => panic!("not a number"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn slices_values() {
+        let values = numbers(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(to_numbers(&slice(&values, 2.0, 3.0)), vec![2.0, 3.0]);
+        assert_eq!(
+            to_numbers(&slice(&values, 0.0, 100.0)),
+            vec![1.0, 2.0, 3.0, 4.0]
+        );
+        assert!(slice(&values, 3.0, 2.0).is_empty());
+        assert!(slice(&values, 5.0, 6.0).is_empty());
+    }
+
+    #[test]
+    fn finds_values() {
+        let values = vec![
+            Value::String("Elodie".to_string()),
+            Value::Number(2.0),
+            Value::Bool(true),
+        ];
+        assert_eq!(index_of(&values, &Value::Number(2.0)), 2);
+        assert_eq!(index_of(&values, &Value::Bool(true)), 3);
+        assert_eq!(index_of(&values, &Value::String("Elodie".to_string())), 1);
+        assert_eq!(index_of(&values, &Value::String("2".to_string())), 0);
+    }
+
+    #[test]
+    fn compares_lists_by_their_values() {
+        assert!(equals(
+            &list(numbers(&[1.0, 2.0])),
+            &list(numbers(&[1.0, 2.0]))
+        ));
+        assert!(!equals(&list(numbers(&[1.0, 2.0])), &list(numbers(&[1.0]))));
+    }
+
+    #[test]
+    fn sorts_values_of_the_same_type() {
+        let mut values = numbers(&[3.0, 1.0, 2.0]);
+        values.sort_by(compare);
+        assert_eq!(to_numbers(&values), vec![1.0, 2.0, 3.0]);
+
+        let mut values = [
+            Value::String("b".to_string()),
+            Value::String("a".to_string()),
+        ];
+        values.sort_by(compare);
+        assert_eq!(values[0].to_string(), "a");
+    }
+}
@@ -0,0 +1,258 @@
+use crate::backend::intrinsic::{number_argument, IntrinsicType, Intrinsics};
+use crate::backend::run::value::Value;
+
+/// Intrinsics of core::intrinsics::math, they behave like the functions of C's math.h. The
+/// integer ones truncate their arguments and return NaN where there is no result
+pub(crate) fn register(intrinsics: &mut Intrinsics) {
+    unary(
+        intrinsics,
+        "cos_f64",
+        "core_intrinsics_math_cos_f64",
+        f64::cos,
+    );
+    unary(
+        intrinsics,
+        "sin_f64",
+        "core_intrinsics_math_sin_f64",
+        f64::sin,
+    );
+    unary(
+        intrinsics,
+        "tan_f64",
+        "core_intrinsics_math_tan_f64",
+        f64::tan,
+    );
+    unary(
+        intrinsics,
+        "sqrt_f64",
+        "core_intrinsics_math_sqrt_f64",
+        f64::sqrt,
+    );
+    unary(
+        intrinsics,
+        "exp_f64",
+        "core_intrinsics_math_exp_f64",
+        f64::exp,
+    );
+    unary(
+        intrinsics,
+        "log_f64",
+        "core_intrinsics_math_log_f64",
+        f64::ln,
+    );
+    unary(
+        intrinsics,
+        "floor_f64",
+        "core_intrinsics_math_floor_f64",
+        f64::floor,
+    );
+    unary(
+        intrinsics,
+        "ceil_f64",
+        "core_intrinsics_math_ceil_f64",
+        f64::ceil,
+    );
+    unary(
+        intrinsics,
+        "round_f64",
+        "core_intrinsics_math_round_f64",
+        f64::round,
+    );
+    unary(
+        intrinsics,
+        "abs_f64",
+        "core_intrinsics_math_abs_f64",
+        f64::abs,
+    );
+
+    binary(
+        intrinsics,
+        "pow_f64",
+        "core_intrinsics_math_pow_f64",
+        f64::powf,
+    );
+    binary(
+        intrinsics,
+        "min_f64",
+        "core_intrinsics_math_min_f64",
+        f64::min,
+    );
+    binary(
+        intrinsics,
+        "max_f64",
+        "core_intrinsics_math_max_f64",
+        f64::max,
+    );
+
+    intrinsics.register_symbol(
+        "clamp_f64",
+        &[IntrinsicType::F64, IntrinsicType::F64, IntrinsicType::F64],
+        IntrinsicType::F64,
+        "core_intrinsics_math_clamp_f64",
+        |args| {
+            let (x, min, max) = (
+                number_argument(args, 0)?,
+                number_argument(args, 1)?,
+                number_argument(args, 2)?,
+            );
+            Ok(Value::Number(clamp(x, min, max)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "gcd",
+        &[IntrinsicType::Number, IntrinsicType::Number],
+        IntrinsicType::Number,
+        "core_intrinsics_math_gcd",
+        |args| {
+            Ok(Value::Number(gcd(
+                number_argument(args, 0)?,
+                number_argument(args, 1)?,
+            )))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "mod_pow",
+        &[
+            IntrinsicType::Number,
+            IntrinsicType::Number,
+            IntrinsicType::Number,
+        ],
+        IntrinsicType::Number,
+        "core_intrinsics_math_mod_pow",
+        |args| {
+            Ok(Value::Number(mod_pow(
+                number_argument(args, 0)?,
+                number_argument(args, 1)?,
+                number_argument(args, 2)?,
+            )))
+        },
+    );
+}
+
+fn unary(intrinsics: &mut Intrinsics, name: &'static str, symbol: &'static str, f: fn(f64) -> f64) {
+    intrinsics.register_symbol(
+        name,
+        &[IntrinsicType::F64],
+        IntrinsicType::F64,
+        symbol,
+        move |args| Ok(Value::Number(f(number_argument(args, 0)?))),
+    );
+}
+
+fn binary(
+    intrinsics: &mut Intrinsics,
+    name: &'static str,
+    symbol: &'static str,
+    f: fn(f64, f64) -> f64,
+) {
+    intrinsics.register_symbol(
+        name,
+        &[IntrinsicType::F64, IntrinsicType::F64],
+        IntrinsicType::F64,
+        symbol,
+        move |args| {
+            Ok(Value::Number(f(
+                number_argument(args, 0)?,
+                number_argument(args, 1)?,
+            )))
+        },
+    );
+}
+
+/// Unlike f64::clamp it does not panic if min is larger than max, max wins like with fmin(fmax())
+fn clamp(x: f64, min: f64, max: f64) -> f64 {
+    x.max(min).min(max)
+}
+
+/// Greatest common divisor of the truncated values, 0 if both are 0
+fn gcd(a: f64, b: f64) -> f64 {
+    let (mut a, mut b) = (
+        (a.trunc() as i64).unsigned_abs(),
+        (b.trunc() as i64).unsigned_abs(),
+    );
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a as f64
+}
+
+/// Integers beyond 2^53 can not be told apart from their neighbours as f64
+const MAX_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+/// base to the power of exponent modulo modulus without overflowing, NaN for a negative exponent,
+/// a modulus which is not positive or any value beyond 2^53
+fn mod_pow(base: f64, exponent: f64, modulus: f64) -> f64 {
+    let (base, exponent, modulus) = (base.trunc(), exponent.trunc(), modulus.trunc());
+    let within = |x: f64, min: f64| (min..=MAX_INTEGER).contains(&x);
+    if !within(base, -MAX_INTEGER) || !within(exponent, 0.0) || !within(modulus, 1.0) {
+        return f64::NAN;
+    }
+    let modulus = modulus as i64;
+    let mut base = (base as i64).rem_euclid(modulus) as u128;
+    let modulus = modulus as u128;
+    let mut exponent = exponent as u64;
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::intrinsic::math::{clamp, gcd, mod_pow};
+
+    #[test]
+    fn clamps_into_range() {
+        assert_eq!(clamp(5.0, 1.0, 3.0), 3.0);
+        assert_eq!(clamp(-5.0, 1.0, 3.0), 1.0);
+        assert_eq!(clamp(2.0, 1.0, 3.0), 2.0);
+        assert_eq!(clamp(2.0, 3.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn finds_greatest_common_divisor() {
+        assert_eq!(gcd(12.0, 18.0), 6.0);
+        assert_eq!(gcd(-12.0, 18.0), 6.0);
+        assert_eq!(gcd(7.0, 0.0), 7.0);
+        assert_eq!(gcd(0.0, 0.0), 0.0);
+        assert_eq!(gcd(12.9, 18.0), 6.0);
+    }
+
+    #[test]
+    fn raises_to_power_modulo() {
+        assert_eq!(mod_pow(2.0, 10.0, 1000.0), 24.0);
+        assert_eq!(mod_pow(3.0, 0.0, 7.0), 1.0);
+        assert_eq!(mod_pow(3.0, 0.0, 1.0), 0.0);
+        assert_eq!(mod_pow(-2.0, 3.0, 5.0), 2.0);
+        assert_eq!(
+            mod_pow(4_294_967_291.0, 4_294_967_290.0, 4_294_967_291.0),
+            0.0
+        );
+        assert_eq!(mod_pow(2.0, 4_294_967_290.0, 4_294_967_291.0), 1.0);
+        assert!(mod_pow(2.0, -1.0, 5.0).is_nan());
+        assert!(mod_pow(2.0, 3.0, 0.0).is_nan());
+    }
+
+    #[test]
+    fn has_no_power_beyond_exact_integers() {
+        assert!(mod_pow(-1e19, 1.0, 1e20).is_nan());
+        assert!(mod_pow(2.0, 1e19, 7.0).is_nan());
+        assert!(mod_pow(2.0, 3.0, f64::INFINITY).is_nan());
+        assert!(mod_pow(f64::NAN, 3.0, 7.0).is_nan());
+        assert_eq!(
+            mod_pow(-9_007_199_254_740_992.0, 1.0, 9_007_199_254_740_991.0),
+            9_007_199_254_740_990.0
+        );
+        assert_eq!(
+            mod_pow(9_007_199_254_740_991.0, 2.0, 9_007_199_254_740_992.0),
+            1.0
+        );
+    }
+}
@@ -0,0 +1,414 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::backend::run::output;
+use crate::backend::run::value::{IntrinsicFunctionValue, ListValue, ObjectValue, Value};
+use crate::common::StringTableId;
+use crate::frontend::ast::AstType;
+use crate::frontend::context::Context;
+
+mod fs;
+mod io;
+mod json;
+mod list;
+mod math;
+mod process;
+pub(crate) mod random;
+mod string;
+mod time;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    Missing {
+        name: String,
+    },
+    Mismatched {
+        name: String,
+        expected: String,
+        declared: String,
+    },
+    /// Argument of a type the intrinsic does not take, counting from 0
+    Argument {
+        index: usize,
+        expected: IntrinsicType,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Missing { name } => write!(f, "intrinsic {name} does not exist"),
+            Error::Mismatched {
+                name,
+                expected,
+                declared,
+            } => write!(
+                f,
+                "intrinsic {name} is {expected} but got declared as {declared}"
+            ),
+            Error::Argument { index, expected } => {
+                write!(
+                    f,
+                    "argument {index} of intrinsic is not {}",
+                    expected.name()
+                )
+            }
+        }
+    }
+}
+
+/// Types of the values intrinsics take and return
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IntrinsicType {
+    Any,
+    Bool,
+    F64,
+    List,
+    Number,
+    Result,
+    String,
+    Unit,
+}
+
+impl IntrinsicType {
+    /// Whether a declaration may write the type like this, no type means Unit
+    fn accepts(&self, declared: Option<&AstType>) -> bool {
+        match (self, declared) {
+            (IntrinsicType::Any, _) => true,
+            (IntrinsicType::Unit, None) => true,
+            (IntrinsicType::Bool, Some(AstType::Boolean)) => true,
+            (IntrinsicType::Number, Some(AstType::Number)) => true,
+            (IntrinsicType::String, Some(AstType::String)) => true,
+            // the ast does not keep the name of object types yet
+            (
+                IntrinsicType::F64 | IntrinsicType::List | IntrinsicType::Result,
+                Some(AstType::Object),
+            ) => true,
+            _ => false,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            IntrinsicType::Any => "Any",
+            IntrinsicType::Bool => "Bool",
+            IntrinsicType::F64 => "F64",
+            IntrinsicType::List => "List",
+            IntrinsicType::Number => "Number",
+            IntrinsicType::Result => "Result",
+            IntrinsicType::String => "String",
+            IntrinsicType::Unit => "Unit",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Intrinsic {
+    pub arguments: Vec<IntrinsicType>,
+    pub result: IntrinsicType,
+    /// Function of the C sysroot implementing the intrinsic, none if only the interpreter has it
+    pub symbol: Option<&'static str>,
+    pub function: IntrinsicFunctionValue,
+}
+
+impl Intrinsic {
+    fn signature(&self) -> String {
+        let arguments: Vec<&str> = self.arguments.iter().map(IntrinsicType::name).collect();
+        format!("({}) -> {}", arguments.join(", "), self.result.name())
+    }
+}
+
+/// Functions the host provides, external functions of core and the intrinsics object resolve
+/// against it by name and the backends share it
+pub struct Intrinsics {
+    intrinsics: HashMap<&'static str, Intrinsic>,
+}
+
+impl Intrinsics {
+    /// The intrinsics every backend has, print writes colors only if print_colors is set
+    pub fn new(ctx: &mut Context, print_colors: bool) -> Self {
+        let mut result = Self {
+            intrinsics: HashMap::new(),
+        };
+
+        result.register_symbol(
+            "print",
+            &[IntrinsicType::String],
+            IntrinsicType::Unit,
+            "core_intrinsics_io_print",
+            move |args| {
+                for arg in args {
+                    if arg.to_string() == "\n" {
+                        output::print("\n");
+                    } else if print_colors {
+                        output::print(&format!("{} ", arg.to_string().replace("\\x1b", "\x1b")));
+                    } else {
+                        output::print(&format!("{} ", arg.to_string()));
+                    }
+                }
+                Ok(Value::Unit)
+            },
+        );
+
+        let results = Results::new(ctx);
+        fs::register(&mut result, ctx, results);
+        io::register(&mut result, results);
+        json::register(&mut result, ctx, results);
+        list::register(&mut result, results);
+        math::register(&mut result);
+        process::register(&mut result, ctx, results);
+        random::register(&mut result);
+        string::register(&mut result, results);
+        time::register(&mut result);
+
+        result
+    }
+
+    /// Arguments the program got started with, without them it got none
+    pub fn with_arguments(mut self, arguments: Vec<String>) -> Self {
+        process::register_arguments(&mut self, arguments);
+        self
+    }
+
+    /// Adds an intrinsic only the interpreter has, it replaces one with the same name
+    pub fn register(
+        &mut self,
+        name: &'static str,
+        arguments: &[IntrinsicType],
+        result: IntrinsicType,
+        function: impl Fn(&[Value]) -> crate::backend::run::Result<Value> + 'static,
+    ) {
+        self.insert(name, arguments, result, None, function)
+    }
+
+    /// Adds an intrinsic which generated code calls as symbol of the sysroot
+    pub fn register_symbol(
+        &mut self,
+        name: &'static str,
+        arguments: &[IntrinsicType],
+        result: IntrinsicType,
+        symbol: &'static str,
+        function: impl Fn(&[Value]) -> crate::backend::run::Result<Value> + 'static,
+    ) {
+        self.insert(name, arguments, result, Some(symbol), function)
+    }
+
+    fn insert(
+        &mut self,
+        name: &'static str,
+        arguments: &[IntrinsicType],
+        result: IntrinsicType,
+        symbol: Option<&'static str>,
+        function: impl Fn(&[Value]) -> crate::backend::run::Result<Value> + 'static,
+    ) {
+        self.intrinsics.insert(
+            name,
+            Intrinsic {
+                arguments: arguments.to_vec(),
+                result,
+                symbol,
+                function: IntrinsicFunctionValue(Rc::new(function)),
+            },
+        );
+    }
+
+    pub fn get(&self, name: &str) -> Result<&Intrinsic, Error> {
+        self.intrinsics.get(name).ok_or_else(|| Error::Missing {
+            name: name.to_string(),
+        })
+    }
+
+    /// Looks up the intrinsic an external function declaration refers to, the declared argument
+    /// and return types need to match the ones of the intrinsic
+    pub fn resolve(
+        &self,
+        name: &str,
+        arguments: &[Option<&AstType>],
+        result: Option<&AstType>,
+    ) -> Result<&Intrinsic, Error> {
+        let intrinsic = self.get(name)?;
+
+        let matches = intrinsic.arguments.len() == arguments.len()
+            && intrinsic
+                .arguments
+                .iter()
+                .zip(arguments)
+                .all(|(expected, declared)| expected.accepts(*declared))
+            && intrinsic.result.accepts(result);
+
+        if !matches {
+            let arguments: Vec<&str> = arguments.iter().map(|a| describe(*a)).collect();
+            return Err(Error::Mismatched {
+                name: name.to_string(),
+                expected: intrinsic.signature(),
+                declared: format!("({}) -> {}", arguments.join(", "), describe(result)),
+            });
+        }
+        Ok(intrinsic)
+    }
+
+    /// Symbol of the sysroot implementing the intrinsic
+    pub fn symbol(&self, name: &str) -> Result<&'static str, Error> {
+        self.get(name)?.symbol.ok_or_else(|| Error::Missing {
+            name: name.to_string(),
+        })
+    }
+}
+
+/// Builds the std::result::Result objects intrinsics which can fail return instead of panicking,
+/// value is Unit if it failed and error empty if it did not
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Results {
+    ok: StringTableId,
+    value: StringTableId,
+    error: StringTableId,
+}
+
+impl Results {
+    pub(crate) fn new(ctx: &mut Context) -> Self {
+        Self {
+            ok: ctx.string_table.push_str("ok"),
+            value: ctx.string_table.push_str("value"),
+            error: ctx.string_table.push_str("error"),
+        }
+    }
+
+    pub(crate) fn ok(&self, value: Value) -> Value {
+        self.result(true, value, String::new())
+    }
+
+    pub(crate) fn error(&self, error: impl fmt::Display) -> Value {
+        self.result(false, Value::Unit, error.to_string())
+    }
+
+    pub(crate) fn from<E: fmt::Display>(&self, result: Result<Value, E>) -> Value {
+        match result {
+            Ok(value) => self.ok(value),
+            Err(err) => self.error(err),
+        }
+    }
+
+    fn result(&self, ok: bool, value: Value, error: String) -> Value {
+        let mut result = ObjectValue::new();
+        result.set_property(self.ok, Value::Bool(ok));
+        result.set_property(self.value, value);
+        result.set_property(self.error, Value::String(error));
+        Value::Object(result)
+    }
+}
+
+pub(crate) fn string_argument(args: &[Value], index: usize) -> crate::backend::run::Result<String> {
+    let Some(Value::String(value)) = args.get(index) else {
+        return Err(Error::Argument {
+            index,
+            expected: IntrinsicType::String,
+        }
+        .into());
+    };
+    Ok(value.clone())
+}
+
+pub(crate) fn number_argument(args: &[Value], index: usize) -> crate::backend::run::Result<f64> {
+    let Some(Value::Number(value)) = args.get(index) else {
+        return Err(Error::Argument {
+            index,
+            expected: IntrinsicType::Number,
+        }
+        .into());
+    };
+    Ok(*value)
+}
+
+pub(crate) fn list(values: Vec<Value>) -> Value {
+    Value::List(ListValue(Rc::new(RefCell::new(values))))
+}
+
+fn describe(declared: Option<&AstType>) -> &'static str {
+    match declared {
+        None => "Unit",
+        Some(AstType::Boolean) => "Bool",
+        Some(AstType::Number) => "Number",
+        Some(AstType::String) => "String",
+        Some(AstType::Object) => "Object",
+        Some(AstType::Function { .. }) => "function",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::intrinsic::{Error, Intrinsics};
+    use crate::backend::run::value::Value;
+    use crate::frontend::ast::AstType;
+    use crate::frontend::context::Context;
+
+    #[test]
+    fn resolves_declared_intrinsics() {
+        let intrinsics = Intrinsics::new(&mut Context::new(), false);
+        assert!(intrinsics
+            .resolve("print", &[Some(&AstType::String)], None)
+            .is_ok());
+        assert!(intrinsics
+            .resolve("cos_f64", &[Some(&AstType::Object)], Some(&AstType::Object))
+            .is_ok());
+    }
+
+    #[test]
+    fn reports_missing_intrinsic() {
+        let intrinsics = Intrinsics::new(&mut Context::new(), false);
+        let err = intrinsics
+            .resolve("does_not_exist", &[], None)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            Error::Missing {
+                name: "does_not_exist".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn reports_mismatched_signature() {
+        let intrinsics = Intrinsics::new(&mut Context::new(), false);
+        let err = intrinsics
+            .resolve("print", &[Some(&AstType::Number)], Some(&AstType::Number))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "intrinsic print is (String) -> Unit but got declared as (Number) -> Number"
+        );
+    }
+
+    #[test]
+    fn maps_intrinsics_to_sysroot_symbols() {
+        let intrinsics = Intrinsics::new(&mut Context::new(), false);
+        assert_eq!(intrinsics.symbol("print"), Ok("core_intrinsics_io_print"));
+        assert!(intrinsics.symbol("list_length").is_err());
+    }
+
+    #[test]
+    fn reports_mismatched_arguments() {
+        let intrinsics = Intrinsics::new(&mut Context::new(), false);
+        let call = |name: &str, args: &[Value]| {
+            (intrinsics.get(name).unwrap().function.0)(args)
+                .err()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(
+            call("string_length", &[Value::Number(1.0)]),
+            "argument 0 of intrinsic is not String"
+        );
+        assert_eq!(
+            call("format_duration", &[]),
+            "argument 0 of intrinsic is not Number"
+        );
+        assert_eq!(
+            call("list_length", &[Value::Unit]),
+            "argument 0 of intrinsic is not List"
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use std::env;
+use std::process::{exit, Command, ExitStatus};
+
+use crate::backend::intrinsic::{
+    list, number_argument, string_argument, IntrinsicType, Intrinsics, Results,
+};
+use crate::backend::run::value::{ObjectValue, Value};
+use crate::frontend::context::Context;
+
+/// Intrinsics of core::intrinsics::process, everything which can fail returns a result. In C
+/// those return false or NULL instead and core_intrinsics_process_error tells why
+pub(crate) fn register(intrinsics: &mut Intrinsics, ctx: &mut Context, results: Results) {
+    register_arguments(intrinsics, vec![]);
+
+    intrinsics.register_symbol(
+        "env_var",
+        &[IntrinsicType::String],
+        IntrinsicType::Result,
+        "core_intrinsics_process_env_var",
+        move |args| {
+            let name = string_argument(args, 0)?;
+            Ok(match env::var(&name) {
+                Ok(value) => results.ok(Value::String(value)),
+                Err(_) => results.error(format!("environment variable {name} is not set")),
+            })
+        },
+    );
+
+    let properties = [
+        ctx.string_table.push_str("status"),
+        ctx.string_table.push_str("stdout"),
+        ctx.string_table.push_str("stderr"),
+    ];
+    intrinsics.register_symbol(
+        "run_command",
+        &[IntrinsicType::String, IntrinsicType::List],
+        IntrinsicType::Result,
+        "core_intrinsics_process_run_command",
+        move |args| {
+            let command = string_argument(args, 0)?;
+            Ok(results.from(
+                Command::new(command)
+                    .args(strings(&args[1]))
+                    .output()
+                    .map(|output| {
+                        let [status, stdout, stderr] = properties;
+                        let mut result = ObjectValue::new();
+                        result.set_property(status, Value::Number(code(output.status)));
+                        result.set_property(
+                            stdout,
+                            Value::String(String::from_utf8_lossy(&output.stdout).to_string()),
+                        );
+                        result.set_property(
+                            stderr,
+                            Value::String(String::from_utf8_lossy(&output.stderr).to_string()),
+                        );
+                        Value::Object(result)
+                    }),
+            ))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "spawn_command",
+        &[IntrinsicType::String, IntrinsicType::List],
+        IntrinsicType::Result,
+        "core_intrinsics_process_spawn_command",
+        move |args| {
+            let command = string_argument(args, 0)?;
+            Ok(results.from(
+                Command::new(command)
+                    .args(strings(&args[1]))
+                    .status()
+                    .map(|status| Value::Number(code(status))),
+            ))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "current_dir",
+        &[],
+        IntrinsicType::Result,
+        "core_intrinsics_process_current_dir",
+        move |_| {
+            Ok(results.from(
+                env::current_dir().map(|dir| Value::String(dir.to_string_lossy().to_string())),
+            ))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "exit",
+        &[IntrinsicType::Number],
+        IntrinsicType::Unit,
+        "core_intrinsics_process_exit",
+        |args| exit(number_argument(args, 0)? as i32),
+    );
+}
+
+/// Replaces the arguments the program got started with, the first one names the program
+pub(crate) fn register_arguments(intrinsics: &mut Intrinsics, arguments: Vec<String>) {
+    intrinsics.register_symbol(
+        "arguments",
+        &[],
+        IntrinsicType::List,
+        "core_intrinsics_process_arguments",
+        move |_| Ok(list(arguments.iter().cloned().map(Value::String).collect())),
+    );
+}
+
+/// Exit code of a process, -1 if a signal terminated it
+fn code(status: ExitStatus) -> f64 {
+    status.code().unwrap_or(-1) as f64
+}
+
+fn strings(value: &Value) -> Vec<String> {
+    let Value::List(list) = value else {
+        panic!("not list")
+    };
+    list.0
+        .borrow()
+        .iter()
+        .map(|value| value.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::intrinsic::{list, Intrinsics};
+    use crate::backend::run::value::Value;
+    use crate::frontend::context::Context;
+
+    fn call(intrinsics: &Intrinsics, name: &str, args: &[Value]) -> Value {
+        (intrinsics.get(name).unwrap().function.0)(args).unwrap()
+    }
+
+    fn string(value: &str) -> Value {
+        Value::String(value.to_string())
+    }
+
+    fn property(ctx: &mut Context, value: &Value, name: &str) -> Value {
+        let Value::Object(object) = value else {
+            panic!("not object")
+        };
+        object
+            .get_property(&ctx.string_table.push_str(name))
+            .cloned()
+            .unwrap()
+    }
+
+    #[test]
+    fn runs_commands_and_captures_output() {
+        let mut ctx = Context::new();
+        let intrinsics = Intrinsics::new(&mut ctx, false);
+
+        let result = call(
+            &intrinsics,
+            "run_command",
+            &[
+                string("sh"),
+                list(vec![string("-c"), string("echo out; echo err >&2; exit 3")]),
+            ],
+        );
+        assert!(matches!(
+            property(&mut ctx, &result, "ok"),
+            Value::Bool(true)
+        ));
+        let output = property(&mut ctx, &result, "value");
+        assert!(matches!(
+            property(&mut ctx, &output, "status"),
+            Value::Number(3.0)
+        ));
+        assert_eq!(property(&mut ctx, &output, "stdout").to_string(), "out\n");
+        assert_eq!(property(&mut ctx, &output, "stderr").to_string(), "err\n");
+
+        let result = call(
+            &intrinsics,
+            "run_command",
+            &[string("/does/not/exist"), list(vec![])],
+        );
+        assert!(matches!(
+            property(&mut ctx, &result, "ok"),
+            Value::Bool(false)
+        ));
+    }
+
+    #[test]
+    fn reads_the_environment() {
+        let mut ctx = Context::new();
+        let intrinsics = Intrinsics::new(&mut ctx, false);
+
+        let result = call(&intrinsics, "env_var", &[string("PATH")]);
+        assert!(matches!(
+            property(&mut ctx, &result, "ok"),
+            Value::Bool(true)
+        ));
+
+        let result = call(&intrinsics, "env_var", &[string("ELODIE_NOT_SET")]);
+        assert_eq!(
+            property(&mut ctx, &result, "error").to_string(),
+            "environment variable ELODIE_NOT_SET is not set"
+        );
+    }
+
+    #[test]
+    fn knows_its_arguments() {
+        let intrinsics = Intrinsics::new(&mut Context::new(), false)
+            .with_arguments(vec!["main.ec".to_string(), "--verbose".to_string()]);
+
+        let Value::List(arguments) = call(&intrinsics, "arguments", &[]) else {
+            panic!("not list")
+        };
+        let arguments: Vec<String> = arguments.0.borrow().iter().map(Value::to_string).collect();
+        assert_eq!(arguments, vec!["main.ec", "--verbose"]);
+    }
+
+    #[test]
+    fn has_symbols_of_the_sysroot() {
+        let mut ctx = Context::new();
+        let intrinsics = Intrinsics::new(&mut ctx, false);
+
+        assert_eq!(
+            intrinsics.symbol("exit"),
+            Ok("core_intrinsics_process_exit")
+        );
+        for name in ["env_var", "run_command", "spawn_command", "current_dir"] {
+            assert_eq!(
+                intrinsics.symbol(name),
+                Ok(format!("core_intrinsics_process_{name}").as_str())
+            );
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use crate::backend::intrinsic::{number_argument, IntrinsicType, Intrinsics};
+use crate::backend::run::value::Value;
+
+// largest integer a number holds exactly
+const MAX_EXACT: f64 = 9_007_199_254_740_992.0;
+
+/// Intrinsics of core::intrinsics::random. They keep no state, the numbers only depend on the seed
+/// and how many got drawn before so that C yields the same sequences
+pub(crate) fn register(intrinsics: &mut Intrinsics) {
+    intrinsics.register_symbol(
+        "random_float",
+        &[IntrinsicType::Number, IntrinsicType::Number],
+        IntrinsicType::Number,
+        "core_intrinsics_random_float",
+        |args| {
+            let (seed, step) = (number_argument(args, 0)?, number_argument(args, 1)?);
+            Ok(Value::Number(float(seed, step)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "random_below",
+        &[
+            IntrinsicType::Number,
+            IntrinsicType::Number,
+            IntrinsicType::Number,
+        ],
+        IntrinsicType::Number,
+        "core_intrinsics_random_below",
+        |args| {
+            let (seed, step) = (number_argument(args, 0)?, number_argument(args, 1)?);
+            Ok(Value::Number(below(seed, step, number_argument(args, 2)?)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "random_seed",
+        &[],
+        IntrinsicType::Number,
+        "core_intrinsics_random_seed",
+        |_| Ok(Value::Number(entropy_seed())),
+    );
+}
+
+/// Deterministic source of random numbers, the same seed always yields the same numbers (splitmix64)
+#[derive(Debug, Clone)]
+pub(crate) struct Rng(u64);
+
+const GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Continues where one of the seed stood after returning step numbers
+    pub(crate) fn at(seed: u64, step: u64) -> Self {
+        Self(seed.wrapping_add(step.wrapping_mul(GAMMA)))
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(GAMMA);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Number within 0 and bound, excluding the bound
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound.max(1)
+    }
+}
+
+/// The step-th number of the seed, counting from 0
+fn next(seed: f64, step: f64) -> u64 {
+    Rng::at(integer(seed), integer(step)).next_u64()
+}
+
+/// Within 0 and 1, excluding 1
+fn float(seed: f64, step: f64) -> f64 {
+    (next(seed, step) >> 11) as f64 / MAX_EXACT
+}
+
+/// Integer within 0 and bound, excluding the bound. Bounds below 1 count as 1, the ones above
+/// 2^53 as 2^53
+fn below(seed: f64, step: f64, bound: f64) -> f64 {
+    let bound = bound.clamp(1.0, MAX_EXACT) as u64;
+    (next(seed, step) % bound) as f64
+}
+
+/// Truncates towards 0, saturates at the bounds of i64 and maps nan to 0
+fn integer(value: f64) -> u64 {
+    value as i64 as u64
+}
+
+/// Seed from the randomness of the os which a number holds exactly
+fn entropy_seed() -> f64 {
+    let entropy = RandomState::new().build_hasher().finish();
+    (entropy >> 11) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::intrinsic::random::{below, entropy_seed, float, next, Rng};
+
+    #[test]
+    fn repeats_numbers_of_seed() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        for _ in 0..10 {
+            assert_eq!(a.below(100), b.below(100));
+        }
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+    }
+
+    #[test]
+    fn continues_at_any_step() {
+        let mut rng = Rng::new(7);
+        rng.next_u64();
+        rng.next_u64();
+        assert_eq!(Rng::at(7, 2).next_u64(), rng.next_u64());
+    }
+
+    #[test]
+    fn follows_splitmix64() {
+        let mut rng = Rng::new(42);
+        for step in 0..5 {
+            assert_eq!(next(42.0, step as f64), rng.next_u64());
+        }
+        assert_eq!(next(0.0, 0.0), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(next(-1.0, 0.0), Rng::new(u64::MAX).next_u64());
+        assert_eq!(next(f64::NAN, 0.0), next(0.0, 0.0));
+    }
+
+    #[test]
+    fn stays_within_bounds() {
+        for step in 0..1000 {
+            let value = float(7.0, step as f64);
+            assert!((0.0..1.0).contains(&value));
+
+            let value = below(7.0, step as f64, 6.0);
+            assert!((0.0..6.0).contains(&value));
+            assert_eq!(value, value.trunc());
+        }
+        assert_eq!(below(7.0, 0.0, 0.0), 0.0);
+        assert_eq!(below(7.0, 0.0, -5.0), 0.0);
+    }
+
+    #[test]
+    fn seeds_differ() {
+        let seed = entropy_seed();
+        assert_eq!(seed, seed.trunc());
+        assert_ne!(seed, entropy_seed());
+    }
+}
@@ -0,0 +1,313 @@
+use crate::backend::intrinsic::{
+    list, number_argument, string_argument, IntrinsicType, Intrinsics, Results,
+};
+use crate::backend::run::value::Value;
+
+/// Intrinsics of core::intrinsics::string. Positions count chars starting at 1 like the ones of
+/// lists, case conversion and trimming only know ascii so that C behaves the same. In C
+/// converting to a number returns false instead and core_intrinsics_string_error tells why
+pub(crate) fn register(intrinsics: &mut Intrinsics, results: Results) {
+    intrinsics.register_symbol(
+        "string_length",
+        &[IntrinsicType::String],
+        IntrinsicType::Number,
+        "core_intrinsics_string_length",
+        |args| {
+            Ok(Value::Number(
+                string_argument(args, 0)?.chars().count() as f64
+            ))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "string_slice",
+        &[
+            IntrinsicType::String,
+            IntrinsicType::Number,
+            IntrinsicType::Number,
+        ],
+        IntrinsicType::String,
+        "core_intrinsics_string_slice",
+        |args| {
+            let text = string_argument(args, 0)?;
+            let (from, to) = (number_argument(args, 1)?, number_argument(args, 2)?);
+            Ok(Value::String(slice(&text, from, to)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "string_chars",
+        &[IntrinsicType::String],
+        IntrinsicType::List,
+        "core_intrinsics_string_chars",
+        |args| Ok(strings(chars(&string_argument(args, 0)?))),
+    );
+
+    intrinsics.register_symbol(
+        "string_split",
+        &[IntrinsicType::String, IntrinsicType::String],
+        IntrinsicType::List,
+        "core_intrinsics_string_split",
+        |args| {
+            let (text, separator) = (string_argument(args, 0)?, string_argument(args, 1)?);
+            if separator.is_empty() {
+                return Ok(strings(chars(&text)));
+            }
+            Ok(strings(
+                text.split(separator.as_str()).map(str::to_string).collect(),
+            ))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "string_join",
+        &[IntrinsicType::List, IntrinsicType::String],
+        IntrinsicType::String,
+        "core_intrinsics_string_join",
+        |args| {
+            let Value::List(parts) = &args[0] else {
+                panic!("not list")
+            };
+            let parts: Vec<String> = parts.0.borrow().iter().map(Value::to_string).collect();
+            Ok(Value::String(parts.join(&string_argument(args, 1)?)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "string_trim",
+        &[IntrinsicType::String],
+        IntrinsicType::String,
+        "core_intrinsics_string_trim",
+        |args| {
+            let text = string_argument(args, 0)?;
+            Ok(Value::String(
+                text.trim_matches(|c: char| c.is_ascii_whitespace())
+                    .to_string(),
+            ))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "string_starts_with",
+        &[IntrinsicType::String, IntrinsicType::String],
+        IntrinsicType::Bool,
+        "core_intrinsics_string_starts_with",
+        |args| {
+            let (text, prefix) = (string_argument(args, 0)?, string_argument(args, 1)?);
+            Ok(Value::Bool(text.starts_with(&prefix)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "string_ends_with",
+        &[IntrinsicType::String, IntrinsicType::String],
+        IntrinsicType::Bool,
+        "core_intrinsics_string_ends_with",
+        |args| {
+            let (text, suffix) = (string_argument(args, 0)?, string_argument(args, 1)?);
+            Ok(Value::Bool(text.ends_with(&suffix)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "string_contains",
+        &[IntrinsicType::String, IntrinsicType::String],
+        IntrinsicType::Bool,
+        "core_intrinsics_string_contains",
+        |args| {
+            let (text, part) = (string_argument(args, 0)?, string_argument(args, 1)?);
+            Ok(Value::Bool(text.contains(&part)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "string_equals",
+        &[IntrinsicType::String, IntrinsicType::String],
+        IntrinsicType::Bool,
+        "core_intrinsics_string_equals",
+        |args| {
+            Ok(Value::Bool(
+                string_argument(args, 0)? == string_argument(args, 1)?,
+            ))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "string_replace",
+        &[
+            IntrinsicType::String,
+            IntrinsicType::String,
+            IntrinsicType::String,
+        ],
+        IntrinsicType::String,
+        "core_intrinsics_string_replace",
+        |args| {
+            let text = string_argument(args, 0)?;
+            let (from, to) = (string_argument(args, 1)?, string_argument(args, 2)?);
+            if from.is_empty() {
+                return Ok(Value::String(text));
+            }
+            Ok(Value::String(text.replace(&from, &to)))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "string_to_upper",
+        &[IntrinsicType::String],
+        IntrinsicType::String,
+        "core_intrinsics_string_to_upper",
+        |args| {
+            Ok(Value::String(
+                string_argument(args, 0)?.to_ascii_uppercase(),
+            ))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "string_to_lower",
+        &[IntrinsicType::String],
+        IntrinsicType::String,
+        "core_intrinsics_string_to_lower",
+        |args| {
+            Ok(Value::String(
+                string_argument(args, 0)?.to_ascii_lowercase(),
+            ))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "string_to_number",
+        &[IntrinsicType::String],
+        IntrinsicType::Result,
+        "core_intrinsics_string_to_number",
+        move |args| {
+            let text = string_argument(args, 0)?;
+            Ok(match parse_number(&text) {
+                Some(number) => results.ok(Value::Number(number)),
+                None => results.error(format!("'{text}' is not a number")),
+            })
+        },
+    );
+}
+
+fn chars(text: &str) -> Vec<String> {
+    text.chars().map(String::from).collect()
+}
+
+fn strings(values: Vec<String>) -> Value {
+    list(values.into_iter().map(Value::String).collect())
+}
+
+/// Chars from one position up to and including the other, positions outside of the text get
+/// clamped to it
+fn slice(text: &str, from: f64, to: f64) -> String {
+    let from = from.max(1.0) as usize;
+    let to = to.max(0.0) as usize;
+    if to < from {
+        return String::new();
+    }
+    text.chars().skip(from - 1).take(to - from + 1).collect()
+}
+
+/// Decimal numbers with an optional sign, fraction and exponent. Anything else, like surrounding
+/// whitespace, inf or nan, is no number
+fn parse_number(text: &str) -> Option<f64> {
+    let bytes = text.as_bytes();
+    let mut idx = 0;
+    let digits = |idx: &mut usize| {
+        let start = *idx;
+        while *idx < bytes.len() && bytes[*idx].is_ascii_digit() {
+            *idx += 1;
+        }
+        *idx - start
+    };
+
+    if idx < bytes.len() && (bytes[idx] == b'+' || bytes[idx] == b'-') {
+        idx += 1;
+    }
+    let mut mantissa = digits(&mut idx);
+    if idx < bytes.len() && bytes[idx] == b'.' {
+        idx += 1;
+        mantissa += digits(&mut idx);
+    }
+    if mantissa == 0 {
+        return None;
+    }
+    if idx < bytes.len() && (bytes[idx] == b'e' || bytes[idx] == b'E') {
+        idx += 1;
+        if idx < bytes.len() && (bytes[idx] == b'+' || bytes[idx] == b'-') {
+            idx += 1;
+        }
+        if digits(&mut idx) == 0 {
+            return None;
+        }
+    }
+    if idx != bytes.len() {
+        return None;
+    }
+    text.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::intrinsic::string::{parse_number, slice};
+    use crate::backend::intrinsic::Intrinsics;
+    use crate::backend::run::value::Value;
+    use crate::frontend::context::Context;
+
+    #[test]
+    fn slices_chars() {
+        assert_eq!(slice("Elodie", 1.0, 3.0), "Elo");
+        assert_eq!(slice("Elodie", 4.0, 100.0), "die");
+        assert_eq!(slice("Elodie", 0.0, 1.0), "E");
+        assert_eq!(slice("Elodie", 3.0, 2.0), "");
+        assert_eq!(slice("äöü", 2.0, 2.0), "ö");
+    }
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(parse_number("42"), Some(42.0));
+        assert_eq!(parse_number("-4.5"), Some(-4.5));
+        assert_eq!(parse_number("+.5"), Some(0.5));
+        assert_eq!(parse_number("1e3"), Some(1000.0));
+        assert_eq!(parse_number("2.E-1"), Some(0.2));
+
+        assert_eq!(parse_number(""), None);
+        assert_eq!(parse_number("."), None);
+        assert_eq!(parse_number(" 1"), None);
+        assert_eq!(parse_number("1e"), None);
+        assert_eq!(parse_number("inf"), None);
+        assert_eq!(parse_number("NaN"), None);
+        assert_eq!(parse_number("0x10"), None);
+    }
+
+    #[test]
+    fn changes_case_of_ascii_letters_only() {
+        let mut ctx = Context::new();
+        let intrinsics = Intrinsics::new(&mut ctx, false);
+        let case = |name: &str, text: &str| {
+            let args = [Value::String(text.to_string())];
+            (intrinsics.get(name).unwrap().function.0)(&args)
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(case("string_to_upper", "Élodie ß"), "ÉLODIE ß");
+        assert_eq!(case("string_to_lower", "ÉLODIE"), "Élodie");
+    }
+
+    #[test]
+    fn has_symbols_of_the_sysroot() {
+        let mut ctx = Context::new();
+        let intrinsics = Intrinsics::new(&mut ctx, false);
+
+        assert_eq!(
+            intrinsics.symbol("string_trim"),
+            Ok("core_intrinsics_string_trim")
+        );
+        assert_eq!(
+            intrinsics.symbol("string_to_number"),
+            Ok("core_intrinsics_string_to_number")
+        );
+    }
+}
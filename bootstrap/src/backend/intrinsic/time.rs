@@ -0,0 +1,214 @@
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::backend::intrinsic::{number_argument, IntrinsicType, Intrinsics};
+use crate::backend::run::value::Value;
+
+/// Intrinsics of core::intrinsics::time. Durations and monotonic instants are nanoseconds, wall
+/// clock timestamps milliseconds since the unix epoch in utc
+pub(crate) fn register(intrinsics: &mut Intrinsics) {
+    intrinsics.register_symbol(
+        "monotonic_nanos",
+        &[],
+        IntrinsicType::Number,
+        "core_intrinsics_time_monotonic_nanos",
+        |_| Ok(Value::Number(monotonic_nanos())),
+    );
+
+    intrinsics.register_symbol(
+        "wall_clock_millis",
+        &[],
+        IntrinsicType::Number,
+        "core_intrinsics_time_wall_clock_millis",
+        |_| {
+            let millis = match SystemTime::now().duration_since(UNIX_EPOCH) {
+                Ok(since) => since.as_secs_f64() * 1000.0,
+                Err(before) => -before.duration().as_secs_f64() * 1000.0,
+            };
+            Ok(Value::Number(millis.floor()))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "subtract_nanos",
+        &[IntrinsicType::Number, IntrinsicType::Number],
+        IntrinsicType::Number,
+        "core_intrinsics_time_subtract_nanos",
+        |args| {
+            Ok(Value::Number(
+                number_argument(args, 0)? - number_argument(args, 1)?,
+            ))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "divide_nanos",
+        &[IntrinsicType::Number, IntrinsicType::Number],
+        IntrinsicType::Number,
+        "core_intrinsics_time_divide_nanos",
+        |args| {
+            Ok(Value::Number(
+                number_argument(args, 0)? / number_argument(args, 1)?,
+            ))
+        },
+    );
+
+    intrinsics.register_symbol(
+        "format_duration",
+        &[IntrinsicType::Number],
+        IntrinsicType::String,
+        "core_intrinsics_time_format_duration",
+        |args| Ok(Value::String(format_duration(number_argument(args, 0)?))),
+    );
+
+    intrinsics.register_symbol(
+        "format_iso8601",
+        &[IntrinsicType::Number],
+        IntrinsicType::String,
+        "core_intrinsics_time_format_iso8601",
+        |args| Ok(Value::String(format_iso8601(number_argument(args, 0)?))),
+    );
+
+    intrinsics.register_symbol(
+        "sleep_millis",
+        &[IntrinsicType::Number],
+        IntrinsicType::Unit,
+        "core_intrinsics_time_sleep_millis",
+        |args| {
+            let millis = number_argument(args, 0)?;
+            if millis > 0.0 {
+                thread::sleep(Duration::from_secs_f64(millis / 1000.0));
+            }
+            Ok(Value::Unit)
+        },
+    );
+}
+
+/// Nanoseconds since the first time anything asked for it
+fn monotonic_nanos() -> f64 {
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_nanos() as f64
+}
+
+/// The largest unit below a minute with up to 3 decimals like 1.5s, 250ms or 12.345µs, longer
+/// durations in hours, minutes and seconds like 1h 2m 3.5s
+fn format_duration(nanos: f64) -> String {
+    if nanos < 0.0 {
+        return format!("-{}", format_duration(-nanos));
+    }
+    // rounded to what the unit shows before choosing it, 999.9996ms is 1s and not 1000ms
+    let nanos = nanos.round();
+    if nanos < 1e3 {
+        return format!("{nanos}ns");
+    }
+    if nanos < 1e6 {
+        return format!("{}µs", decimals(nanos / 1e3));
+    }
+    let micros = (nanos / 1e3).round();
+    if micros < 1e6 {
+        return format!("{}ms", decimals(micros / 1e3));
+    }
+    let millis = (nanos / 1e6).round();
+    if millis < 60e3 {
+        return format!("{}s", decimals(millis / 1e3));
+    }
+    let minutes = (millis / 60e3).floor();
+    let seconds = decimals((millis - minutes * 60e3) / 1e3);
+    let hours = (minutes / 60.0).floor();
+    let minutes = minutes - hours * 60.0;
+    if hours > 0.0 {
+        format!("{hours}h {minutes}m {seconds}s")
+    } else {
+        format!("{minutes}m {seconds}s")
+    }
+}
+
+/// Up to 3 decimals without trailing zeros
+fn decimals(value: f64) -> String {
+    let result = format!("{value:.3}");
+    result
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// Like 2024-02-29T13:05:09.042Z, the milliseconds get truncated
+fn format_iso8601(millis: f64) -> String {
+    let millis = millis.floor() as i64;
+    let (days, millis_of_day) = (millis.div_euclid(86_400_000), millis.rem_euclid(86_400_000));
+    let (year, month, day) = civil_from_days(days);
+    let seconds_of_day = millis_of_day / 1000;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60,
+        millis_of_day % 1000
+    )
+}
+
+/// Year, month and day of the days since 1970-01-01 in the proleptic gregorian calendar, see
+/// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::intrinsic::time::{format_duration, format_iso8601, monotonic_nanos};
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(0.0), "0ns");
+        assert_eq!(format_duration(999.0), "999ns");
+        assert_eq!(format_duration(12_345.0), "12.345µs");
+        assert_eq!(format_duration(250_000_000.0), "250ms");
+        assert_eq!(format_duration(1_500_000_000.0), "1.5s");
+        assert_eq!(format_duration(125_000_000_000.0), "2m 5s");
+        assert_eq!(format_duration(3_723_500_000_000.0), "1h 2m 3.5s");
+        assert_eq!(format_duration(-2_000_000.0), "-2ms");
+    }
+
+    #[test]
+    fn rounds_durations_before_choosing_the_unit() {
+        assert_eq!(format_duration(999_999.6), "1ms");
+        assert_eq!(format_duration(999_999_600.0), "1s");
+        assert_eq!(format_duration(59_999_600_000.0), "1m 0s");
+        assert_eq!(format_duration(3_599_999_600_000.0), "1h 0m 0s");
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_iso8601(0.0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(
+            format_iso8601(951_782_400_000.0),
+            "2000-02-29T00:00:00.000Z"
+        );
+        assert_eq!(
+            format_iso8601(1_709_211_909_042.0),
+            "2024-02-29T13:05:09.042Z"
+        );
+        assert_eq!(format_iso8601(-1.0), "1969-12-31T23:59:59.999Z");
+    }
+
+    #[test]
+    fn never_goes_back() {
+        let earlier = monotonic_nanos();
+        assert!(monotonic_nanos() >= earlier);
+    }
+}
@@ -1,4 +1,6 @@
 pub mod build;
 pub mod generate;
+pub mod intrinsic;
+pub mod repl;
 pub mod run;
 pub mod test;
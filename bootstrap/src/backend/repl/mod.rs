@@ -0,0 +1,369 @@
+use std::fs;
+use std::io;
+use std::io::{BufRead, Write};
+
+use crate::backend::intrinsic::Intrinsics;
+use crate::backend::run::scope::Scope;
+use crate::backend::run::type_definitions::TypeDefinitions;
+use crate::backend::run::value::Value;
+use crate::backend::run::{evaluate, load_prelude};
+use crate::common::node::Node;
+use crate::common::panic::catch_silently;
+use crate::frontend::ast_from_str;
+use crate::frontend::context::Context;
+use crate::frontend::lex::lex;
+use crate::frontend::lex::token::OperatorToken;
+use crate::frontend::lex::token::TokenKind::Operator;
+use crate::frontend::{lex, Ast};
+
+/// Reads entries from stdin and evaluates them against a scope with core and std loaded
+pub fn repl() {
+    let mut ctx = Context::new();
+    let intrinsics = Intrinsics::new(&mut ctx, true);
+    let (scope, definitions) = load_prelude(&mut ctx, &intrinsics);
+    let mut repl = Repl::new(ctx, scope, definitions, intrinsics);
+
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut entry = String::new();
+    loop {
+        print!("{}", if entry.is_empty() { "> " } else { ". " });
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        if input.read_line(&mut line).unwrap() == 0 {
+            println!();
+            return;
+        }
+
+        entry.push_str(&line);
+        // an entry which panics while checking it gets executed to report the error
+        if !catch_silently(|| is_complete(&entry)).unwrap_or(true) {
+            continue;
+        }
+
+        // errors of an entry get reported by the repl, the session continues
+        match catch_silently(|| repl.execute(entry.trim())).unwrap_or_else(Err) {
+            Ok(Some(output)) => println!("{output}"),
+            Ok(None) => {}
+            Err(err) => eprintln!("Error: {err}"),
+        }
+        entry.clear();
+    }
+}
+
+pub(crate) struct Repl {
+    ctx: Context,
+    scope: Scope,
+    definitions: TypeDefinitions,
+    intrinsics: Intrinsics,
+}
+
+impl Repl {
+    pub(crate) fn new(
+        ctx: Context,
+        scope: Scope,
+        definitions: TypeDefinitions,
+        intrinsics: Intrinsics,
+    ) -> Self {
+        Self {
+            ctx,
+            scope,
+            definitions,
+            intrinsics,
+        }
+    }
+
+    /// Executes an entry, returns what should be shown to the user
+    pub(crate) fn execute(&mut self, entry: &str) -> Result<Option<String>, String> {
+        if entry.starts_with(':') {
+            let (command, argument) = entry.split_once(char::is_whitespace).unwrap_or((entry, ""));
+            return match command {
+                ":type" => {
+                    // the type is the one of the value, so the expression runs and what it does
+                    // like printing happens. It runs against a copy of the scope, nothing the
+                    // expression declares stays
+                    let (_, _, value) = self.run(argument)?;
+                    Ok(Some(self.type_name(&value).to_string()))
+                }
+                ":ast" => {
+                    let ast = self.parse(argument)?;
+                    Ok(Some(format!("{:#?}", ast.nodes)))
+                }
+                ":load" => {
+                    let file = argument.trim();
+                    let source = fs::read_to_string(file)
+                        .map_err(|err| format!("Failed to load {file} - {err}"))?;
+                    self.evaluate(&source)?;
+                    Ok(Some(format!("Loaded {file}")))
+                }
+                _ => Err(format!(
+                    "Unknown command {command} - supported are :type <expression>, :ast <source> and :load <file>"
+                )),
+            };
+        }
+
+        match self.evaluate(entry)? {
+            Value::Unit => Ok(None),
+            value => Ok(Some(display(&value))),
+        }
+    }
+
+    fn parse(&mut self, source: &str) -> Result<Ast, String> {
+        let ctx = &mut self.ctx;
+        match catch_silently(|| ast_from_str(ctx, source)) {
+            Ok(Ok(ast)) => Ok(ast),
+            Ok(Err(err)) => Err(format!("{err:?}")),
+            Err(panic) => Err(panic),
+        }
+    }
+
+    /// Records by the name of their type
+    fn type_name(&self, value: &Value) -> &str {
+        match value {
+            Value::Bool(_) => "Bool",
+            Value::Function(_) => "Function",
+            Value::List(_) => "List",
+            Value::Number(_) => "Number",
+            Value::F64(_) => "F64",
+            Value::Object(object) => match object.type_name {
+                Some(type_name) => self.ctx.get_str(type_name),
+                None => "Object",
+            },
+            Value::Package(_) => "Package",
+            Value::String(_) => "String",
+            Value::Tuple(_) => "Tuple",
+            Value::Unit => "Unit",
+        }
+    }
+
+    // the scope only gets replaced once the entry ran successfully
+    fn evaluate(&mut self, source: &str) -> Result<Value, String> {
+        let (scope, definitions, value) = self.run(source)?;
+        self.scope = scope;
+        self.definitions = definitions;
+        Ok(value)
+    }
+
+    /// Runs the source against a copy of the scope and returns the copy afterwards
+    fn run(&mut self, source: &str) -> Result<(Scope, TypeDefinitions, Value), String> {
+        let ast = self.parse(source)?;
+        let declares = matches!(
+            ast.nodes.last().map(|node| node.node()),
+            Some(
+                Node::DeclareExternalFunction(_)
+                    | Node::DeclareFunction(_)
+                    | Node::DeclarePackage(_)
+                    | Node::DeclareType(_)
+                    | Node::DefineType(_)
+            )
+        );
+
+        let ctx = &mut self.ctx;
+        let intrinsics = &self.intrinsics;
+        let scope = self.scope.clone();
+        let definitions = self.definitions.clone();
+        match catch_silently(|| evaluate(ctx, scope, definitions, ast, intrinsics)) {
+            // declarations evaluate to what they declare, which is not worth printing
+            Ok(Ok((scope, definitions, value))) => Ok((
+                scope,
+                definitions,
+                if declares { Value::Unit } else { value },
+            )),
+            Ok(Err(err)) => Err(err.to_string()),
+            Err(panic) => Err(panic),
+        }
+    }
+}
+
+/// Entries stay open while strings, blocks or argument lists are not closed
+pub(crate) fn is_complete(entry: &str) -> bool {
+    let mut ctx = Context::new();
+    let tokens = match lex(&mut ctx, entry) {
+        Ok(tokens) => tokens,
+        Err(lex::Error::UnexpectedEndOfFile) => return false,
+        Err(_) => return true,
+    };
+
+    let mut depth = 0;
+    for token in tokens {
+        match token.kind {
+            Operator(OperatorToken::OpenCurly)
+            | Operator(OperatorToken::OpenParen)
+            | Operator(OperatorToken::OpenBracket) => depth += 1,
+            Operator(OperatorToken::CloseCurly)
+            | Operator(OperatorToken::CloseParen)
+            | Operator(OperatorToken::CloseBracket) => depth -= 1,
+            _ => {}
+        }
+    }
+    depth <= 0
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(value) => format!("'{value}'"),
+        Value::List(list) => {
+            let values = list
+                .0
+                .borrow()
+                .iter()
+                .map(display)
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("[{values}]")
+        }
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+
+    use crate::backend::intrinsic::Intrinsics;
+    use crate::backend::repl::{is_complete, Repl};
+    use crate::backend::run::output::{end_capture, start_capture};
+    use crate::backend::run::scope::Scope;
+    use crate::backend::run::type_definitions::TypeDefinitions;
+    use crate::frontend::context::Context;
+
+    fn repl() -> Repl {
+        let mut ctx = Context::new();
+        let intrinsics = Intrinsics::new(&mut ctx, false);
+        Repl::new(
+            ctx,
+            Scope::new(HashMap::new(), HashMap::new()),
+            TypeDefinitions {
+                definitions: Default::default(),
+            },
+            intrinsics,
+        )
+    }
+
+    #[test]
+    fn complete_entries() {
+        assert!(is_complete("1 + 2"));
+        assert!(is_complete("function f() {\n return 1\n}"));
+        assert!(is_complete("'{'"));
+        assert!(!is_complete("function f() {"));
+        assert!(!is_complete("f(1,"));
+        assert!(!is_complete("'Elodie"));
+    }
+
+    #[test]
+    fn evaluates_against_live_scope() {
+        let mut repl = repl();
+        assert_eq!(repl.execute("let value = 21"), Ok(None));
+        assert_eq!(repl.execute("value * 2"), Ok(Some("42".to_string())));
+        assert_eq!(
+            repl.execute("function greet() {\n return 'Elodie'\n}"),
+            Ok(None)
+        );
+        assert_eq!(repl.execute("greet()"), Ok(Some("'Elodie'".to_string())));
+    }
+
+    #[test]
+    fn failing_entry_keeps_scope() {
+        let mut repl = repl();
+        repl.execute("let value = 1").unwrap();
+        assert!(repl.execute("let other = 1\nunknown_variable").is_err());
+        assert_eq!(repl.execute("value"), Ok(Some("1".to_string())));
+        assert!(repl.execute("other").is_err());
+    }
+
+    #[test]
+    fn reports_undeclarable_intrinsics() {
+        let mut repl = repl();
+        assert_eq!(
+            repl.execute("package io {\n external function print(n: Number) -> Number\n}"),
+            Err(
+                "intrinsic print is (String) -> Unit but got declared as (Number) -> Number"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            repl.execute("package io {\n external function read()\n}"),
+            Err("intrinsic read does not exist".to_string())
+        );
+        assert_eq!(
+            repl.execute("package io {\n external function print(s: String)\n}"),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn type_command() {
+        let mut repl = repl();
+        assert_eq!(repl.execute(":type 1 + 2"), Ok(Some("Number".to_string())));
+        assert_eq!(repl.execute(":type 'text'"), Ok(Some("String".to_string())));
+        assert_eq!(repl.execute(":type true"), Ok(Some("Bool".to_string())));
+    }
+
+    #[test]
+    fn type_command_names_records() {
+        let mut repl = repl();
+        repl.execute("type Point(x: Number)").unwrap();
+        assert_eq!(
+            repl.execute(":type Point(x = 1)"),
+            Ok(Some("Point".to_string()))
+        );
+    }
+
+    #[test]
+    fn type_command_evaluates() {
+        let mut repl = repl();
+        repl.execute("package io {\n external function print(s: String)\n}")
+            .unwrap();
+        start_capture();
+        let result = repl.execute(":type io::print('printed')");
+        assert_eq!(end_capture(), "printed ");
+        assert_eq!(result, Ok(Some("Unit".to_string())));
+    }
+
+    #[test]
+    fn type_command_keeps_scope() {
+        let mut repl = repl();
+        repl.execute("let value = 1").unwrap();
+        assert_eq!(
+            repl.execute(":type let value = 'text'\nvalue"),
+            Ok(Some("String".to_string()))
+        );
+        assert_eq!(repl.execute("value"), Ok(Some("1".to_string())));
+        assert_eq!(repl.execute(":type\tvalue"), Ok(Some("Number".to_string())));
+    }
+
+    #[test]
+    fn ast_command() {
+        let mut repl = repl();
+        let result = repl.execute(":ast 42").unwrap().unwrap();
+        assert!(result.contains("LiteralNumber"));
+        assert!(repl.execute(":ast function 1()").is_err());
+    }
+
+    #[test]
+    fn load_command() {
+        let file = std::env::temp_dir().join("elodie_repl_load_command.ec");
+        fs::write(&file, "let loaded = 'from file'\n").unwrap();
+
+        let mut repl = repl();
+        assert_eq!(
+            repl.execute(&format!(":load {}", file.display())),
+            Ok(Some(format!("Loaded {}", file.display())))
+        );
+        assert_eq!(repl.execute("loaded"), Ok(Some("'from file'".to_string())));
+        assert!(repl.execute(":load /does/not/exist.ec").is_err());
+        fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn unknown_command() {
+        let mut repl = repl();
+        assert!(repl.execute(":unknown").is_err());
+        assert_eq!(
+            repl.execute(":typefoo 1"),
+            Err("Unknown command :typefoo - supported are :type <expression>, :ast <source> and :load <file>".to_string())
+        );
+    }
+}
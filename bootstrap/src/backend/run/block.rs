@@ -9,7 +9,9 @@ impl<'a> Runner<'a> {
 
         for node in &node.nodes {
             if let Some(Interrupt::Return(return_value)) = &self.interrupt {
-                return Ok(return_value.clone());
+                let return_value = return_value.clone();
+                self.scope.leave();
+                return Ok(return_value);
             }
 
             value = self.run_node(node)?;
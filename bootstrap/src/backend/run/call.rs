@@ -1,9 +1,12 @@
 use std::collections::HashMap;
+use std::rc::Rc;
 
 use crate::backend::run::value::{FunctionValue, Value};
 use crate::backend::run::Runner;
 use crate::common::StringTableId;
-use crate::frontend::ast::{AStCallFunctionNode, AstCallFunctionWithLambdaNode};
+use crate::frontend::ast::{
+    AStCallFunctionNode, AstBlockNode, AstCallFunctionWithLambdaNode, AstFunctionArgument, AstType,
+};
 
 impl<'a> Runner<'a> {
     pub(crate) fn run_node_call_function(
@@ -12,15 +15,6 @@ impl<'a> Runner<'a> {
     ) -> crate::backend::run::Result<Value> {
         self.reset_interrupt();
 
-        let mut args: Vec<Value> = Vec::with_capacity(node.arguments.len());
-        for arg in &node.arguments {
-            args.push(self.run_node(arg)?);
-        }
-
-        if let Some(Value::IntrinsicFunction(func)) = self.scope.get_value(&node.function.0) {
-            return func.0(&args);
-        }
-
         let function = if let Some(Value::Function(func)) = self.scope.get_value(&node.function.0) {
             func.clone()
         } else {
@@ -39,6 +33,12 @@ impl<'a> Runner<'a> {
             counter += 1;
         }
 
+        // lambdas with arguments run on top of the frame they got defined in, the frames of
+        // their caller get set aside meanwhile. Once that frame got left they see those as well
+        let hidden = function
+            .frame
+            .and_then(|frame| self.scope.hide_above(frame));
+
         self.scope.enter();
         for arg in &args {
             self.scope.insert_value(arg.0.clone(), arg.1.clone())
@@ -46,6 +46,10 @@ impl<'a> Runner<'a> {
         let result = self.run_block(&function.body);
         self.scope.leave();
 
+        if let Some(hidden) = hidden {
+            self.scope.restore(hidden);
+        }
+
         self.reset_interrupt();
         result
     }
@@ -82,15 +86,8 @@ impl<'a> Runner<'a> {
         }
 
         // last parameter is lambda function
-        let lambda_function = Value::Function(FunctionValue {
-            arguments: vec![],
-            body: node.lambda.clone(),
-        });
-
-        args.insert(
-            function.arguments.last().unwrap().argument.0,
-            lambda_function,
-        );
+        let parameter = function.arguments.last().unwrap();
+        args.insert(parameter.argument.0, self.lambda(&node.lambda, parameter));
 
         self.scope.enter();
         for arg in &args {
@@ -103,6 +100,34 @@ impl<'a> Runner<'a> {
         result
     }
 
+    /// Function value of a lambda passed for the parameter. If the type of the parameter names
+    /// arguments, like it of function(it: Any) -> Any, the lambda gets them passed and runs where
+    /// it got defined. Otherwise it sees the scope of its caller, the blocks of the test runner
+    /// rely on that
+    pub(crate) fn lambda(&self, body: &Rc<AstBlockNode>, parameter: &AstFunctionArgument) -> Value {
+        let arguments = match &parameter.argument_type {
+            Some(AstType::Function { arguments, .. }) => arguments
+                .iter()
+                .filter_map(|argument| {
+                    Some(Rc::new(AstFunctionArgument {
+                        argument: argument.identifier.clone()?,
+                        argument_type: Some(*argument.r#type.clone()),
+                    }))
+                })
+                .collect(),
+            _ => vec![],
+        };
+        let frame = match arguments.is_empty() {
+            true => None,
+            false => Some(self.scope.frame()),
+        };
+        Value::Function(FunctionValue {
+            arguments,
+            body: body.clone(),
+            frame,
+        })
+    }
+
     pub(crate) fn run_node_call(
         &mut self,
         function_value: FunctionValue,
@@ -0,0 +1,56 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::common::{Span, StringTableId, WithSpan};
+use crate::frontend::ast::AstTreeNode;
+
+/// Identifies a node by the file it is in and the start and end index of its span
+pub type NodeKey = (StringTableId, usize, usize);
+
+/// What ran while recording, nodes of sources which were not loaded from a file are left out
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Recording {
+    /// How often each node ran
+    pub nodes: HashMap<NodeKey, usize>,
+    /// How often each branch got taken, keyed by the condition of an if or the loop. Branch 0 is
+    /// the then block or another iteration, branch 1 the otherwise block or leaving the loop
+    pub branches: HashMap<(NodeKey, usize), usize>,
+}
+
+// recording is optional, without it the runner only pays for the lookup
+thread_local! {
+    static RECORDING: RefCell<Option<Recording>> = const { RefCell::new(None) };
+}
+
+pub fn start_recording() {
+    RECORDING.with(|recording| *recording.borrow_mut() = Some(Recording::default()))
+}
+
+/// Stops recording and returns what ran since it started
+pub fn finish_recording() -> Option<Recording> {
+    RECORDING.with(|recording| recording.borrow_mut().take())
+}
+
+pub(crate) fn record_node(node: &AstTreeNode) {
+    RECORDING.with(|recording| {
+        if let Some(recording) = recording.borrow_mut().as_mut() {
+            if let Some(key) = key(&node.span()) {
+                *recording.nodes.entry(key).or_default() += 1;
+            }
+        }
+    })
+}
+
+pub(crate) fn record_branch(span: &Span, branch: usize) {
+    RECORDING.with(|recording| {
+        if let Some(recording) = recording.borrow_mut().as_mut() {
+            if let Some(key) = key(span) {
+                *recording.branches.entry((key, branch)).or_default() += 1;
+            }
+        }
+    })
+}
+
+pub fn key(span: &Span) -> Option<NodeKey> {
+    Some((span.start.file?, span.start.index.0, span.end.index.0))
+}
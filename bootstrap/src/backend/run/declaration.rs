@@ -2,14 +2,13 @@ use std::collections::HashMap;
 use std::ops::Deref;
 use std::rc::Rc;
 
-use crate::backend::run::value::{FunctionValue, IntrinsicFunctionValue, PackageValue, Value};
+use crate::backend::run::value::{FunctionValue, PackageValue, Value};
 use crate::backend::run::Runner;
 use crate::common::node::Node;
 use crate::frontend::ast::{
-    AstDeclareFunctionNode, AstDeclarePackageNode, AstDeclareVariableNode, AstTreeNode,
+    AstDeclareFunctionNode, AstDeclarePackageNode, AstDeclareVariableNode, AstTreeNode, AstType,
     SPAN_NOT_IMPLEMENTED,
 };
-use crate::ir::TypeId;
 
 impl<'a> Runner<'a> {
     pub(crate) fn run_external_function_declaration(
@@ -43,6 +42,7 @@ impl<'a> Runner<'a> {
         let f = Value::Function(FunctionValue {
             body: node.nodes.clone(),
             arguments,
+            frame: None,
         });
 
         self.scope.insert_value(name, f.clone());
@@ -63,6 +63,7 @@ impl<'a> Runner<'a> {
             let f = FunctionValue {
                 body: node.nodes.clone(),
                 arguments,
+                frame: None,
             };
             functions.insert(name, f);
         }
@@ -92,54 +93,24 @@ impl<'a> Runner<'a> {
                     panic!()
                 };
                 self.type_definitions
-                    .add_function(TypeId(99), func_ident, func);
+                    .add_function(node.r#type.0, func_ident, func);
             }
         }
 
+        // external functions are intrinsics, declared with the signature the registry has for them
         let mut external_functions = HashMap::new();
-
         for node in &node.external_functions {
-            // dbg!(node);
-            // println!("{}", self.ctx.get_str(node.identifier.0));
-
-            let function = self.ctx.get_str(node.function.0);
-            // FIXME load
-
-            let print_colors = self.print_colors.clone();
-            match function {
-                "cos_f64" => {
-                    external_functions.insert(
-                        node.function.0,
-                        IntrinsicFunctionValue(Rc::new(move |args: &[Value]| {
-                            let Value::Number(arg) = args.get(0).cloned().unwrap() else {
-                                panic!()
-                            };
-
-                            Ok(Value::Number(arg.cos()))
-                        })),
-                    );
-                }
-                "print" => {
-                    external_functions.insert(
-                        node.function.0,
-                        IntrinsicFunctionValue(Rc::new(move |args: &[Value]| {
-                            for arg in args {
-                                if arg.to_string() == "\\n" {
-                                    println!();
-                                } else {
-                                    if print_colors {
-                                        print!("{} ", arg.to_string().replace("\\x1b", "\x1b"));
-                                    } else {
-                                        print!("{} ", arg.to_string())
-                                    }
-                                }
-                            }
-                            Ok(Value::Unit)
-                        })),
-                    );
-                }
-                _ => unimplemented!("{function}"),
-            }
+            let arguments: Vec<Option<&AstType>> = node
+                .arguments
+                .iter()
+                .map(|argument| argument.argument_type.as_ref())
+                .collect();
+            let intrinsic = self.intrinsics.resolve(
+                self.ctx.get_str(node.function.0),
+                &arguments,
+                node.return_type.as_ref(),
+            )?;
+            external_functions.insert(node.function.0, intrinsic.function.clone());
         }
 
         Ok(Value::Package(PackageValue {
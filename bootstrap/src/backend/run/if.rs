@@ -1,5 +1,7 @@
+use crate::backend::run::coverage::record_branch;
 use crate::backend::run::value::Value;
 use crate::backend::run::Runner;
+use crate::common::WithSpan;
 use crate::frontend::ast::AstIfNode;
 
 impl<'a> Runner<'a> {
@@ -7,6 +9,7 @@ impl<'a> Runner<'a> {
         let condition = self.run_node(&node.condition)?;
         match condition {
             Value::Bool(v) => {
+                record_branch(&node.condition.span(), if v { 0 } else { 1 });
                 if v {
                     self.scope.enter();
                     // let result = self.interpret_expression(&Expression::Block(expr.then.clone()));
@@ -1,5 +1,7 @@
+use crate::backend::run::coverage::record_branch;
 use crate::backend::run::value::Value;
 use crate::backend::run::{Interrupt, Runner};
+use crate::common::Span;
 use crate::frontend::ast::{AstBreakLoopNode, AstContinueLoopNode, AstLoopNode};
 
 impl<'a> Runner<'a> {
@@ -24,7 +26,12 @@ impl<'a> Runner<'a> {
         Ok(value)
     }
 
-    pub(crate) fn run_loop(&mut self, node: &AstLoopNode) -> crate::backend::run::Result<Value> {
+    // coverage records another iteration as branch 0 and leaving the loop as branch 1
+    pub(crate) fn run_loop(
+        &mut self,
+        node: &AstLoopNode,
+        span: &Span,
+    ) -> crate::backend::run::Result<Value> {
         'main: loop {
             self.scope.enter();
 
@@ -39,22 +46,27 @@ impl<'a> Runner<'a> {
                     let interrupt = interrupt.clone();
                     match interrupt {
                         Interrupt::Break(v) => {
+                            record_branch(span, 1);
                             self.reset_interrupt();
                             self.scope.leave();
                             return Ok(v.clone());
                         }
                         Interrupt::Continue => {
+                            record_branch(span, 0);
                             self.reset_interrupt();
                             self.scope.leave();
                             continue 'main;
                         }
                         Interrupt::Return(v) => {
+                            record_branch(span, 1);
                             self.scope.leave();
                             return Ok(v);
                         }
                     }
                 }
             }
+            self.scope.leave();
+            record_branch(span, 0);
         }
     }
 }
@@ -1,35 +1,54 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io;
-use std::io::Read;
+use std::fmt;
 use std::ops::Deref;
-use std::path::PathBuf;
+use std::path::Path;
 use std::process::exit;
 use std::rc::Rc;
 
+use crate::backend::intrinsic;
+use crate::backend::intrinsic::Intrinsics;
 use crate::backend::run::scope::Scope;
-use crate::backend::run::type_definitions::TypeDefinitions;
-use crate::backend::run::value::Value::{IntrinsicFunction, Unit};
+use crate::backend::run::type_definitions::{type_of, TypeDefinitions};
+use crate::backend::run::value::Value::Unit;
 use crate::backend::run::value::{IntrinsicFunctionValue, ListValue, ObjectValue, Value};
 use crate::common::node::{CalculationOperator, CompareOperator, Node};
+use crate::common::WithSpan;
 use crate::frontend::ast::AstTreeNode;
 use crate::frontend::context::Context;
-use crate::frontend::{ast, ast_from_str};
+use crate::frontend::{ast, ast_from_file};
 use crate::ir::{Type, TypeId, TypeName, TypeVariable};
-use crate::{frontend, load_library_file};
+use crate::{frontend, library_file_path};
 
 mod block;
 mod call;
+pub mod coverage;
 mod declaration;
 mod r#if;
 mod r#loop;
+pub mod output;
 pub mod scope;
 pub mod type_definitions;
 pub mod value;
 
 #[derive(Debug)]
-pub enum Error {}
+pub enum Error {
+    Intrinsic(intrinsic::Error),
+}
+
+impl From<intrinsic::Error> for Error {
+    fn from(value: intrinsic::Error) -> Self {
+        Self::Intrinsic(value)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Intrinsic(err) => write!(f, "{err}"),
+        }
+    }
+}
 
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
@@ -38,7 +57,7 @@ pub struct Runner<'a> {
     scope: Scope,
     pub interrupt: Option<Interrupt>,
     type_definitions: TypeDefinitions,
-    pub print_colors: bool,
+    intrinsics: &'a Intrinsics,
 }
 
 #[derive(Debug, Clone)]
@@ -48,122 +67,47 @@ pub enum Interrupt {
     Return(Value),
 }
 
-pub fn run_file(file: &String, print_colors: bool) {
-    fn load_text_from_file(path: &str) -> io::Result<String> {
-        let mut file = File::open(path)?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
-        Ok(contents)
+pub fn run_file(file: &String, arguments: Vec<String>, print_colors: bool) {
+    let mut ctx = Context::new();
+    let intrinsics = Intrinsics::new(&mut ctx, print_colors).with_arguments(arguments);
+    let (scope, definitions) = load_prelude(&mut ctx, &intrinsics);
+
+    let source_file = match ast_from_file(&mut ctx, Path::new(file)) {
+        Ok(source_file) => source_file,
+        Err(err) => {
+            eprintln!("{err}");
+            exit(1)
+        }
+    };
+
+    if let Err(err) = run(&mut ctx, scope, definitions, source_file, &intrinsics) {
+        eprintln!("{err}");
+        exit(1)
     }
+}
 
-    let mut ctx = Context::new();
-    let mut root_values = HashMap::new();
-    let mut root_types = HashMap::new();
-
-    // root_values.insert(ctx.string_table.insert("ec_io_print"), IntrinsicFunction(IntrinsicFunctionValue(Rc::new(|args: &[Value]| {
-    //     for arg in args {
-    //         if arg.to_string() == "\\n" {
-    //             println!();
-    //         } else {
-    //             print!("{} ", arg.to_string().replace("\\x1b", "\x1b"));
-    //         }
-    //     }
-    //     Ok(Value::Unit)
-    // }))));
-
-    let mut intrinsics = ObjectValue::new();
-    intrinsics.set_property(
-        ctx.string_table.push_str("print"),
-        IntrinsicFunction(IntrinsicFunctionValue(Rc::new(|args: &[Value]| {
-            for arg in args {
-                if arg.to_string() == "\\n" {
-                    println!();
-                } else {
-                    print!("{} ", arg.to_string().replace("\\x1b", "\x1b"));
-                }
-            }
-            Ok(Value::Unit)
-        }))),
-    );
-
-    intrinsics.set_property(
-        ctx.string_table.push_str("list_length"),
-        IntrinsicFunction(IntrinsicFunctionValue(Rc::new(|args| {
-            let Value::List(list) = args.get(0).unwrap() else {
-                panic!("not list")
-            };
-            let len: u32 = list.0.borrow().len() as u32;
-            Ok(Value::Number(len.into()))
-        }))),
-    );
-
-    intrinsics.set_property(
-        ctx.string_table.push_str("list_append"),
-        IntrinsicFunction(IntrinsicFunctionValue(Rc::new(|args| {
-            let Value::List(list) = args.get(0).unwrap() else {
-                panic!("not list")
-            };
-            let arg = args.get(1).cloned().unwrap();
-            list.0.borrow_mut().push(arg);
-            Ok(Value::Unit)
-        }))),
-    );
-
-    intrinsics.set_property(
-        ctx.string_table.push_str("list_get"),
-        IntrinsicFunction(IntrinsicFunctionValue(Rc::new(|args| {
-            let Value::List(list) = args.get(0).unwrap() else {
-                panic!("not list")
-            };
-            let Value::Number(arg) = args.get(1).cloned().unwrap() else {
-                panic!("not a number")
-            };
-            Ok(list.0.borrow().get(arg as usize - 1).cloned().unwrap())
-        }))),
-    );
-
-    intrinsics.set_property(
-        ctx.string_table.push_str("exit"),
-        IntrinsicFunction(IntrinsicFunctionValue(Rc::new(|args| {
-            let Value::Number(code) = args.get(0).cloned().unwrap() else {
-                panic!("not a number")
-            };
-            exit(code as i32)
-        }))),
-    );
-
-    root_values.insert(
-        ctx.string_table.push_str("intrinsics"),
-        Value::Object(intrinsics),
-    );
+/// Creates a scope with core and std loaded into it
+pub(crate) fn load_prelude(ctx: &mut Context, intrinsics: &Intrinsics) -> (Scope, TypeDefinitions) {
+    let root_values = HashMap::new();
+    let root_types = HashMap::new();
     let scope = Scope::new(root_values, root_types);
 
     let (scope, definitions) = {
-        let std_content = load_library_file("core/index.ec").unwrap();
-        let std_file = ast_from_str(&mut ctx, std_content.as_str()).unwrap();
+        let std_file = ast_from_file(ctx, &library_file_path("core/index.ec")).unwrap();
         run(
-            &mut ctx,
+            ctx,
             scope,
             TypeDefinitions {
                 definitions: Default::default(),
             },
             std_file,
-            true,
+            intrinsics,
         )
         .unwrap()
     };
 
-    let (scope, definitions) = {
-        let std_content = load_library_file("std/index.ec").unwrap();
-        let std_file = ast_from_str(&mut ctx, std_content.as_str()).unwrap();
-        run(&mut ctx, scope, definitions, std_file, true).unwrap()
-    };
-
-    let mut path = PathBuf::from(file);
-    let content = load_text_from_file(path.to_str().unwrap()).unwrap();
-    let source_file = ast_from_str(&mut ctx, content.as_str()).unwrap();
-
-    run(&mut ctx, scope, definitions, source_file, true).unwrap();
+    let std_file = ast_from_file(ctx, &library_file_path("std/index.ec")).unwrap();
+    run(ctx, scope, definitions, std_file, intrinsics).unwrap()
 }
 
 pub fn run(
@@ -171,26 +115,42 @@ pub fn run(
     scope: Scope,
     definitions: TypeDefinitions,
     file: frontend::Ast,
-    print_colors: bool,
+    intrinsics: &Intrinsics,
 ) -> Result<(Scope, TypeDefinitions)> {
-    let mut runner = Runner::new(ctx, scope, definitions, print_colors);
+    let mut runner = Runner::new(ctx, scope, definitions, intrinsics);
     runner.run(file)?;
     Ok((runner.scope, runner.type_definitions))
 }
 
+/// Runs the file like [run] and returns the value of its last node
+pub fn evaluate(
+    ctx: &mut Context,
+    scope: Scope,
+    definitions: TypeDefinitions,
+    file: frontend::Ast,
+    intrinsics: &Intrinsics,
+) -> Result<(Scope, TypeDefinitions, Value)> {
+    let mut runner = Runner::new(ctx, scope, definitions, intrinsics);
+    let mut result = Value::Unit;
+    for node in &file.nodes {
+        result = runner.run_node(node)?;
+    }
+    Ok((runner.scope, runner.type_definitions, result))
+}
+
 impl<'a> Runner<'a> {
     pub(crate) fn new(
         ctx: &'a mut Context,
         scope: Scope,
         definitions: TypeDefinitions,
-        print_colors: bool,
+        intrinsics: &'a Intrinsics,
     ) -> Self {
         Self {
             ctx,
             scope,
             interrupt: None,
             type_definitions: definitions,
-            print_colors,
+            intrinsics,
         }
     }
 
@@ -202,6 +162,7 @@ impl<'a> Runner<'a> {
     }
 
     pub(crate) fn run_node(&mut self, node: &AstTreeNode) -> Result<Value> {
+        coverage::record_node(node);
         match node.node() {
             Node::BreakLoop(break_node) => self.run_break(break_node),
 
@@ -221,7 +182,7 @@ impl<'a> Runner<'a> {
                 object,
                 function,
                 arguments,
-                ..
+                lambda,
             }) => {
                 // let some_arg_value = if let Node::CallFunction(arg_1) = &arguments[0] {
                 //     let value = self.run_call_function(arg_1)?.clone();
@@ -242,93 +203,37 @@ impl<'a> Runner<'a> {
                     args.push(self.run_node(arg)?); // Now we can mutably borrow `self` without conflict
                 }
 
-                if let Value::List(object) = self.scope.get_value(&object.0).unwrap() {
-                    let mut args = HashMap::with_capacity(arguments.len());
-                    args.insert(
-                        self.ctx.string_table.push_str("self"),
-                        Value::List(object.clone()),
-                    );
-
-                    let mut counter = 0;
-
-                    let func =
-                        if let Some(Value::Function(func)) = self.scope.get_value(&function.0) {
-                            func.clone()
-                        } else {
-                            todo!()
-                        };
-
-                    for arg in arguments {
-                        let arg_node = func.arguments.get(counter).unwrap();
-
-                        let name = arg_node.argument.0;
-                        // FIXME resolve  name from definition
-                        args.insert(name, self.run_node(arg)?);
-                        counter += 1;
-                    }
-
-                    // let mut args = HashMap::with_capacity(arguments.len());
-
-                    // args.extend(&direct_args);
-
-                    let func = self.type_definitions.get_function(&TypeId(99), &function.0);
-                    self.scope.enter();
-
-                    let result = self.run_node_call(func.clone(), args);
-
-                    self.scope.leave();
-
-                    return result;
+                // intrinsics are no value of the scope, they get looked up by name
+                if obj_name == "intrinsics" {
+                    let intrinsic = self.intrinsics.get(self.ctx.get_str(function.0))?;
+                    return intrinsic.function.0(args.as_slice());
                 }
 
-                let Value::Object(object) = self.scope.get_value(&object.0).unwrap() else {
+                let object = self.scope.get_value(&object.0).unwrap().clone();
+                let Some(type_name) = type_of(&object, &mut self.ctx.string_table) else {
                     panic!()
                 };
+                let func = self.type_definitions.get_function(&type_name, &function.0);
 
-                // FIXME
-                if obj_name == "intrinsics" {
-                    // println!("{}", self.ctx.get_str(function.0));
-                    let func = object
-                        .get_property_host_function(&function.clone())
-                        .unwrap();
-
-                    let mut args = Vec::with_capacity(arguments.len());
-                    for arg in arguments {
-                        if let Node::AccessVariable(load_varialbe_node) = arg.node() {
-                            let value = self
-                                .scope
-                                .get_value(&load_varialbe_node.variable.0)
-                                .unwrap()
-                                .clone();
-                            args.push(value);
-                        } else if let Node::LiteralString(node) = arg.node() {
-                            args.push(Value::String(self.ctx.get_str(node.0.value).to_string()));
-                        } else if let Node::LiteralNumber(node) = arg.node() {
-                            args.push(Value::Number(
-                                self.ctx.get_str(node.0.value()).parse().unwrap(),
-                            ));
-                        } else {
-                            unimplemented!("{:#?}", arg);
-                        }
-                    }
+                // the lambda is the argument after the ones given in parentheses
+                if let Some(lambda) = lambda {
+                    let parameter = &func.arguments[args.len()];
+                    args.push(self.lambda(lambda, parameter));
+                }
 
-                    return func.0(args.as_slice());
-                } else {
-                    let mut args = HashMap::with_capacity(arguments.len());
-                    args.insert(
-                        self.ctx.string_table.push_str("self"),
-                        Value::Object(object.clone()),
-                    );
+                let mut call_args = HashMap::with_capacity(args.len() + 1);
+                call_args.insert(self.ctx.string_table.push_str("self"), object);
+                for (argument, value) in func.arguments.iter().zip(args) {
+                    call_args.insert(argument.argument.0, value);
+                }
 
-                    let func = self.type_definitions.get_function(&TypeId(99), &function.0);
-                    self.scope.enter();
+                self.scope.enter();
 
-                    let result = self.run_node_call(func.clone(), args);
+                let result = self.run_node_call(func.clone(), call_args);
 
-                    self.scope.leave();
+                self.scope.leave();
 
-                    return result;
-                };
+                return result;
             }
 
             Node::CallFunctionOfPackage(ast::AstCallFunctionOfPackageNode {
@@ -371,22 +276,7 @@ impl<'a> Runner<'a> {
                 {
                     let mut args = Vec::with_capacity(arguments.len());
                     for arg in arguments {
-                        if let Node::AccessVariable(load_varialbe_node) = arg.node() {
-                            let value = self
-                                .scope
-                                .get_value(&load_varialbe_node.variable.0)
-                                .unwrap()
-                                .clone();
-                            args.push(value);
-                        } else if let Node::LiteralString(node) = arg.node() {
-                            args.push(Value::String(self.ctx.get_str(node.0.value).to_string()));
-                        } else if let Node::LiteralNumber(node) = arg.node() {
-                            args.push(Value::Number(
-                                self.ctx.get_str(node.0.value()).parse().unwrap(),
-                            ))
-                        } else {
-                            unimplemented!("{:#?}", arg);
-                        }
+                        args.push(self.run_node(arg)?);
                     }
 
                     return func(args.as_slice());
@@ -441,14 +331,14 @@ impl<'a> Runner<'a> {
                 }
             }
             Node::LiteralString(node) => {
-                Ok(Value::String(self.ctx.get_str(node.0.value).to_string()))
+                Ok(Value::String(unescape(self.ctx.get_str(node.0.value))))
             }
             Node::LiteralNumber(node) => Ok(Value::Number(
                 self.ctx.get_str(node.0.value).parse().unwrap(),
             )),
             Node::LiteralBoolean(node) => Ok(Value::Bool(self.ctx.get_str(node.0.value) == "true")),
 
-            Node::Loop(loop_node) => self.run_loop(loop_node),
+            Node::Loop(loop_node) => self.run_loop(loop_node, &node.span()),
             Node::If(if_node) => self.run_if(if_node),
 
             Node::Block(block_node) => self.run_block(block_node),
@@ -544,22 +434,26 @@ impl<'a> Runner<'a> {
                     return Ok(Value::List(ListValue(Rc::new(RefCell::new(vec![])))));
                 }
 
-                let obj = Value::Object(ObjectValue { properties });
+                let obj = Value::Object(ObjectValue {
+                    type_name: Some(node.r#type.0),
+                    properties,
+                });
 
                 // self.scope.insert_value(node.identifier.0.to_string(), obj.clone());
 
                 Ok(obj)
             }
             Node::DefineType(node) => {
-                let func_ident = node.functions.get(0).unwrap().function.0;
-                let func = node.functions.get(0).unwrap().clone();
-                let value = self.run_function_declaration(&func)?;
+                for func in &node.functions {
+                    let func_ident = func.function.0;
+                    let value = self.run_function_declaration(func)?;
 
-                let Value::Function(func) = value else {
-                    panic!()
-                };
-                self.type_definitions
-                    .add_function(TypeId(99), func_ident, func);
+                    let Value::Function(func) = value else {
+                        panic!()
+                    };
+                    self.type_definitions
+                        .add_function(node.r#type.0, func_ident, func);
+                }
 
                 Ok(Value::Unit)
             }
@@ -596,3 +490,38 @@ impl<'a> Runner<'a> {
         self.interrupt = None
     }
 }
+
+/// Text of a string literal, its escape sequences stay the way they are written until it runs
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            result.push(char);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('\\') => result.push('\\'),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::run::unescape;
+
+    #[test]
+    fn unescapes_text() {
+        assert_eq!(unescape("a\\nb\\tc"), "a\nb\tc");
+        assert_eq!(unescape("printf(\"\\\\n\")"), "printf(\"\\n\")");
+        assert_eq!(unescape("\\x1b\\"), "\\x1b\\");
+    }
+}
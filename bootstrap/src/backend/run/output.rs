@@ -0,0 +1,57 @@
+use std::cell::RefCell;
+
+// the innermost capture receives everything printed, without any capture it goes to stdout
+thread_local! {
+    static CAPTURES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Writes text of the print intrinsic
+pub fn print(text: &str) {
+    CAPTURES.with(|captures| match captures.borrow_mut().last_mut() {
+        Some(capture) => capture.push_str(text),
+        None => print!("{text}"),
+    })
+}
+
+/// Captures everything printed until the matching end_capture
+pub fn start_capture() {
+    CAPTURES.with(|captures| captures.borrow_mut().push(String::new()))
+}
+
+/// Stops the innermost capture and returns what got printed while it was active
+pub fn end_capture() -> String {
+    CAPTURES.with(|captures| captures.borrow_mut().pop().unwrap_or_default())
+}
+
+/// Stops all captures and returns what got printed while they were active, outermost first. A
+/// panic might have left inner captures behind
+pub fn end_all_captures() -> String {
+    CAPTURES.with(|captures| captures.borrow_mut().drain(..).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::run::output::{end_all_captures, end_capture, print, start_capture};
+
+    #[test]
+    fn captures_nested() {
+        start_capture();
+        print("outer ");
+        start_capture();
+        print("inner");
+        assert_eq!(end_capture(), "inner");
+        print("again");
+        assert_eq!(end_capture(), "outer again");
+        assert_eq!(end_capture(), "");
+    }
+
+    #[test]
+    fn ends_all_captures() {
+        start_capture();
+        print("outer ");
+        start_capture();
+        print("inner");
+        assert_eq!(end_all_captures(), "outer inner");
+        assert_eq!(end_capture(), "");
+    }
+}
@@ -4,10 +4,27 @@ use crate::backend::run::value::Value;
 use crate::common::StringTableId;
 use crate::ir::Type;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Scope {
     pub values: Vec<HashMap<StringTableId, Value>>,
     pub types: Vec<HashMap<StringTableId, Type>>,
+    frames: Vec<usize>,
+    next_frame: usize,
+}
+
+/// Innermost frame of the scope at some point, lambdas remember the one they got defined in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame {
+    depth: usize,
+    id: usize,
+}
+
+/// Frames set aside while a lambda runs on top of the frame it got defined in
+#[derive(Debug)]
+pub struct Hidden {
+    values: Vec<HashMap<StringTableId, Value>>,
+    types: Vec<HashMap<StringTableId, Type>>,
+    frames: Vec<usize>,
 }
 
 impl Scope {
@@ -18,6 +35,8 @@ impl Scope {
         Self {
             values: vec![root_values],
             types: vec![root_types],
+            frames: vec![0],
+            next_frame: 1,
         }
     }
 
@@ -50,10 +69,88 @@ impl Scope {
     pub fn enter(&mut self) {
         self.values.push(HashMap::new());
         self.types.push(HashMap::new());
+        self.frames.push(self.next_frame);
+        self.next_frame += 1;
     }
 
     pub fn leave(&mut self) {
         self.values.pop().unwrap();
         self.types.pop().unwrap();
+        self.frames.pop().unwrap();
+    }
+
+    pub fn frame(&self) -> Frame {
+        Frame {
+            depth: self.frames.len(),
+            id: *self.frames.last().unwrap(),
+        }
+    }
+
+    /// Sets the frames above the given one aside until they get restored, None if the frame got
+    /// left already
+    pub fn hide_above(&mut self, frame: Frame) -> Option<Hidden> {
+        if self.frames.get(frame.depth.checked_sub(1)?) != Some(&frame.id) {
+            return None;
+        }
+        Some(Hidden {
+            values: self.values.split_off(frame.depth),
+            types: self.types.split_off(frame.depth),
+            frames: self.frames.split_off(frame.depth),
+        })
+    }
+
+    pub fn restore(&mut self, hidden: Hidden) {
+        self.values.extend(hidden.values);
+        self.types.extend(hidden.types);
+        self.frames.extend(hidden.frames);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::backend::run::scope::Scope;
+    use crate::backend::run::value::Value;
+    use crate::common::StringTableId;
+
+    fn number(scope: &Scope, key: usize) -> Option<f64> {
+        match scope.get_value(&StringTableId(key)) {
+            Some(Value::Number(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn hides_frames_above() {
+        let mut scope = Scope::new(HashMap::new(), HashMap::new());
+        scope.enter();
+        scope.insert_value(StringTableId(1), Value::Number(1.0));
+        let frame = scope.frame();
+
+        scope.enter();
+        scope.insert_value(StringTableId(1), Value::Number(2.0));
+        scope.insert_value(StringTableId(2), Value::Number(2.0));
+
+        let hidden = scope.hide_above(frame).unwrap();
+        assert_eq!(number(&scope, 1), Some(1.0));
+        assert_eq!(number(&scope, 2), None);
+
+        scope.restore(hidden);
+        assert_eq!(number(&scope, 1), Some(2.0));
+        assert_eq!(number(&scope, 2), Some(2.0));
+    }
+
+    #[test]
+    fn knows_left_frames() {
+        let mut scope = Scope::new(HashMap::new(), HashMap::new());
+        scope.enter();
+        let frame = scope.frame();
+        scope.leave();
+        assert!(scope.hide_above(frame).is_none());
+
+        // another frame at the same depth is not the one the lambda got defined in
+        scope.enter();
+        assert!(scope.hide_above(frame).is_none());
     }
 }
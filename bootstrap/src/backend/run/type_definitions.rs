@@ -1,45 +1,65 @@
 use std::collections::HashMap;
 
-use crate::backend::run::value::FunctionValue;
-use crate::common::StringTableId;
-use crate::ir::TypeId;
+use crate::backend::run::value::{FunctionValue, Value};
+use crate::common::{StringTable, StringTableId};
 
-#[derive(Debug)]
+/// Name of the type whose definition has the functions which can be called on a value
+pub fn type_of(value: &Value, strings: &mut StringTable) -> Option<StringTableId> {
+    match value {
+        Value::List(_) => Some(strings.push_str("List")),
+        Value::String(_) => Some(strings.push_str("String")),
+        Value::Object(object) => object.type_name,
+        _ => None,
+    }
+}
+
+/// Functions of define blocks, by the name of the type they define
+#[derive(Debug, Clone)]
 pub struct TypeDefinitions {
-    pub definitions: HashMap<TypeId, TypeDefinition>,
+    pub definitions: HashMap<StringTableId, TypeDefinition>,
 }
 
 impl TypeDefinitions {
-    pub fn add_function(&mut self, type_id: TypeId, fn_id: StringTableId, value: FunctionValue) {
-        if let Some(type_def) = self.definitions.get_mut(&type_id) {
+    pub fn add_function(
+        &mut self,
+        type_name: StringTableId,
+        fn_id: StringTableId,
+        value: FunctionValue,
+    ) {
+        if let Some(type_def) = self.definitions.get_mut(&type_name) {
             type_def.functions.insert(fn_id, value);
         } else {
             let mut new_type_def = TypeDefinition {
                 functions: HashMap::new(),
             };
             new_type_def.functions.insert(fn_id, value);
-            self.definitions.insert(type_id, new_type_def);
+            self.definitions.insert(type_name, new_type_def);
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypeDefinition {
     pub functions: HashMap<StringTableId, FunctionValue>,
 }
 
 impl TypeDefinitions {
-    pub fn insert_function(&mut self, type_id: TypeId, idx: StringTableId, value: FunctionValue) {
+    pub fn insert_function(
+        &mut self,
+        type_name: StringTableId,
+        idx: StringTableId,
+        value: FunctionValue,
+    ) {
         self.definitions
-            .get_mut(&type_id)
+            .get_mut(&type_name)
             .unwrap()
             .functions
             .insert(idx, value);
     }
 
-    pub fn get_function(&self, type_id: &TypeId, idx: &StringTableId) -> FunctionValue {
+    pub fn get_function(&self, type_name: &StringTableId, idx: &StringTableId) -> FunctionValue {
         self.definitions
-            .get(type_id)
+            .get(type_name)
             .unwrap()
             .functions
             .get(idx)
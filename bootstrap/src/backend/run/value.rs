@@ -3,16 +3,15 @@ use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 
+use crate::backend::run::scope::Frame;
 use crate::common::StringTableId;
 use crate::frontend::ast::node::AstNode;
-use crate::frontend::ast::{AstBlockNode, AstFunctionArgument, AstIdentifier};
+use crate::frontend::ast::{AstBlockNode, AstFunctionArgument};
 
 #[derive(Debug, Clone)]
 pub enum Value {
     Bool(bool),
     Function(FunctionValue),
-    #[deprecated]
-    IntrinsicFunction(IntrinsicFunctionValue),
     List(ListValue),
     Number(f64),
     F64(f64),
@@ -20,7 +19,6 @@ pub enum Value {
     Package(PackageValue),
     String(String),
     Tuple(TupleValue),
-    // FIXME make intrinsics not a normal object - have some Intrinsic value instead
     Unit,
 }
 
@@ -28,7 +26,6 @@ impl Value {
     pub fn to_string(&self) -> String {
         match self {
             Value::Bool(v) => v.to_string(),
-            Value::IntrinsicFunction(_) => "[IntrinsicFunction]".to_string(),
             Value::Function(_) => "[Function]".to_string(),
             Value::Number(v) => v.to_string(),
             Value::F64(v) => v.to_string(),
@@ -43,7 +40,6 @@ impl Value {
 }
 
 #[derive(Clone)]
-#[deprecated]
 pub struct IntrinsicFunctionValue(pub Rc<dyn Fn(&[Value]) -> crate::backend::run::Result<Value>>);
 
 impl Debug for IntrinsicFunctionValue {
@@ -56,6 +52,9 @@ impl Debug for IntrinsicFunctionValue {
 pub struct FunctionValue {
     pub arguments: Vec<Rc<AstFunctionArgument>>,
     pub body: Rc<AstBlockNode>,
+    /// Where a lambda with arguments got defined, it sees the scope there instead of the one of
+    /// its caller
+    pub frame: Option<Frame>,
 }
 
 #[derive(Debug, Clone)]
@@ -84,12 +83,15 @@ impl PackageValue {
 
 #[derive(Debug, Clone)]
 pub struct ObjectValue {
+    /// Type the object got instantiated as, none for objects of the host like results
+    pub type_name: Option<StringTableId>,
     pub properties: HashMap<StringTableId, Value>,
 }
 
 impl ObjectValue {
     pub fn new() -> Self {
         Self {
+            type_name: None,
             properties: HashMap::new(),
         }
     }
@@ -101,18 +103,6 @@ impl ObjectValue {
     pub fn get_property(&self, key: &StringTableId) -> Option<&Value> {
         self.properties.get(key)
     }
-
-    #[deprecated]
-    pub fn get_property_host_function(
-        &self,
-        identifier: &AstIdentifier,
-    ) -> Option<&IntrinsicFunctionValue> {
-        if let Some(Value::IntrinsicFunction(result)) = &self.properties.get(&identifier.0) {
-            Some(result)
-        } else {
-            None
-        }
-    }
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,144 @@
+use crate::backend::run::value::Value;
+
+/// Structural equality of values, as required by expect(actual).to_equal(expected)
+pub(crate) fn equals(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Bool(left), Value::Bool(right)) => left == right,
+        (Value::Number(left), Value::Number(right)) => left == right,
+        (Value::F64(left), Value::F64(right)) => left == right,
+        (Value::String(left), Value::String(right)) => left == right,
+        (Value::Unit, Value::Unit) => true,
+        (Value::List(left), Value::List(right)) => {
+            let (left, right) = (left.0.borrow(), right.0.borrow());
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right.iter())
+                    .all(|(left, right)| equals(left, right))
+        }
+        (Value::Object(left), Value::Object(right)) => {
+            left.properties.len() == right.properties.len()
+                && left.properties.iter().all(|(key, left)| {
+                    right
+                        .properties
+                        .get(key)
+                        .is_some_and(|right| equals(left, right))
+                })
+        }
+        _ => false,
+    }
+}
+
+/// Whether a list contains an element equal to the value or a string contains the value
+pub(crate) fn contains(container: &Value, value: &Value) -> bool {
+    match (container, value) {
+        (Value::List(list), value) => list.0.borrow().iter().any(|item| equals(item, value)),
+        (Value::String(string), value) => string.contains(&value.to_string()),
+        _ => false,
+    }
+}
+
+/// Values this long or spanning multiple lines get shown as a diff instead of next to each other
+const DIFF_THRESHOLD: usize = 40;
+
+pub(crate) fn needs_diff(expected: &str, actual: &str) -> bool {
+    expected.contains('\n')
+        || actual.contains('\n')
+        || expected.len() > DIFF_THRESHOLD
+        || actual.len() > DIFF_THRESHOLD
+}
+
+/// Line based diff from expected to actual, removed lines are prefixed with -, added ones with +
+pub(crate) fn diff(expected: &str, actual: &str) -> Vec<String> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // longest common subsequence of the lines following each position
+    let mut common = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for e in (0..expected.len()).rev() {
+        for a in (0..actual.len()).rev() {
+            common[e][a] = if expected[e] == actual[a] {
+                common[e + 1][a + 1] + 1
+            } else {
+                common[e + 1][a].max(common[e][a + 1])
+            };
+        }
+    }
+
+    let mut result = vec![];
+    let (mut e, mut a) = (0, 0);
+    while e < expected.len() && a < actual.len() {
+        if expected[e] == actual[a] {
+            result.push(format!("  {}", expected[e]));
+            e += 1;
+            a += 1;
+        } else if common[e + 1][a] >= common[e][a + 1] {
+            result.push(format!("- {}", expected[e]));
+            e += 1;
+        } else {
+            result.push(format!("+ {}", actual[a]));
+            a += 1;
+        }
+    }
+    result.extend(expected[e..].iter().map(|line| format!("- {line}")));
+    result.extend(actual[a..].iter().map(|line| format!("+ {line}")));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use crate::backend::run::value::{ListValue, Value};
+    use crate::backend::test::assertion::{contains, diff, equals, needs_diff};
+
+    fn list(values: Vec<Value>) -> Value {
+        Value::List(ListValue(Rc::new(RefCell::new(values))))
+    }
+
+    #[test]
+    fn compares_values() {
+        assert!(equals(&Value::Number(1.0), &Value::Number(1.0)));
+        assert!(!equals(&Value::Number(1.0), &Value::Number(2.0)));
+        assert!(equals(
+            &Value::String("a".to_string()),
+            &Value::String("a".to_string())
+        ));
+        assert!(!equals(
+            &Value::String("1".to_string()),
+            &Value::Number(1.0)
+        ));
+        assert!(equals(
+            &list(vec![Value::Number(1.0), Value::Bool(true)]),
+            &list(vec![Value::Number(1.0), Value::Bool(true)])
+        ));
+        assert!(!equals(
+            &list(vec![Value::Number(1.0)]),
+            &list(vec![Value::Number(1.0), Value::Number(1.0)])
+        ));
+    }
+
+    #[test]
+    fn checks_containment() {
+        let numbers = list(vec![Value::Number(1.0), Value::Number(2.0)]);
+        assert!(contains(&numbers, &Value::Number(2.0)));
+        assert!(!contains(&numbers, &Value::Number(3.0)));
+        assert!(contains(
+            &Value::String("elodie".to_string()),
+            &Value::String("lod".to_string())
+        ));
+        assert!(!contains(&Value::Number(12.0), &Value::Number(1.0)));
+    }
+
+    #[test]
+    fn diffs_lines() {
+        assert!(!needs_diff("1", "2"));
+        assert!(needs_diff("a\nb", "a"));
+
+        assert_eq!(
+            diff("a\nb\nc\nd", "a\nc\nx\nd\ne"),
+            vec!["  a", "- b", "  c", "+ x", "  d", "+ e"]
+        );
+    }
+}
@@ -0,0 +1,457 @@
+use std::collections::{BTreeMap, HashSet};
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::backend::run::coverage::{key, NodeKey, Recording};
+use crate::common::node::Node;
+use crate::common::WithSpan;
+use crate::frontend::ast::{
+    AstBlockNode, AstDeclareFunctionNode, AstDeclarePackageNode, AstTreeNode,
+};
+use crate::frontend::context::Context;
+use crate::test_runner_file_path;
+
+/// Coverage of a single file, lines are 1 based
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FileCoverage {
+    /// How often the statements starting on a line ran, for every line with a statement
+    pub lines: BTreeMap<usize, usize>,
+    /// How often both branches got taken, by line and index of the condition or loop
+    pub branches: BTreeMap<(usize, usize), [usize; 2]>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Coverage {
+    pub files: BTreeMap<PathBuf, FileCoverage>,
+}
+
+/// Covered out of all lines or branches
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Ratio {
+    pub covered: usize,
+    pub total: usize,
+}
+
+impl Ratio {
+    pub fn add(&mut self, other: Ratio) {
+        self.covered += other.covered;
+        self.total += other.total;
+    }
+
+    /// Nothing to cover counts as fully covered
+    pub fn percentage(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.covered as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+impl FileCoverage {
+    pub fn lines(&self) -> Ratio {
+        Ratio {
+            covered: self.lines.values().filter(|hits| **hits > 0).count(),
+            total: self.lines.len(),
+        }
+    }
+
+    pub fn branches(&self) -> Ratio {
+        Ratio {
+            covered: self
+                .branches
+                .values()
+                .flatten()
+                .filter(|hits| **hits > 0)
+                .count(),
+            total: self.branches.len() * 2,
+        }
+    }
+}
+
+impl Coverage {
+    /// Coverage of all files loaded into the context, except for test files and the test runner
+    pub(crate) fn collect(ctx: &Context, recording: &Recording) -> Self {
+        let mut coverable = Coverable::default();
+        for (_, ast) in ctx.modules.loaded() {
+            for node in &ast.nodes {
+                coverable.statement(node);
+            }
+        }
+
+        let runner = test_runner_file_path();
+        let runner = runner.canonicalize().unwrap_or(runner);
+        let mut sources = BTreeMap::new();
+        let mut result = Coverage::default();
+        for (file, start, end) in coverable.statements {
+            let Some(lines) = lines_of(ctx, &mut sources, &runner, file) else {
+                continue;
+            };
+            let hits = recording.nodes.get(&(file, start, end)).copied();
+            let line = lines.line(start);
+            let file = result.files.entry(lines.path.clone()).or_default();
+            let entry = file.lines.entry(line).or_default();
+            *entry = (*entry).max(hits.unwrap_or_default());
+        }
+
+        for node @ (file, start, _) in coverable.branches {
+            let Some(lines) = lines_of(ctx, &mut sources, &runner, file) else {
+                continue;
+            };
+            let taken = |branch| recording.branches.get(&(node, branch)).copied();
+            let line = lines.line(start);
+            result
+                .files
+                .entry(lines.path.clone())
+                .or_default()
+                .branches
+                .insert(
+                    (line, start),
+                    [taken(0).unwrap_or_default(), taken(1).unwrap_or_default()],
+                );
+        }
+        result
+    }
+
+    pub(crate) fn merge(&mut self, other: Coverage) {
+        for (path, other) in other.files {
+            let file = self.files.entry(path).or_default();
+            for (line, hits) in other.lines {
+                *file.lines.entry(line).or_default() += hits;
+            }
+            for (branch, [then, otherwise]) in other.branches {
+                let hits = file.branches.entry(branch).or_default();
+                hits[0] += then;
+                hits[1] += otherwise;
+            }
+        }
+    }
+
+    /// Only the files within one of the directories
+    pub(crate) fn within(self, directories: &[PathBuf]) -> Self {
+        Coverage {
+            files: self
+                .files
+                .into_iter()
+                .filter(|(path, _)| directories.iter().any(|dir| path.starts_with(dir)))
+                .collect(),
+        }
+    }
+
+    pub fn lines(&self) -> Ratio {
+        let mut result = Ratio::default();
+        for file in self.files.values() {
+            result.add(file.lines());
+        }
+        result
+    }
+
+    pub fn branches(&self) -> Ratio {
+        let mut result = Ratio::default();
+        for file in self.files.values() {
+            result.add(file.branches());
+        }
+        result
+    }
+
+    /// Tracefile as understood by lcov and genhtml
+    pub fn lcov(&self) -> String {
+        let mut result = String::from("TN:\n");
+        for (path, file) in &self.files {
+            writeln!(result, "SF:{}", display(path)).unwrap();
+            for (block, ((line, _), hits)) in file.branches.iter().enumerate() {
+                for (branch, taken) in hits.iter().enumerate() {
+                    // - marks branches whose condition never got evaluated
+                    let taken = if hits.iter().all(|hits| *hits == 0) {
+                        "-".to_string()
+                    } else {
+                        taken.to_string()
+                    };
+                    writeln!(result, "BRDA:{line},{block},{branch},{taken}").unwrap();
+                }
+            }
+            let branches = file.branches();
+            writeln!(result, "BRF:{}\nBRH:{}", branches.total, branches.covered).unwrap();
+            for (line, hits) in &file.lines {
+                writeln!(result, "DA:{line},{hits}").unwrap();
+            }
+            let lines = file.lines();
+            writeln!(result, "LF:{}\nLH:{}", lines.total, lines.covered).unwrap();
+            result.push_str("end_of_record\n");
+        }
+        result
+    }
+
+    /// Follows the totals of the pretty report, which already printed the separator
+    pub fn summary(&self) -> String {
+        let describe = |lines: Ratio, branches: Ratio| {
+            format!(
+                "lines {}/{} ({:.1}%), branches {}/{} ({:.1}%)",
+                lines.covered,
+                lines.total,
+                lines.percentage(),
+                branches.covered,
+                branches.total,
+                branches.percentage()
+            )
+        };
+
+        let mut result = String::from("Coverage\n");
+        for (path, file) in &self.files {
+            writeln!(
+                result,
+                "  {} - {}",
+                display(path),
+                describe(file.lines(), file.branches())
+            )
+            .unwrap();
+        }
+        writeln!(
+            result,
+            "Total - {}",
+            describe(self.lines(), self.branches())
+        )
+        .unwrap();
+        result
+    }
+}
+
+/// Statements and branching nodes of the loaded files, a file imported by several others is
+/// part of all their trees
+#[derive(Debug, Default)]
+struct Coverable {
+    statements: HashSet<NodeKey>,
+    branches: HashSet<NodeKey>,
+}
+
+impl Coverable {
+    // declarations run once the file gets loaded or never if they are exported, only their
+    // bodies count
+    fn statement(&mut self, node: &AstTreeNode) {
+        let declaration = matches!(
+            node.node(),
+            Node::DeclareExternalFunction(_)
+                | Node::DeclareFunction(_)
+                | Node::DeclarePackage(_)
+                | Node::DeclareType(_)
+                | Node::DefineType(_)
+                | Node::ExportPackage(_)
+        );
+        if let Some(key) = key(&node.span()).filter(|_| !declaration) {
+            self.statements.insert(key);
+        }
+        self.expression(node);
+    }
+
+    fn block(&mut self, block: &AstBlockNode) {
+        for node in &block.nodes {
+            self.statement(node);
+        }
+    }
+
+    fn function(&mut self, function: &AstDeclareFunctionNode) {
+        self.block(&function.nodes);
+    }
+
+    fn package(&mut self, package: &AstDeclarePackageNode) {
+        package
+            .functions
+            .iter()
+            .for_each(|node| self.function(node));
+        package.packages.iter().for_each(|node| self.package(node));
+        for definition in &package.definitions {
+            definition
+                .functions
+                .iter()
+                .for_each(|node| self.function(node));
+        }
+    }
+
+    fn expression(&mut self, node: &AstTreeNode) {
+        match node.node() {
+            Node::Block(block) => self.block(block),
+            Node::BreakLoop(node) => {
+                if let Some(node) = &node.node {
+                    self.expression(node)
+                }
+            }
+            Node::ReturnFromFunction(node) => {
+                if let Some(node) = &node.node {
+                    self.expression(node)
+                }
+            }
+            Node::Calculate(node) => {
+                self.expression(&node.left);
+                self.expression(&node.right);
+            }
+            Node::Compare(node) => {
+                self.expression(&node.left);
+                self.expression(&node.right);
+            }
+            Node::CallFunction(node) => {
+                node.arguments.iter().for_each(|node| self.expression(node))
+            }
+            Node::CallFunctionOfObject(node) => {
+                node.arguments.iter().for_each(|node| self.expression(node));
+                if let Some(lambda) = &node.lambda {
+                    self.block(lambda);
+                }
+            }
+            Node::CallFunctionOfPackage(node) => {
+                node.arguments.iter().for_each(|node| self.expression(node))
+            }
+            Node::CallFunctionWithLambda(node) => {
+                node.arguments.iter().for_each(|node| self.expression(node));
+                self.block(&node.lambda);
+            }
+            Node::DeclareFunction(node) => self.function(node),
+            Node::DeclarePackage(node) => self.package(node),
+            Node::DeclareVariable(node) => self.expression(&node.value),
+            Node::DefineType(node) => node.functions.iter().for_each(|node| self.function(node)),
+            Node::If(node) => {
+                if let Some(key) = key(&node.condition.span()) {
+                    self.branches.insert(key);
+                }
+                self.expression(&node.condition);
+                self.block(&node.then);
+                if let Some(otherwise) = &node.otherwise {
+                    self.block(otherwise);
+                }
+            }
+            Node::InterpolateString(node) => {
+                node.nodes.iter().for_each(|node| self.expression(node))
+            }
+            Node::InstantiateType(node) => {
+                for argument in &node.arguments {
+                    self.expression(&argument.value)
+                }
+            }
+            Node::Loop(loop_node) => {
+                if let Some(key) = key(&node.span()) {
+                    self.branches.insert(key);
+                }
+                loop_node.nodes.iter().for_each(|node| self.statement(node));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Source of a covered file to map indices to lines
+struct Lines {
+    path: PathBuf,
+    starts: Vec<usize>,
+}
+
+impl Lines {
+    fn new(path: PathBuf, source: &str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(
+                source
+                    .chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '\n')
+                    .map(|(index, _)| index + 1),
+            )
+            .collect();
+        Self { path, starts }
+    }
+
+    // spans index chars, rows of spans are not reliable yet
+    fn line(&self, index: usize) -> usize {
+        self.starts.partition_point(|start| *start <= index)
+    }
+}
+
+fn lines_of<'a>(
+    ctx: &Context,
+    sources: &'a mut BTreeMap<usize, Option<Lines>>,
+    runner: &Path,
+    file: crate::common::StringTableId,
+) -> Option<&'a Lines> {
+    sources
+        .entry(file.0)
+        .or_insert_with(|| {
+            let path = PathBuf::from(ctx.get_str(file));
+            let is_test = path.to_string_lossy().ends_with(".test.ec");
+            if is_test || path == runner {
+                return None;
+            }
+            let source = fs::read_to_string(&path).ok()?;
+            Some(Lines::new(path, &source))
+        })
+        .as_ref()
+}
+
+/// Paths within the working directory are shown relative to it
+fn display(path: &Path) -> String {
+    env::current_dir()
+        .ok()
+        .and_then(|dir| dir.canonicalize().ok())
+        .and_then(|dir| path.strip_prefix(dir).ok())
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    use crate::backend::test::coverage::{Coverage, FileCoverage, Lines};
+
+    fn file(lines: &[(usize, usize)], branches: &[(usize, [usize; 2])]) -> FileCoverage {
+        FileCoverage {
+            lines: lines.iter().copied().collect(),
+            branches: branches
+                .iter()
+                .map(|(line, hits)| ((*line, *line * 10), *hits))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn maps_indices_to_lines() {
+        let lines = Lines::new(PathBuf::from("a.ec"), "ab\ncd\n\ne");
+        assert_eq!(lines.line(0), 1);
+        assert_eq!(lines.line(2), 1);
+        assert_eq!(lines.line(3), 2);
+        assert_eq!(lines.line(7), 4);
+    }
+
+    #[test]
+    fn merges_and_reports() {
+        let mut coverage = Coverage {
+            files: BTreeMap::from([(
+                PathBuf::from("/lib/a.ec"),
+                file(&[(1, 1), (2, 0), (3, 0)], &[(2, [0, 0])]),
+            )]),
+        };
+        coverage.merge(Coverage {
+            files: BTreeMap::from([
+                (
+                    PathBuf::from("/lib/a.ec"),
+                    file(&[(1, 1), (2, 2), (3, 0)], &[(2, [2, 0])]),
+                ),
+                (PathBuf::from("/other/b.ec"), file(&[(1, 1)], &[])),
+            ]),
+        });
+
+        let coverage = coverage.within(&[PathBuf::from("/lib")]);
+        assert_eq!(coverage.files.len(), 1);
+        assert_eq!(coverage.lines().covered, 2);
+        assert_eq!(coverage.lines().total, 3);
+        assert_eq!(coverage.branches().covered, 1);
+        assert_eq!(coverage.branches().total, 2);
+        assert_eq!(
+            coverage.lcov(),
+            "TN:\nSF:/lib/a.ec\nBRDA:2,0,0,2\nBRDA:2,0,1,0\nBRF:2\nBRH:1\nDA:1,2\nDA:2,2\nDA:3,0\nLF:3\nLH:2\nend_of_record\n"
+        );
+        assert!(coverage
+            .summary()
+            .contains("Total - lines 2/3 (66.7%), branches 1/2 (50.0%)"));
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::backend::test::result::{DescribeResult, TestResult};
+use crate::common::node::Node;
+use crate::common::WithSpan;
+use crate::frontend::ast::{AstBlockNode, AstCallFunctionWithLambdaNode, AstTreeNode};
+use crate::frontend::context::Context;
+use crate::frontend::Ast;
+
+const SHOULDS: [&str; 6] = [
+    "should",
+    "skip_should",
+    "only_should",
+    "should_print",
+    "should_match_golden",
+    "forall",
+];
+
+const TESTS: [&str; 3] = ["test", "skip_test", "only_test"];
+
+/// Lines of the should calls of a test file, by description in order of appearance
+#[derive(Debug, Default)]
+pub(crate) struct ShouldLines(HashMap<String, VecDeque<usize>>);
+
+impl ShouldLines {
+    pub(crate) fn new(ctx: &Context, ast: &Ast, source: &str) -> Self {
+        let mut result = Self::default();
+        visit_calls(ast, &mut |node, call| {
+            if !SHOULDS.contains(&ctx.get_str(call.function.0)) {
+                return;
+            }
+            if let Some(Node::LiteralString(description)) =
+                call.arguments.first().map(|argument| argument.node())
+            {
+                result
+                    .0
+                    .entry(ctx.get_str(description.0.value).to_string())
+                    .or_default()
+                    .push_back(line(source, node.span().start.index.0));
+            }
+        });
+        result
+    }
+
+    /// Assigns the line of its should call to every result, calls with the same description get
+    /// assigned in the order they appear in
+    pub(crate) fn locate(mut self, results: &mut [TestResult]) {
+        for test in results {
+            for describe in &mut test.describes {
+                self.locate_describe(describe);
+            }
+        }
+    }
+
+    fn locate_describe(&mut self, describe: &mut DescribeResult) {
+        for should in &mut describe.shoulds {
+            if let Some(lines) = self.0.get_mut(&should.description) {
+                should.line = if lines.len() > 1 {
+                    lines.pop_front()
+                } else {
+                    lines.front().copied()
+                };
+            }
+        }
+        for describe in &mut describe.describes {
+            self.locate_describe(describe);
+        }
+    }
+}
+
+/// Whether the test file focuses on some of its tests, using only_test, only_describe or only_should
+pub(crate) fn has_focus(ctx: &Context, ast: &Ast) -> bool {
+    let mut result = false;
+    visit_calls(ast, &mut |_, call| {
+        result |= ctx.get_str(call.function.0).starts_with("only_");
+    });
+    result
+}
+
+/// Whether the file declares any test, files without get checked against their // out: lines
+pub(crate) fn declares_tests(ctx: &Context, ast: &Ast) -> bool {
+    let mut result = false;
+    visit_calls(ast, &mut |_, call| {
+        result |= TESTS.contains(&ctx.get_str(call.function.0));
+    });
+    result
+}
+
+/// Visits all calls of functions with a trailing lambda, like test, describe and should
+fn visit_calls(ast: &Ast, visitor: &mut impl FnMut(&AstTreeNode, &AstCallFunctionWithLambdaNode)) {
+    for node in &ast.nodes {
+        visit(node, visitor);
+    }
+}
+
+fn visit(
+    node: &AstTreeNode,
+    visitor: &mut impl FnMut(&AstTreeNode, &AstCallFunctionWithLambdaNode),
+) {
+    match node.node() {
+        Node::Block(node) => visit_block(node, visitor),
+        Node::CallFunction(node) => {
+            for argument in &node.arguments {
+                visit(argument, visitor);
+            }
+        }
+        Node::CallFunctionWithLambda(call) => {
+            visitor(node, call);
+            for argument in &call.arguments {
+                visit(argument, visitor);
+            }
+            visit_block(&call.lambda, visitor);
+        }
+        Node::DeclareFunction(node) => visit_block(&node.nodes, visitor),
+        Node::If(node) => {
+            visit_block(&node.then, visitor);
+            if let Some(otherwise) = &node.otherwise {
+                visit_block(otherwise, visitor);
+            }
+        }
+        Node::Loop(node) => {
+            for node in &node.nodes {
+                visit(node, visitor);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn visit_block(
+    block: &AstBlockNode,
+    visitor: &mut impl FnMut(&AstTreeNode, &AstCallFunctionWithLambdaNode),
+) {
+    for node in &block.nodes {
+        visit(node, visitor);
+    }
+}
+
+// rows of spans are not reliable yet, the index is
+fn line(source: &str, index: usize) -> usize {
+    source.chars().take(index).filter(|c| *c == '\n').count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::backend::test::location::{declares_tests, has_focus, ShouldLines};
+    use crate::backend::test::result::{DescribeResult, ShouldResult, TestResult};
+    use crate::frontend::ast_from_str;
+    use crate::frontend::context::Context;
+
+    fn should(description: &str) -> ShouldResult {
+        ShouldResult {
+            description: description.to_string(),
+            passed: true,
+            skipped: false,
+            duration: Duration::ZERO,
+            message: None,
+            expected: None,
+            actual: None,
+            line: None,
+        }
+    }
+
+    #[test]
+    fn locates_should_calls() {
+        let source = "test('t'){\n  describe('d'){\n    should('a'){ true }\n    should('b'){\n      false\n    }\n    should('a'){ true }\n  }\n}\n";
+        let mut ctx = Context::new();
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let lines = ShouldLines::new(&ctx, &ast, source);
+
+        let mut results = vec![TestResult {
+            name: "t".to_string(),
+            duration: Duration::ZERO,
+            describes: vec![DescribeResult {
+                description: "d".to_string(),
+                duration: Duration::ZERO,
+                describes: vec![],
+                shoulds: vec![should("a"), should("b"), should("a"), should("c")],
+            }],
+        }];
+        lines.locate(&mut results);
+
+        let lines: Vec<Option<usize>> = results[0].describes[0]
+            .shoulds
+            .iter()
+            .map(|should| should.line)
+            .collect();
+        assert_eq!(lines, vec![Some(3), Some(4), Some(7), None]);
+    }
+
+    #[test]
+    fn detects_focus() {
+        let mut ctx = Context::new();
+        let ast = ast_from_str(
+            &mut ctx,
+            "test('t'){ describe('d'){ should('a'){ true } } }",
+        )
+        .unwrap();
+        assert!(!has_focus(&ctx, &ast));
+
+        let ast = ast_from_str(
+            &mut ctx,
+            "test('t'){ describe('d'){ only_should('a'){ true } } }",
+        )
+        .unwrap();
+        assert!(has_focus(&ctx, &ast));
+    }
+
+    #[test]
+    fn detects_tests() {
+        let mut ctx = Context::new();
+        let ast = ast_from_str(&mut ctx, "std::io::println('hello')").unwrap();
+        assert!(!declares_tests(&ctx, &ast));
+
+        let ast = ast_from_str(&mut ctx, "skip_test('t'){ }").unwrap();
+        assert!(declares_tests(&ctx, &ast));
+    }
+}
@@ -1,141 +1,1016 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io;
-use std::io::Read;
-use std::path::PathBuf;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
 use std::process::exit;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc;
+use std::time::Instant;
+use std::{fs, io, panic, thread};
 
+use crate::backend::intrinsic::{string_argument, IntrinsicType, Intrinsics, Results};
+use crate::backend::run::coverage::{finish_recording, start_recording};
+use crate::backend::run::output::{end_all_captures, end_capture, start_capture};
 use crate::backend::run::run;
 use crate::backend::run::scope::Scope;
 use crate::backend::run::type_definitions::TypeDefinitions;
-use crate::backend::run::value::Value::IntrinsicFunction;
-use crate::backend::run::value::{IntrinsicFunctionValue, ObjectValue, Value};
-use crate::frontend::ast_from_str;
+use crate::backend::run::value::Value;
+use crate::backend::test::assertion::{contains, equals};
+use crate::backend::test::coverage::Coverage;
+use crate::backend::test::location::{declares_tests, has_focus, ShouldLines};
+use crate::backend::test::property::Property;
+use crate::backend::test::report::{report, Reporter};
+use crate::backend::test::result::{read_results, TestResult};
+use crate::backend::test::snapshot::{check_out, golden, normalize, out_lines};
+use crate::common::panic::panic_message;
+use crate::frontend::ast_from_file;
 use crate::frontend::context::Context;
-use crate::{load_library_file, load_test_runner};
+use crate::frontend::Ast;
+use crate::{library_file_path, test_runner_file_path};
 
-pub fn test_files(files: Vec<PathBuf>, print_colors: bool, fails_at_the_end: bool) {
-    test_file(files.first().unwrap(), print_colors, fails_at_the_end);
+mod assertion;
+pub mod coverage;
+mod location;
+mod property;
+pub mod report;
+pub mod result;
+mod snapshot;
+
+pub struct TestOptions {
+    pub print_colors: bool,
+    pub reporter: Reporter,
+    /// Only shoulds whose name or any name of their enclosing test or describes contains the
+    /// filter run, the others get reported as skipped
+    pub filter: Option<String>,
+    /// Golden files get rewritten with the actual output instead of being compared against
+    pub update_snapshots: bool,
+    /// Number of files tested at the same time
+    pub jobs: usize,
+    /// Where to write the lcov report of the library code the tests ran
+    pub coverage: Option<PathBuf>,
+    /// Minimal percentage of covered lines, the command fails below it
+    pub coverage_threshold: Option<f64>,
+    /// Seed of the values properties get checked with, a failing property reports the seed
+    pub seed: u64,
 }
 
-fn test_file(file: &PathBuf, print_colors: bool, fails_at_the_end: bool) {
-    let mut ctx = Context::new();
-    let mut root_values = HashMap::new();
-    let mut root_types = HashMap::new();
+impl TestOptions {
+    fn records_coverage(&self) -> bool {
+        self.coverage.is_some() || self.coverage_threshold.is_some()
+    }
+}
 
-    let (tx, rx) = mpsc::channel();
+/// A test file together with the name it gets reported under
+#[derive(Debug, PartialEq)]
+pub(crate) struct TestFile {
+    path: PathBuf,
+    name: String,
+}
 
-    let mut intrinsics = ObjectValue::new();
-    intrinsics.set_property(
-        ctx.string_table.push_str("list_length"),
-        IntrinsicFunction(IntrinsicFunctionValue(Rc::new(|args| {
-            let Value::List(list) = args.get(0).unwrap() else {
-                panic!("not list")
-            };
-            let len: u32 = list.0.borrow().len() as u32;
-            Ok(Value::Number(len.into()))
-        }))),
+#[derive(Debug, PartialEq)]
+pub(crate) enum Outcome {
+    Ran(Vec<TestResult>),
+    Errored(String),
+}
+
+impl Outcome {
+    fn succeeded(&self) -> bool {
+        match self {
+            Outcome::Ran(results) => results.iter().all(TestResult::passed),
+            Outcome::Errored(_) => false,
+        }
+    }
+}
+
+/// Tests the given files and all *.test.ec files within the given directories, every file in a scope of its own.
+/// Exits with 1 if any test failed or a file could not be tested
+pub fn test_files(paths: Vec<PathBuf>, options: TestOptions) {
+    let mut files = vec![];
+    for path in &paths {
+        match discover(path) {
+            Ok(found) => files.extend(found),
+            Err(err) => {
+                eprintln!("Failed to discover tests in {} - {err}", path.display());
+                exit(1)
+            }
+        }
+    }
+
+    if files.is_empty() {
+        eprintln!("No test files found");
+        exit(1)
+    }
+
+    // unsupported constructs panic, the file gets reported as errored instead
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let (outcomes, outputs, coverage) = test_in_parallel(&files, &options);
+
+    panic::set_hook(hook);
+
+    print!(
+        "{}",
+        report(
+            options.reporter,
+            &files,
+            &outcomes,
+            &outputs,
+            options.print_colors
+        )
     );
 
-    intrinsics.set_property(
-        ctx.string_table.push_str("list_append"),
-        IntrinsicFunction(IntrinsicFunctionValue(Rc::new(|args| {
-            let Value::List(list) = args.get(0).unwrap() else {
-                panic!("not list")
-            };
-            let arg = args.get(1).cloned().unwrap();
-            list.0.borrow_mut().push(arg);
-            Ok(Value::Unit)
-        }))),
+    if options.records_coverage() {
+        // only code next to the tests counts, not core or std used by them
+        let directories: Vec<PathBuf> = paths
+            .iter()
+            .filter_map(|path| path.canonicalize().ok())
+            .map(|path| match path.is_dir() {
+                true => path,
+                false => path.parent().map(Path::to_path_buf).unwrap_or(path),
+            })
+            .collect();
+        check_coverage(&coverage.within(&directories), &options);
+    }
+
+    if !outcomes.iter().all(Outcome::succeeded) {
+        exit(1)
+    }
+}
+
+/// Prints the summary, writes the lcov report and exits with 1 if too few lines got covered
+fn check_coverage(coverage: &Coverage, options: &TestOptions) {
+    // the other reporters own stdout
+    if options.reporter == Reporter::Pretty {
+        print!("{}", coverage.summary());
+    } else {
+        eprint!("{}", coverage.summary());
+    }
+
+    if let Some(path) = &options.coverage {
+        if let Err(err) = fs::write(path, coverage.lcov()) {
+            eprintln!("Failed to write coverage to {} - {err}", path.display());
+            exit(1)
+        }
+    }
+
+    if let Some(threshold) = options.coverage_threshold {
+        let percentage = coverage.lines().percentage();
+        if percentage < threshold {
+            eprintln!("Line coverage of {percentage:.1}% is below the threshold of {threshold}%");
+            exit(1)
+        }
+    }
+}
+
+/// Tests the files on up to options.jobs workers, outcomes and what got printed while testing
+/// are in the order of the files
+fn test_in_parallel(
+    files: &[TestFile],
+    options: &TestOptions,
+) -> (Vec<Outcome>, Vec<String>, Coverage) {
+    // workers take the next file once done with the previous one. The pretty reporter prints
+    // their output in the order of the files once all files before got printed, the other
+    // reporters own stdout and put it into their report
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    let mut outcomes = vec![];
+    let mut outputs = vec![];
+    let mut coverage = Coverage::default();
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.clamp(1, files.len()) {
+            let sender = sender.clone();
+            let next = &next;
+            thread::Builder::new()
+                .stack_size(WORKER_STACK_SIZE)
+                .spawn_scoped(scope, move || {
+                    let mut worker = Worker::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::SeqCst);
+                        let Some(file) = files.get(index) else {
+                            break;
+                        };
+                        start_capture();
+                        if options.records_coverage() {
+                            start_recording();
+                        }
+                        let outcome = catch_unwind(AssertUnwindSafe(|| {
+                            worker.test_file(&file.path, options)
+                        }));
+                        let coverage = finish_recording()
+                            .map(|recording| Coverage::collect(&worker.ctx, &recording));
+                        let outcome = outcome.unwrap_or_else(|panic| {
+                            // the context might be left in the middle of a module
+                            worker = Worker::new();
+                            Outcome::Errored(panic_message(panic))
+                        });
+                        if sender
+                            .send((index, end_all_captures(), outcome, coverage))
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                })
+                .unwrap();
+        }
+        drop(sender);
+
+        let mut finished = HashMap::new();
+        for (index, output, outcome, file_coverage) in receiver {
+            if let Some(file_coverage) = file_coverage {
+                coverage.merge(file_coverage);
+            }
+            finished.insert(index, (output, outcome));
+            while let Some((output, outcome)) = finished.remove(&outcomes.len()) {
+                if options.reporter == Reporter::Pretty {
+                    if files.len() > 1 {
+                        println!("{}", files[outcomes.len()].name);
+                    }
+                    print!("{output}");
+                }
+                outcomes.push(outcome);
+                outputs.push(output);
+            }
+        }
+    });
+
+    (outcomes, outputs, coverage)
+}
+
+fn discover(path: &Path) -> io::Result<Vec<TestFile>> {
+    let name = path.strip_prefix(".").unwrap_or(path);
+    if !fs::metadata(path)?.is_dir() {
+        return Ok(vec![TestFile {
+            path: path.to_path_buf(),
+            name: name.display().to_string(),
+        }]);
+    }
+
+    let mut result = vec![];
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() {
+            result.extend(discover(&entry)?);
+        } else if entry
+            .file_name()
+            .is_some_and(|file_name| file_name.to_string_lossy().ends_with(".test.ec"))
+        {
+            result.push(TestFile {
+                name: entry
+                    .strip_prefix(".")
+                    .unwrap_or(&entry)
+                    .display()
+                    .to_string(),
+                path: entry,
+            });
+        }
+    }
+    Ok(result)
+}
+
+/// Core, std and the test runner, parsed once per worker and run again for every file
+struct Prelude {
+    core: Ast,
+    std: Ast,
+    runner: Ast,
+}
+
+/// Tests one file after another, reusing its context and prelude
+struct Worker {
+    ctx: Context,
+    prelude: Prelude,
+}
+
+// the interpreter recurses deeply, workers get the stack the main thread would have
+const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+impl Worker {
+    fn new() -> Self {
+        let mut ctx = Context::new();
+        let prelude = Prelude {
+            core: ast_from_file(&mut ctx, &library_file_path("core/index.ec")).unwrap(),
+            std: ast_from_file(&mut ctx, &library_file_path("std/index.ec")).unwrap(),
+            runner: ast_from_file(&mut ctx, &test_runner_file_path()).unwrap(),
+        };
+        Self { ctx, prelude }
+    }
+
+    fn test_file(&mut self, file: &Path, options: &TestOptions) -> Outcome {
+        test_file(&mut self.ctx, &self.prelude, file, options)
+    }
+}
+
+fn test_file(ctx: &mut Context, prelude: &Prelude, file: &Path, options: &TestOptions) -> Outcome {
+    let print_colors = options.print_colors;
+
+    let mut intrinsics = Intrinsics::new(ctx, print_colors);
+    let print_progress = options.reporter == Reporter::Pretty;
+    intrinsics.register("print_progress", &[], IntrinsicType::Bool, move |_| {
+        Ok(Value::Bool(print_progress))
+    });
+
+    let filter = options.filter.clone();
+    intrinsics.register(
+        "filter_matches",
+        &[IntrinsicType::String],
+        IntrinsicType::Bool,
+        move |args| {
+            let name = string_argument(args, 0)?;
+            Ok(Value::Bool(
+                filter.as_ref().is_none_or(|filter| name.contains(filter)),
+            ))
+        },
     );
 
-    intrinsics.set_property(
-        ctx.string_table.push_str("list_get"),
-        IntrinsicFunction(IntrinsicFunctionValue(Rc::new(|args| {
-            let Value::List(list) = args.get(0).unwrap() else {
-                panic!("not list")
-            };
-            let Value::Number(arg) = args.get(1).cloned().unwrap() else {
-                panic!("not a number")
-            };
-            Ok(list.0.borrow().get(arg as usize - 1).cloned().unwrap())
-        }))),
+    // assertions of the runner compare values on the host, the language can not compare all of them
+    intrinsics.register(
+        "values_equal",
+        &[IntrinsicType::Any, IntrinsicType::Any],
+        IntrinsicType::Bool,
+        |args| Ok(Value::Bool(equals(&args[0], &args[1]))),
+    );
+
+    intrinsics.register(
+        "values_contain",
+        &[IntrinsicType::Any, IntrinsicType::Any],
+        IntrinsicType::Bool,
+        |args| Ok(Value::Bool(contains(&args[0], &args[1]))),
+    );
+
+    intrinsics.register(
+        "to_string",
+        &[IntrinsicType::Any],
+        IntrinsicType::String,
+        |args| Ok(Value::String(args[0].to_string())),
+    );
+
+    // should_print and should_match_golden capture everything printed while their body runs
+    intrinsics.register("capture_start", &[], IntrinsicType::Unit, |_| {
+        start_capture();
+        Ok(Value::Unit)
+    });
+
+    intrinsics.register("capture_end", &[], IntrinsicType::String, |_| {
+        Ok(Value::String(normalize(&end_capture())))
+    });
+
+    intrinsics.register(
+        "normalize_output",
+        &[IntrinsicType::Any],
+        IntrinsicType::String,
+        |args| Ok(Value::String(normalize(&args[0].to_string()))),
+    );
+
+    // golden files are relative to the test file
+    let directory = file.parent().map(Path::to_path_buf).unwrap_or_default();
+    // failing to read or write it fails the should instead of the whole file
+    let update_snapshots = options.update_snapshots;
+    let results = Results::new(ctx);
+    intrinsics.register(
+        "golden",
+        &[IntrinsicType::String, IntrinsicType::Any],
+        IntrinsicType::Result,
+        move |args| {
+            let path = string_argument(args, 0)?;
+            let actual = args[1].to_string();
+            Ok(
+                match golden(&directory.join(&path), &actual, update_snapshots) {
+                    Ok(expected) => results.ok(Value::String(expected)),
+                    Err(err) => {
+                        results.error(format!("Failed to access golden file {path} - {err}"))
+                    }
+                },
+            )
+        },
     );
 
-    intrinsics.set_property(
-        ctx.string_table.push_str("exit"),
-        IntrinsicFunction(IntrinsicFunctionValue(Rc::new(|args| {
-            let Value::Number(code) = args.get(0).cloned().unwrap() else {
+    // forall draws the values of its property from here, see property.rs
+    let property = Rc::new(RefCell::new(Property::new(options.seed)));
+    let property_intrinsic =
+        |intrinsics: &mut Intrinsics,
+         name: &'static str,
+         arguments: &[IntrinsicType],
+         result: IntrinsicType,
+         function: fn(&mut Property, &[Value]) -> Value| {
+            let property = property.clone();
+            intrinsics.register(name, arguments, result, move |args| {
+                Ok(function(&mut property.borrow_mut(), args))
+            });
+        };
+    property_intrinsic(
+        &mut intrinsics,
+        "property_begin",
+        &[IntrinsicType::String],
+        IntrinsicType::Unit,
+        |property, args| {
+            property.begin(&args[0].to_string());
+            Value::Unit
+        },
+    );
+    property_intrinsic(
+        &mut intrinsics,
+        "property_next",
+        &[IntrinsicType::Number],
+        IntrinsicType::Bool,
+        |property, args| {
+            let Value::Number(runs) = args[0] else {
                 panic!("not a number")
             };
-            exit(code as i32)
-        }))),
-    );
-
-    // FIXME collect test results - should be possible to collect std out etc.... as everything is just an intrinsics
-    intrinsics.set_property(
-        ctx.string_table.push_str("report_test_failure"),
-        IntrinsicFunction(IntrinsicFunctionValue(Rc::new(move |args| {
-            tx.send(false).unwrap();
-            Ok(Value::Unit)
-        }))),
-    );
-
-    root_values.insert(
-        ctx.string_table.push_str("intrinsics"),
-        Value::Object(intrinsics),
-    );
-    let scope = Scope::new(root_values, root_types);
-
-    // load core
-    let (scope, definitions) = {
-        let std_content = load_library_file("core/index.ec").unwrap();
-        let std_file = ast_from_str(&mut ctx, std_content.as_str()).unwrap();
-        run(
-            &mut ctx,
-            scope,
-            TypeDefinitions {
-                definitions: Default::default(),
-            },
-            std_file,
-            print_colors,
-        )
-        .unwrap()
-    };
+            Value::Bool(property.next_run(runs as usize))
+        },
+    );
+    property_intrinsic(
+        &mut intrinsics,
+        "property_shrink",
+        &[],
+        IntrinsicType::Bool,
+        |property, _| Value::Bool(property.next_shrink()),
+    );
+    property_intrinsic(
+        &mut intrinsics,
+        "property_failed",
+        &[],
+        IntrinsicType::Unit,
+        |property, _| {
+            property.failed();
+            Value::Unit
+        },
+    );
+    property_intrinsic(
+        &mut intrinsics,
+        "property_counterexample",
+        &[],
+        IntrinsicType::String,
+        |property, _| Value::String(property.counterexample()),
+    );
+    property_intrinsic(
+        &mut intrinsics,
+        "property_enter",
+        &[],
+        IntrinsicType::Unit,
+        |property, _| {
+            property.enter();
+            Value::Unit
+        },
+    );
+    property_intrinsic(
+        &mut intrinsics,
+        "property_leave",
+        &[IntrinsicType::Any],
+        IntrinsicType::Unit,
+        |property, args| {
+            property.leave(&args[0]);
+            Value::Unit
+        },
+    );
+    property_intrinsic(
+        &mut intrinsics,
+        "draw_number",
+        &[],
+        IntrinsicType::Number,
+        |property, _| Value::Number(property.number()),
+    );
+    property_intrinsic(
+        &mut intrinsics,
+        "draw_bool",
+        &[],
+        IntrinsicType::Bool,
+        |property, _| Value::Bool(property.bool()),
+    );
+    property_intrinsic(
+        &mut intrinsics,
+        "draw_string",
+        &[],
+        IntrinsicType::String,
+        |property, _| Value::String(property.string()),
+    );
+    property_intrinsic(
+        &mut intrinsics,
+        "draw_length",
+        &[],
+        IntrinsicType::Number,
+        |property, _| Value::Number(property.length() as f64),
+    );
 
-    // load std
-    let (scope, definitions) = {
-        let std_content = load_library_file("std/index.ec").unwrap();
-        let std_file = ast_from_str(&mut ctx, std_content.as_str()).unwrap();
-        run(&mut ctx, scope, definitions, std_file, print_colors).unwrap()
+    // the runner needs to know whether the file focuses on some tests before it runs any of them
+    let source_file = match ast_from_file(ctx, file) {
+        Ok(source_file) => source_file,
+        Err(err) => return Outcome::Errored(err.to_string()),
     };
+    let source = fs::read_to_string(file).unwrap_or_default();
+    let lines = ShouldLines::new(ctx, &source_file, &source);
+    let focus = has_focus(ctx, &source_file);
+    intrinsics.register("has_focus", &[], IntrinsicType::Bool, move |_| {
+        Ok(Value::Bool(focus))
+    });
+
+    let scope = Scope::new(HashMap::new(), HashMap::new());
 
-    // load test runner
-    let (scope, definitions) = {
-        let std_content = load_test_runner().unwrap();
-        let std_file = ast_from_str(&mut ctx, std_content.as_str()).unwrap();
-        run(&mut ctx, scope, definitions, std_file, print_colors).unwrap()
+    // load core, std and the test runner
+    let (scope, definitions) = run(
+        ctx,
+        scope,
+        TypeDefinitions {
+            definitions: Default::default(),
+        },
+        prelude.core.clone(),
+        &intrinsics,
+    )
+    .unwrap();
+    let (scope, definitions) =
+        run(ctx, scope, definitions, prelude.std.clone(), &intrinsics).unwrap();
+    let (scope, definitions) =
+        run(ctx, scope, definitions, prelude.runner.clone(), &intrinsics).unwrap();
+
+    // files without tests only print, what they print gets compared with their // out: lines
+    let expected = out_lines(&source);
+    if !declares_tests(ctx, &source_file) && !expected.is_empty() {
+        let started = Instant::now();
+        start_capture();
+        if let Err(err) = run(ctx, scope, definitions, source_file, &intrinsics) {
+            end_capture();
+            return Outcome::Errored(err.to_string());
+        }
+        return Outcome::Ran(vec![check_out(
+            &expected,
+            &end_capture(),
+            started.elapsed(),
+        )]);
+    }
+
+    let scope = match run(ctx, scope, definitions, source_file, &intrinsics) {
+        Ok((scope, _)) => scope,
+        Err(err) => return Outcome::Errored(err.to_string()),
     };
 
-    let mut path = PathBuf::from(file);
-    let content = load_text_from_file(path.to_str().unwrap()).unwrap();
-    let source_file = ast_from_str(&mut ctx, content.as_str()).unwrap();
+    let results = scope
+        .get_value(&ctx.string_table.push_str("test_results"))
+        .cloned()
+        .unwrap();
+    let mut results = read_results(ctx, &results);
+    lines.locate(&mut results);
+    Outcome::Ran(results)
+}
 
-    run(&mut ctx, scope, definitions, source_file, print_colors).unwrap();
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
 
-    if fails_at_the_end {
-        match rx.try_recv() {
-            Ok(_) => exit(-1),
-            Err(_) => {}
+    use crate::backend::test::report::Reporter;
+    use crate::backend::test::result::{Counts, TestResult};
+    use crate::backend::test::{discover, test_in_parallel, Outcome, TestOptions, Worker};
+
+    fn test_file(file: &Path, options: &TestOptions) -> Outcome {
+        Worker::new().test_file(file, options)
+    }
+
+    fn test_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        for (file, content) in files {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
         }
+        dir
     }
-}
 
-fn load_text_from_file(path: &str) -> io::Result<String> {
-    let mut file = File::open(path)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-    Ok(contents)
+    fn options() -> TestOptions {
+        TestOptions {
+            print_colors: false,
+            reporter: Reporter::Json,
+            filter: None,
+            update_snapshots: false,
+            jobs: 1,
+            coverage: None,
+            coverage_threshold: None,
+            seed: 1,
+        }
+    }
+
+    fn run_test(file: &Path, options: &TestOptions) -> Vec<TestResult> {
+        match test_file(file, options) {
+            Outcome::Ran(results) => results,
+            Outcome::Errored(message) => panic!("{} errored - {message}", file.display()),
+        }
+    }
+
+    /// Runs the source as the only test file of a fresh directory
+    fn run_test_source(name: &str, source: &str) -> Vec<TestResult> {
+        let dir = test_dir(name, &[("index.test.ec", source)]);
+        run_test(&dir.join("index.test.ec"), &options())
+    }
+
+    #[test]
+    fn discovers_test_files_recursively() {
+        let dir = test_dir(
+            "elodie_test_discover",
+            &[
+                ("b.test.ec", ""),
+                ("a/c.test.ec", ""),
+                ("a/helper.ec", ""),
+                ("d.ec", ""),
+            ],
+        );
+
+        let files: Vec<PathBuf> = discover(&dir)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.path)
+            .collect();
+        assert_eq!(files, vec![dir.join("a/c.test.ec"), dir.join("b.test.ec")]);
+
+        let files = discover(&dir.join("d.ec")).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(discover(&dir.join("unknown")).is_err());
+    }
+
+    #[test]
+    fn collects_results_tree() {
+        let results = run_test_source(
+            "elodie_test_results",
+            "test('t'){\n describe('outer'){\n  should('pass'){ true }\n  describe('inner'){\n   should('fail'){ false }\n  }\n }\n describe('second'){ }\n}",
+        );
+        assert_eq!(results.len(), 1);
+
+        let test = &results[0];
+        assert_eq!(test.name, "t");
+        assert_eq!(
+            test.describes
+                .iter()
+                .map(|describe| describe.description.as_str())
+                .collect::<Vec<_>>(),
+            vec!["outer", "second"]
+        );
+
+        let outer = &test.describes[0];
+        assert_eq!(outer.shoulds[0].description, "pass");
+        assert!(outer.shoulds[0].passed);
+        assert_eq!(outer.shoulds[0].message, None);
+
+        let inner = &outer.describes[0];
+        assert_eq!(inner.description, "inner");
+        assert!(!inner.shoulds[0].passed);
+        assert_eq!(inner.shoulds[0].message, Some("returned false".to_string()));
+        assert_eq!(inner.shoulds[0].line, Some(5));
+
+        assert_eq!(
+            test.counts(),
+            Counts {
+                passed: 1,
+                failed: 1,
+                skipped: 0
+            }
+        );
+    }
+
+    fn skipped(file: &std::path::Path, filter: Option<&str>) -> Vec<(String, bool)> {
+        let results = run_test(
+            file,
+            &TestOptions {
+                filter: filter.map(str::to_string),
+                ..options()
+            },
+        );
+        let mut result = vec![];
+        for test in &results {
+            for describe in &test.describes {
+                for should in &describe.shoulds {
+                    result.push((should.description.clone(), should.skipped));
+                }
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn skips_and_focuses() {
+        let dir = test_dir(
+            "elodie_test_skip",
+            &[
+                (
+                    "skip.test.ec",
+                    "test('t'){\n describe('d'){\n  should('a'){ true }\n  skip_should('b'){ false }\n }\n skip_describe('e'){\n  should('c'){ false }\n }\n}\nskip_test('u'){\n describe('d'){\n  should('d'){ false }\n }\n}",
+                ),
+                (
+                    "only.test.ec",
+                    "test('t'){\n describe('d'){\n  should('a'){ false }\n  only_should('b'){ true }\n }\n only_describe('e'){\n  should('c'){ true }\n }\n}",
+                ),
+            ],
+        );
+
+        assert_eq!(
+            skipped(&dir.join("skip.test.ec"), None),
+            vec![
+                ("a".to_string(), false),
+                ("b".to_string(), true),
+                ("c".to_string(), true),
+                ("d".to_string(), true),
+            ]
+        );
+        assert_eq!(
+            skipped(&dir.join("only.test.ec"), None),
+            vec![
+                ("a".to_string(), true),
+                ("b".to_string(), false),
+                ("c".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn filters_by_name() {
+        let dir = test_dir(
+            "elodie_test_filter",
+            &[(
+                "filter.test.ec",
+                "test('Point'){\n describe('x'){\n  should('be 1'){ true }\n  should('be set'){ true }\n }\n describe('y'){\n  should('be 2'){ true }\n }\n}",
+            )],
+        );
+        let file = dir.join("filter.test.ec");
+
+        assert_eq!(
+            skipped(&file, Some("set")),
+            vec![
+                ("be 1".to_string(), true),
+                ("be set".to_string(), false),
+                ("be 2".to_string(), true),
+            ]
+        );
+        assert_eq!(
+            skipped(&file, Some("y")),
+            vec![
+                ("be 1".to_string(), true),
+                ("be set".to_string(), true),
+                ("be 2".to_string(), false),
+            ]
+        );
+        assert!(skipped(&file, Some("Point"))
+            .iter()
+            .all(|(_, skipped)| !skipped));
+    }
+
+    #[test]
+    fn records_compared_values() {
+        let results = run_test_source(
+            "elodie_test_expect",
+            "test('t'){\n describe('d'){\n  should('equal'){ expect(1).to_equal(1) }\n  should('differ'){ expect('actual').to_equal('expected') }\n  should('fail'){\n   expect(1 == 2).to_fail()\n   expect(2 == 2).to_fail()\n   true\n  }\n }\n}",
+        );
+
+        let shoulds = &results[0].describes[0].shoulds;
+        assert!(shoulds[0].passed);
+        assert_eq!(shoulds[0].expected, None);
+
+        assert!(!shoulds[1].passed);
+        assert_eq!(shoulds[1].message, Some("expected to equal".to_string()));
+        assert_eq!(shoulds[1].expected, Some("expected".to_string()));
+        assert_eq!(shoulds[1].actual, Some("actual".to_string()));
+
+        assert!(!shoulds[2].passed);
+        assert_eq!(shoulds[2].message, Some("expected to fail".to_string()));
+        assert_eq!(shoulds[2].expected, Some("false".to_string()));
+        assert_eq!(shoulds[2].actual, Some("true".to_string()));
+    }
+
+    #[test]
+    fn runs_hooks() {
+        let results = run_test_source(
+            "elodie_test_hooks",
+            "let log = std::collection::list::empty()
+test('t'){
+    before_all(){ log.append('before all') }
+    before_each(){ log.append('before each t') }
+    after_each(){ log.append('after each t') }
+    describe('d'){
+        before_each(){ log.append('before each d') }
+        after_each(){ log.append('after each d') }
+        should('run'){ log.append('should')
+            true }
+    }
+    describe('order'){
+        should('be outer first'){
+            expect(log.get(2)).to_equal('before each t')
+            expect(log.get(3)).to_equal('before each d')
+            expect(log.get(4)).to_equal('should')
+            expect(log.get(5)).to_equal('after each d')
+            expect(log.get(6)).to_equal('after each t')
+        }
+    }
+    describe('failing before_all'){
+        before_all(){ false }
+        should('fail'){ true }
+    }
+    describe('failing before_each'){
+        before_each(){ expect(1).to_equal(2) }
+        should('fail'){ true }
+    }
+    describe('failing after_each'){
+        after_each(){ false }
+        should('fail'){ true }
+    }
+    describe('failing after_all'){
+        after_all(){ false }
+        should('fail'){ true }
+        describe('nested'){
+            should('fail'){ true }
+        }
+    }
+}",
+        );
+
+        let messages: Vec<Option<String>> = results[0]
+            .describes
+            .iter()
+            .map(|describe| describe.shoulds[0].message.clone())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                None,
+                None,
+                Some("before_all hook of failing before_all failed".to_string()),
+                Some("before_each hook failed".to_string()),
+                Some("after_each hook failed".to_string()),
+                Some("after_all hook of failing after_all failed".to_string()),
+            ]
+        );
+        assert_eq!(
+            results[0].describes[5].describes[0].shoulds[0].message,
+            Some("after_all hook of failing after_all failed".to_string())
+        );
+    }
+
+    #[test]
+    fn compares_printed_output() {
+        let dir = test_dir(
+            "elodie_test_print",
+            &[
+                (
+                    "print.test.ec",
+                    "test('t'){\n describe('d'){\n  should_print('print', 'a\\nb'){\n   std::io::println('a')\n   std::io::println('b')\n  }\n  should_print('differ', 'a'){ std::io::print('b') }\n  should_match_golden('golden', 'print.golden'){ std::io::print('c') }\n }\n}",
+                ),
+                ("out.ec", "std::io::println('a')\n// out: a\n"),
+            ],
+        );
+        let results = run_test(&dir.join("print.test.ec"), &options());
+        let shoulds = &results[0].describes[0].shoulds;
+        assert!(shoulds[0].passed);
+        assert!(!shoulds[1].passed);
+        assert_eq!(shoulds[1].expected, Some("a".to_string()));
+        assert_eq!(shoulds[1].actual, Some("b".to_string()));
+        assert!(shoulds[2].passed);
+        assert_eq!(fs::read_to_string(dir.join("print.golden")).unwrap(), "c\n");
+
+        let results = run_test(&dir.join("out.ec"), &options());
+        assert!(results[0].passed());
+    }
+
+    #[test]
+    fn fails_should_if_golden_file_is_inaccessible() {
+        let dir = test_dir(
+            "elodie_test_golden",
+            &[
+                (
+                    "golden.test.ec",
+                    "test('t'){\n describe('d'){\n  should_match_golden('golden', 'folder'){ std::io::print('c') }\n  should('pass'){ true }\n }\n}",
+                ),
+                ("folder/file", ""),
+            ],
+        );
+        let results = run_test(&dir.join("golden.test.ec"), &options());
+        let shoulds = &results[0].describes[0].shoulds;
+        assert!(!shoulds[0].passed);
+        let message = shoulds[0].message.clone().unwrap();
+        assert!(
+            message.starts_with("Failed to access golden file folder - "),
+            "{message}"
+        );
+        assert!(shoulds[1].passed);
+    }
+
+    #[test]
+    fn keeps_order_of_files_in_parallel() {
+        let dir = test_dir(
+            "elodie_test_parallel",
+            &[
+                (
+                    "a.test.ec",
+                    "test('a'){ describe('d'){ should('pass'){ true } } }",
+                ),
+                (
+                    "b.test.ec",
+                    "test('b'){ describe('d'){ should('fail'){ false } } }",
+                ),
+                (
+                    "c.test.ec",
+                    "test('c'){ describe('d'){ should('pass'){ true } } }",
+                ),
+                ("d.test.ec", "unknown()"),
+            ],
+        );
+        let files = discover(&dir).unwrap();
+
+        let (outcomes, _, _) = test_in_parallel(
+            &files,
+            &TestOptions {
+                jobs: 3,
+                ..options()
+            },
+        );
+
+        let names: Vec<Option<&str>> = outcomes
+            .iter()
+            .map(|outcome| match outcome {
+                Outcome::Ran(results) => Some(results[0].name.as_str()),
+                Outcome::Errored(_) => None,
+            })
+            .collect();
+        assert_eq!(names, vec![Some("a"), Some("b"), Some("c"), None]);
+        assert!(!outcomes[1].succeeded());
+    }
+
+    #[test]
+    fn collects_printed_output() {
+        let dir = test_dir(
+            "elodie_test_printed_output",
+            &[
+                (
+                    "a.test.ec",
+                    "test('a'){ describe('d'){ should('print'){ std::io::print('from a')\n true } } }",
+                ),
+                ("b.test.ec", "std::io::print('from b')\nunknown()"),
+            ],
+        );
+        let files = discover(&dir).unwrap();
+
+        let (_, outputs, _) = test_in_parallel(
+            &files,
+            &TestOptions {
+                jobs: 2,
+                ..options()
+            },
+        );
+
+        assert_eq!(outputs, vec!["from a ", "from b "]);
+    }
+
+    #[test]
+    fn records_coverage() {
+        let dir = test_dir(
+            "elodie_test_coverage",
+            &[
+                (
+                    "lib.ec",
+                    "function pick(a: Bool) -> Number {\n    if a {\n        1\n    } else {\n        2\n    }\n}\n",
+                ),
+                (
+                    "lib.test.ec",
+                    "import './lib'\ntest('t'){ describe('d'){ should('pick'){ pick(true) == 1 } } }",
+                ),
+            ],
+        );
+        let files = discover(&dir).unwrap();
+
+        let (_, _, coverage) = test_in_parallel(
+            &files,
+            &TestOptions {
+                coverage: Some(dir.join("lcov.info")),
+                ..options()
+            },
+        );
+
+        let coverage = coverage.within(&[dir.canonicalize().unwrap()]);
+        let lib = &coverage.files[&dir.join("lib.ec").canonicalize().unwrap()];
+        assert_eq!(
+            lib.lines
+                .iter()
+                .map(|(line, hits)| (*line, *hits))
+                .collect::<Vec<_>>(),
+            vec![(2, 1), (3, 1), (5, 0)]
+        );
+        assert_eq!(lib.branches.values().collect::<Vec<_>>(), vec![&[1, 0]]);
+    }
+
+    #[test]
+    fn shrinks_failing_properties() {
+        let results = run_test_source(
+            "elodie_test_property",
+            "test('t'){\n describe('d'){\n  forall('commutes', 50){\n   let a = any_number()\n   let b = any_number()\n   a + b == b + a\n  }\n  forall('stays small', 50){\n   let list = any_list(any_number)\n   expect(list.length() > 1).to_fail()\n  }\n }\n}",
+        );
+
+        let shoulds = &results[0].describes[0].shoulds;
+        assert!(shoulds[0].passed);
+        assert!(!shoulds[1].passed);
+        let message = shoulds[1].message.clone().unwrap();
+        assert!(message.contains("with seed 1"), "{message}");
+        assert!(message.ends_with("counterexample: [0, 0]"), "{message}");
+        assert_eq!(shoulds[1].expected, None);
+    }
 }
@@ -0,0 +1,281 @@
+use std::collections::VecDeque;
+
+use crate::backend::intrinsic::random::Rng;
+use crate::backend::run::value::Value;
+
+const MAX_NUMBER: u64 = 1000;
+const MAX_LENGTH: u64 = 10;
+const CHARACTERS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 ";
+// shrinking stops after this many attempts, even if it might get smaller
+const MAX_SHRINKS: usize = 1000;
+
+/// State of the property the test runner checks right now.
+///
+/// Generators draw choices, values are derived from them so that smaller choices give simpler
+/// values. A failing run gets shrunk by replaying smaller sequences of choices until none of
+/// them fails anymore
+#[derive(Debug)]
+pub(crate) struct Property {
+    seed: u64,
+    rng: Rng,
+    runs: usize,
+    choices: Vec<u64>,
+    replay: Option<Vec<u64>>,
+    notes: Vec<String>,
+    depth: usize,
+    counterexample: Option<(Vec<u64>, Vec<String>)>,
+    candidates: VecDeque<Vec<u64>>,
+    shrinks: usize,
+}
+
+impl Property {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: Rng::new(seed),
+            runs: 0,
+            choices: vec![],
+            replay: None,
+            notes: vec![],
+            depth: 0,
+            counterexample: None,
+            candidates: VecDeque::new(),
+            shrinks: 0,
+        }
+    }
+
+    /// Starts checking another property, its numbers depend on the seed and its description only
+    pub(crate) fn begin(&mut self, description: &str) {
+        *self = Self::new(self.seed);
+        self.rng = Rng::new(self.seed ^ fnv(description));
+    }
+
+    /// Prepares the next random run, false once all runs are done
+    pub(crate) fn next_run(&mut self, runs: usize) -> bool {
+        if self.runs >= runs {
+            return false;
+        }
+        self.runs += 1;
+        self.start(None);
+        true
+    }
+
+    /// Prepares the replay of the next smaller candidate, false once there are none left
+    pub(crate) fn next_shrink(&mut self) -> bool {
+        if self.shrinks >= MAX_SHRINKS {
+            return false;
+        }
+        let Some(candidate) = self.candidates.pop_front() else {
+            return false;
+        };
+        self.shrinks += 1;
+        self.start(Some(candidate));
+        true
+    }
+
+    fn start(&mut self, replay: Option<Vec<u64>>) {
+        self.replay = replay;
+        self.choices.clear();
+        self.notes.clear();
+        self.depth = 0;
+    }
+
+    /// The current run failed, it becomes the counterexample if it is simpler than the previous one
+    pub(crate) fn failed(&mut self) {
+        let simpler = self
+            .counterexample
+            .as_ref()
+            .is_none_or(|(choices, _)| shortlex(&self.choices) < shortlex(choices));
+        if simpler {
+            self.counterexample = Some((self.choices.clone(), self.notes.clone()));
+            self.candidates = candidates(&self.choices).into();
+        }
+    }
+
+    pub(crate) fn counterexample(&self) -> String {
+        let notes = self
+            .counterexample
+            .as_ref()
+            .map(|(_, notes)| notes.join(", "))
+            .unwrap_or_default();
+        let runs = if self.runs == 1 { "run" } else { "runs" };
+        format!(
+            "falsified after {} {runs} with seed {}, counterexample: {notes}",
+            self.runs, self.seed
+        )
+    }
+
+    fn choose(&mut self, bound: u64) -> u64 {
+        let choice = match &self.replay {
+            Some(replay) => replay
+                .get(self.choices.len())
+                .copied()
+                .unwrap_or_default()
+                .min(bound.saturating_sub(1)),
+            None => self.rng.below(bound),
+        };
+        self.choices.push(choice);
+        choice
+    }
+
+    // only values of the property itself get noted, not the elements of a generated list
+    fn note(&mut self, value: String) {
+        if self.depth == 0 {
+            self.notes.push(value);
+        }
+    }
+
+    /// Whole number between -1000 and 1000, choice 0 is 0, 1 is 1, 2 is -1 and so on
+    pub(crate) fn number(&mut self) -> f64 {
+        let choice = self.choose(2 * MAX_NUMBER + 1);
+        let magnitude = choice.div_ceil(2) as f64;
+        let result = if choice.is_multiple_of(2) {
+            -magnitude
+        } else {
+            magnitude
+        };
+        let result = if result == 0.0 { 0.0 } else { result };
+        self.note(format!("{result}"));
+        result
+    }
+
+    pub(crate) fn bool(&mut self) -> bool {
+        let result = self.choose(2) == 1;
+        self.note(result.to_string());
+        result
+    }
+
+    pub(crate) fn string(&mut self) -> String {
+        let length = self.choose(MAX_LENGTH + 1);
+        let characters: Vec<char> = CHARACTERS.chars().collect();
+        let result: String = (0..length)
+            .map(|_| characters[self.choose(characters.len() as u64) as usize])
+            .collect();
+        self.note(format!("'{result}'"));
+        result
+    }
+
+    pub(crate) fn length(&mut self) -> usize {
+        self.choose(MAX_LENGTH + 1) as usize
+    }
+
+    /// Values generated until leave are part of a composite value
+    pub(crate) fn enter(&mut self) {
+        self.depth += 1;
+    }
+
+    pub(crate) fn leave(&mut self, value: &Value) {
+        self.depth = self.depth.saturating_sub(1);
+        self.note(describe(value));
+    }
+}
+
+/// Generated values the way they get written in source
+fn describe(value: &Value) -> String {
+    match value {
+        Value::List(list) => {
+            let elements: Vec<String> = list.0.borrow().iter().map(describe).collect();
+            format!("[{}]", elements.join(", "))
+        }
+        Value::String(string) => format!("'{string}'"),
+        value => value.to_string(),
+    }
+}
+
+// shorter sequences are simpler, sequences of the same length are compared choice by choice
+fn shortlex(choices: &[u64]) -> (usize, &[u64]) {
+    (choices.len(), choices)
+}
+
+/// Smaller variations of the choices, removing some of them first and making single ones smaller after
+fn candidates(choices: &[u64]) -> Vec<Vec<u64>> {
+    let mut result = vec![];
+    for size in [8, 4, 2, 1] {
+        for start in 0..choices.len().saturating_sub(size - 1) {
+            let mut candidate = choices.to_vec();
+            candidate.drain(start..start + size);
+            // the choice before is likely the length the removed ones belong to
+            if start > 0 && candidate[start - 1] > 0 {
+                let mut shorter = candidate.clone();
+                shorter[start - 1] -= 1;
+                result.push(shorter);
+            }
+            result.push(candidate);
+        }
+    }
+    for (index, choice) in choices.iter().enumerate() {
+        // numbers alternate between positive and negative, 2 less keeps the sign
+        for smaller in [
+            0,
+            choice / 2,
+            choice.saturating_sub(2),
+            choice.saturating_sub(1),
+        ] {
+            if smaller < *choice {
+                let mut candidate = choices.to_vec();
+                candidate[index] = smaller;
+                result.push(candidate);
+            }
+        }
+    }
+    result.dedup();
+    result
+}
+
+fn fnv(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::test::property::Property;
+
+    #[test]
+    fn maps_small_choices_to_simple_values() {
+        let mut property = Property::new(1);
+        property.begin("p");
+        property.start(Some(vec![0, 1, 2, 0, 1, 3, 0]));
+        assert_eq!(property.number(), 0.0);
+        assert_eq!(property.number(), 1.0);
+        assert_eq!(property.number(), -1.0);
+        assert!(!property.bool());
+        assert!(property.bool());
+        assert_eq!(property.string(), "aaa");
+        assert_eq!(
+            property.notes,
+            vec!["0", "1", "-1", "false", "true", "'aaa'"]
+        );
+    }
+
+    // the property "all numbers are below 10" gets shrunk to its minimal counterexample
+    #[test]
+    fn shrinks_to_minimal_counterexample() {
+        let mut property = Property::new(42);
+        property.begin("below 10");
+
+        let mut failed = false;
+        while !failed && property.next_run(100) {
+            let (a, b) = (property.number(), property.number());
+            failed = a >= 10.0 || b >= 10.0;
+        }
+        assert!(failed);
+        property.failed();
+
+        while property.next_shrink() {
+            let (a, b) = (property.number(), property.number());
+            if a >= 10.0 || b >= 10.0 {
+                property.failed();
+            }
+        }
+
+        let counterexample = property.counterexample();
+        assert!(counterexample.contains("with seed 42"), "{counterexample}");
+        assert!(
+            counterexample.ends_with("counterexample: 0, 10")
+                || counterexample.ends_with("counterexample: 10, 0"),
+            "{counterexample}"
+        );
+    }
+}
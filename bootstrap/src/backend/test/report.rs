@@ -0,0 +1,737 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::backend::test::assertion::{diff, needs_diff};
+use crate::backend::test::result::{Counts, DescribeResult, ShouldResult, TestResult};
+use crate::backend::test::{Outcome, TestFile};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reporter {
+    Json,
+    Junit,
+    Pretty,
+    Tap,
+}
+
+impl FromStr for Reporter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Reporter::Json),
+            "junit" => Ok(Reporter::Junit),
+            "pretty" => Ok(Reporter::Pretty),
+            "tap" => Ok(Reporter::Tap),
+            _ => Err(format!(
+                "Unknown reporter '{s}', expected one of junit, tap, json, pretty"
+            )),
+        }
+    }
+}
+
+/// Renders the outcome of all tested files together with what they printed, the pretty reporter
+/// only renders the summary as its progress and output got printed while the tests ran
+pub(crate) fn report(
+    reporter: Reporter,
+    files: &[TestFile],
+    outcomes: &[Outcome],
+    outputs: &[String],
+    print_colors: bool,
+) -> String {
+    match reporter {
+        Reporter::Json => json(files, outcomes, outputs),
+        Reporter::Junit => junit(files, outcomes, outputs),
+        Reporter::Pretty => pretty(files, outcomes, print_colors),
+        Reporter::Tap => tap(files, outcomes, outputs),
+    }
+}
+
+/// A should together with the names of the test and describes it is nested in
+struct Case<'a> {
+    path: Vec<&'a str>,
+    should: &'a ShouldResult,
+}
+
+impl<'a> Case<'a> {
+    fn name(&self) -> String {
+        let mut result = self.path.join(" / ");
+        result.push_str(" / ");
+        result.push_str(&self.should.description);
+        result
+    }
+
+    fn location(&self, file: &TestFile) -> Option<String> {
+        self.should.line.map(|line| format!("{}:{line}", file.name))
+    }
+
+    /// Why the should failed, with the values its assertion compared
+    fn failure(&self) -> Vec<String> {
+        let mut result = vec![];
+        if let Some(message) = &self.should.message {
+            result.push(message.clone());
+        }
+        if let (Some(expected), Some(actual)) = (&self.should.expected, &self.should.actual) {
+            if needs_diff(expected, actual) {
+                result.push("- expected".to_string());
+                result.push("+ actual".to_string());
+                result.extend(diff(expected, actual));
+            } else {
+                result.push(format!("expected: {expected}"));
+                result.push(format!("actual:   {actual}"));
+            }
+        }
+        result
+    }
+}
+
+fn cases(results: &[TestResult]) -> Vec<Case<'_>> {
+    fn collect<'a>(path: &[&'a str], describe: &'a DescribeResult, result: &mut Vec<Case<'a>>) {
+        let mut path = path.to_vec();
+        path.push(&describe.description);
+        for should in &describe.shoulds {
+            result.push(Case {
+                path: path.clone(),
+                should,
+            })
+        }
+        for describe in &describe.describes {
+            collect(&path, describe, result);
+        }
+    }
+
+    let mut result = vec![];
+    for test in results {
+        for describe in &test.describes {
+            collect(&[&test.name], describe, &mut result);
+        }
+    }
+    result
+}
+
+fn counts(results: &[TestResult]) -> Counts {
+    let mut result = Counts::default();
+    for test in results {
+        result.add(test.counts());
+    }
+    result
+}
+
+fn duration(results: &[TestResult]) -> Duration {
+    results.iter().map(|test| test.duration).sum()
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn pretty(files: &[TestFile], outcomes: &[Outcome], print_colors: bool) -> String {
+    let colored = |text: &str, color: &str| {
+        if print_colors {
+            format!("\x1b[{color}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    };
+
+    let mut result = String::from("----------------------\n");
+    let (mut files_passed, mut files_failed, mut files_errored) = (0, 0, 0);
+    let mut tests = Counts::default();
+
+    for (file, outcome) in files.iter().zip(outcomes) {
+        let line = match outcome {
+            Outcome::Ran(results) => {
+                let counts = counts(results);
+                tests.add(counts);
+
+                let Counts {
+                    passed,
+                    failed,
+                    skipped,
+                } = counts;
+                let skipped = if skipped > 0 {
+                    format!(", {skipped} skipped")
+                } else {
+                    String::new()
+                };
+                if failed == 0 {
+                    files_passed += 1;
+                    format!(
+                        "{}  {} - {passed} passed{skipped}",
+                        colored("Pass", "0;32"),
+                        file.name
+                    )
+                } else {
+                    files_failed += 1;
+                    let mut line = format!(
+                        "{}  {} - {passed} passed, {failed} failed{skipped}",
+                        colored("Fail", "0;31"),
+                        file.name
+                    );
+                    for case in cases(results) {
+                        if case.should.passed || case.should.skipped {
+                            continue;
+                        }
+                        line.push_str(&format!("\n      {}", case.name()));
+                        if let Some(location) = case.location(file) {
+                            line.push_str(&format!(" ({location})"));
+                        }
+                        for failure in case.failure() {
+                            line.push_str(&format!("\n        {failure}"));
+                        }
+                    }
+                    line
+                }
+            }
+            Outcome::Errored(message) => {
+                files_errored += 1;
+                format!("{} {} - {message}", colored("Error", "0;31"), file.name)
+            }
+        };
+        result.push_str(&line);
+        result.push('\n');
+    }
+
+    result.push_str("----------------------\n");
+    result.push_str(&format!(
+        "Files: {files_passed} passed, {files_failed} failed, {files_errored} errored\n"
+    ));
+    result.push_str(&format!(
+        "Tests: {} passed, {} failed, {} skipped\n",
+        tests.passed, tests.failed, tests.skipped
+    ));
+    result
+}
+
+fn json(files: &[TestFile], outcomes: &[Outcome], outputs: &[String]) -> String {
+    fn describe_json(describe: &DescribeResult, file: &TestFile) -> Value {
+        json!({
+            "description": describe.description,
+            "duration_ms": millis(describe.duration),
+            "describes": describe.describes.iter().map(|nested| describe_json(nested, file)).collect::<Vec<_>>(),
+            "shoulds": describe.shoulds.iter().map(|should| json!({
+                "description": should.description,
+                "passed": should.passed,
+                "skipped": should.skipped,
+                "duration_ms": millis(should.duration),
+                "message": should.message,
+                "expected": should.expected,
+                "actual": should.actual,
+                "location": should.line.map(|line| json!({ "file": file.name, "line": line })),
+            })).collect::<Vec<_>>(),
+        })
+    }
+
+    let mut total = Counts::default();
+    let mut errored = 0;
+
+    let files: Vec<Value> = files
+        .iter()
+        .zip(outcomes)
+        .zip(outputs)
+        .map(|((file, outcome), output)| match outcome {
+            Outcome::Ran(results) => {
+                let counts = counts(results);
+                total.add(counts);
+                json!({
+                    "file": file.name,
+                    "status": if counts.failed == 0 { "passed" } else { "failed" },
+                    "passed": counts.passed,
+                    "failed": counts.failed,
+                    "skipped": counts.skipped,
+                    "duration_ms": millis(duration(results)),
+                    "output": output,
+                    "tests": results.iter().map(|test| json!({
+                        "name": test.name,
+                        "duration_ms": millis(test.duration),
+                        "describes": test.describes.iter().map(|nested| describe_json(nested, file)).collect::<Vec<_>>(),
+                    })).collect::<Vec<_>>(),
+                })
+            }
+            Outcome::Errored(message) => {
+                errored += 1;
+                json!({
+                    "file": file.name,
+                    "status": "errored",
+                    "error": message,
+                    "output": output,
+                })
+            }
+        })
+        .collect();
+
+    let mut result = serde_json::to_string_pretty(&json!({
+        "files": files,
+        "passed": total.passed,
+        "failed": total.failed,
+        "skipped": total.skipped,
+        "errored": errored,
+    }))
+    .unwrap();
+    result.push('\n');
+    result
+}
+
+fn junit(files: &[TestFile], outcomes: &[Outcome], outputs: &[String]) -> String {
+    let mut suites = String::new();
+    let mut total = Counts::default();
+    let mut errored = 0;
+    let mut total_duration = Duration::ZERO;
+
+    for ((file, outcome), output) in files.iter().zip(outcomes).zip(outputs) {
+        let system_out = if output.is_empty() {
+            String::new()
+        } else {
+            format!("    <system-out>{}</system-out>\n", escape(output))
+        };
+        match outcome {
+            Outcome::Ran(results) => {
+                let counts = counts(results);
+                let duration = duration(results);
+                total.add(counts);
+                total_duration += duration;
+
+                suites.push_str(&format!(
+                    "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{:.3}\">\n",
+                    escape(&file.name),
+                    counts.passed + counts.failed + counts.skipped,
+                    counts.failed,
+                    counts.skipped,
+                    duration.as_secs_f64()
+                ));
+                for case in cases(results) {
+                    let line = case
+                        .should
+                        .line
+                        .map(|line| format!(" line=\"{line}\""))
+                        .unwrap_or_default();
+                    let testcase = format!(
+                        "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\" file=\"{}\"{line}",
+                        escape(&case.path.join(" / ")),
+                        escape(&case.should.description),
+                        case.should.duration.as_secs_f64(),
+                        escape(&file.name),
+                    );
+                    match &case.should.message {
+                        _ if case.should.skipped => suites
+                            .push_str(&format!("{testcase}>\n      <skipped/>\n    </testcase>\n")),
+                        Some(message) if !case.should.passed => {
+                            let mut details =
+                                vec![case.location(file).unwrap_or(file.name.clone())];
+                            details.extend(case.failure().into_iter().skip(1));
+                            suites.push_str(&format!(
+                                "{testcase}>\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+                                escape(message),
+                                escape(&details.join("\n"))
+                            ));
+                        }
+                        _ => suites.push_str(&format!("{testcase}/>\n")),
+                    }
+                }
+                suites.push_str(&system_out);
+                suites.push_str("  </testsuite>\n");
+            }
+            Outcome::Errored(message) => {
+                errored += 1;
+                suites.push_str(&format!(
+                    "  <testsuite name=\"{name}\" tests=\"1\" failures=\"0\" errors=\"1\" skipped=\"0\" time=\"0.000\">\n    <testcase classname=\"{name}\" name=\"{name}\" time=\"0.000\" file=\"{name}\">\n      <error message=\"{message}\"/>\n    </testcase>\n{system_out}  </testsuite>\n",
+                    name = escape(&file.name),
+                    message = escape(message),
+                ));
+            }
+        }
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites tests=\"{}\" failures=\"{}\" errors=\"{errored}\" skipped=\"{}\" time=\"{:.3}\">\n{suites}</testsuites>\n",
+        total.passed + total.failed + total.skipped + errored,
+        total.failed,
+        total.skipped,
+        total_duration.as_secs_f64()
+    )
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn tap(files: &[TestFile], outcomes: &[Outcome], outputs: &[String]) -> String {
+    let mut lines = vec![];
+    let mut count = 0;
+    for ((file, outcome), output) in files.iter().zip(outcomes).zip(outputs) {
+        // printed lines become comments in front of the results of their file
+        for line in output.lines() {
+            lines.push(format!("# {line}").trim_end().to_string());
+        }
+        match outcome {
+            Outcome::Ran(results) => {
+                for case in cases(results) {
+                    count += 1;
+                    let status = if case.should.passed { "ok" } else { "not ok" };
+                    let mut line = format!("{status} {count} - {} / {}", file.name, case.name());
+                    if case.should.skipped {
+                        line.push_str(" # SKIP");
+                    }
+                    if !case.should.passed {
+                        line.push_str("\n  ---");
+                        if let Some(message) = &case.should.message {
+                            line.push_str(&format!("\n  message: {}", yaml_string(message)));
+                        }
+                        if let Some(expected) = &case.should.expected {
+                            line.push_str(&format!("\n  expected: {}", yaml_string(expected)));
+                        }
+                        if let Some(actual) = &case.should.actual {
+                            line.push_str(&format!("\n  actual: {}", yaml_string(actual)));
+                        }
+                        if let Some(location) = case.location(file) {
+                            line.push_str(&format!("\n  at: {}", yaml_string(&location)));
+                        }
+                        line.push_str(&format!(
+                            "\n  duration_ms: {}",
+                            millis(case.should.duration)
+                        ));
+                        line.push_str("\n  ...");
+                    }
+                    lines.push(line);
+                }
+            }
+            Outcome::Errored(message) => {
+                count += 1;
+                lines.push(format!(
+                    "not ok {count} - {}\n  ---\n  message: {}\n  ...",
+                    file.name,
+                    yaml_string(message)
+                ));
+            }
+        }
+    }
+
+    let mut result = format!("TAP version 13\n1..{count}\n");
+    for line in lines {
+        result.push_str(&line);
+        result.push('\n');
+    }
+    result
+}
+
+// multi line values get double quoted, json strings are valid yaml
+fn yaml_string(value: &str) -> String {
+    if value.contains('\n') {
+        Value::from(value).to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use crate::backend::test::report::{report, Reporter};
+    use crate::backend::test::result::{DescribeResult, ShouldResult, TestResult};
+    use crate::backend::test::{Outcome, TestFile};
+
+    fn file(name: &str) -> TestFile {
+        TestFile {
+            path: PathBuf::from(name),
+            name: name.to_string(),
+        }
+    }
+
+    fn should(description: &str, passed: bool, line: usize) -> ShouldResult {
+        ShouldResult {
+            description: description.to_string(),
+            passed,
+            skipped: false,
+            duration: Duration::from_millis(2),
+            message: if passed {
+                None
+            } else {
+                Some("returned false".to_string())
+            },
+            expected: None,
+            actual: None,
+            line: Some(line),
+        }
+    }
+
+    fn test(shoulds: Vec<ShouldResult>) -> TestResult {
+        TestResult {
+            name: "Point".to_string(),
+            duration: Duration::from_millis(5),
+            describes: vec![DescribeResult {
+                description: "x".to_string(),
+                duration: Duration::from_millis(4),
+                describes: vec![],
+                shoulds,
+            }],
+        }
+    }
+
+    fn outcomes() -> (Vec<TestFile>, Vec<Outcome>) {
+        (
+            vec![file("a.test.ec"), file("b.test.ec"), file("c.test.ec")],
+            vec![
+                Outcome::Ran(vec![
+                    test(vec![
+                        should("is 1", true, 3),
+                        should("is set", true, 4),
+                        ShouldResult {
+                            skipped: true,
+                            ..should("is unset", true, 5)
+                        },
+                    ]),
+                    test(vec![should("is 2", true, 9)]),
+                ]),
+                Outcome::Ran(vec![test(vec![
+                    should("is 1", true, 3),
+                    should("is < 2", false, 4),
+                    ShouldResult {
+                        message: Some("expected to equal".to_string()),
+                        expected: Some("3".to_string()),
+                        actual: Some("4".to_string()),
+                        ..should("is 3", false, 5)
+                    },
+                ])]),
+                Outcome::Errored("Failed to read c.test.ec".to_string()),
+            ],
+        )
+    }
+
+    #[test]
+    fn reporter_from_str() {
+        assert_eq!("junit".parse(), Ok(Reporter::Junit));
+        assert_eq!("tap".parse(), Ok(Reporter::Tap));
+        assert!("xml".parse::<Reporter>().is_err());
+    }
+
+    #[test]
+    fn pretty() {
+        let (files, outcomes) = outcomes();
+        let outputs = vec![String::new(); files.len()];
+        assert_eq!(
+            report(Reporter::Pretty, &files, &outcomes, &outputs, false),
+            "----------------------
+Pass  a.test.ec - 3 passed, 1 skipped
+Fail  b.test.ec - 1 passed, 2 failed
+      Point / x / is < 2 (b.test.ec:4)
+        returned false
+      Point / x / is 3 (b.test.ec:5)
+        expected to equal
+        expected: 3
+        actual:   4
+Error c.test.ec - Failed to read c.test.ec
+----------------------
+Files: 1 passed, 1 failed, 1 errored
+Tests: 4 passed, 2 failed, 1 skipped
+"
+        );
+    }
+
+    #[test]
+    fn junit() {
+        let (files, outcomes) = outcomes();
+        let outputs = vec![String::new(); files.len()];
+        let result = report(
+            Reporter::Junit,
+            &files[1..],
+            &outcomes[1..],
+            &outputs[1..],
+            false,
+        );
+        assert_eq!(
+            result,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="4" failures="2" errors="1" skipped="0" time="0.005">
+  <testsuite name="b.test.ec" tests="3" failures="2" errors="0" skipped="0" time="0.005">
+    <testcase classname="Point / x" name="is 1" time="0.002" file="b.test.ec" line="3"/>
+    <testcase classname="Point / x" name="is &lt; 2" time="0.002" file="b.test.ec" line="4">
+      <failure message="returned false">b.test.ec:4</failure>
+    </testcase>
+    <testcase classname="Point / x" name="is 3" time="0.002" file="b.test.ec" line="5">
+      <failure message="expected to equal">b.test.ec:5
+expected: 3
+actual:   4</failure>
+    </testcase>
+  </testsuite>
+  <testsuite name="c.test.ec" tests="1" failures="0" errors="1" skipped="0" time="0.000">
+    <testcase classname="c.test.ec" name="c.test.ec" time="0.000" file="c.test.ec">
+      <error message="Failed to read c.test.ec"/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+
+    #[test]
+    fn tap() {
+        let (files, outcomes) = outcomes();
+        let outputs = vec![String::new(); files.len()];
+        let result = report(
+            Reporter::Tap,
+            &files[1..],
+            &outcomes[1..],
+            &outputs[1..],
+            false,
+        );
+        assert_eq!(
+            result,
+            "TAP version 13
+1..4
+ok 1 - b.test.ec / Point / x / is 1
+not ok 2 - b.test.ec / Point / x / is < 2
+  ---
+  message: 'returned false'
+  at: 'b.test.ec:4'
+  duration_ms: 2
+  ...
+not ok 3 - b.test.ec / Point / x / is 3
+  ---
+  message: 'expected to equal'
+  expected: '3'
+  actual: '4'
+  at: 'b.test.ec:5'
+  duration_ms: 2
+  ...
+not ok 4 - c.test.ec
+  ---
+  message: 'Failed to read c.test.ec'
+  ...
+"
+        );
+    }
+
+    #[test]
+    fn json() {
+        let (files, outcomes) = outcomes();
+        let outputs = vec![String::new(); files.len()];
+        let result: serde_json::Value =
+            serde_json::from_str(&report(Reporter::Json, &files, &outcomes, &outputs, false))
+                .unwrap();
+
+        assert_eq!(result["passed"], json!(4));
+        assert_eq!(result["failed"], json!(2));
+        assert_eq!(result["skipped"], json!(1));
+        assert_eq!(result["errored"], json!(1));
+        assert_eq!(result["files"][1]["status"], json!("failed"));
+        assert_eq!(
+            result["files"][1]["tests"][0]["describes"][0]["shoulds"][1],
+            json!({
+                "description": "is < 2",
+                "passed": false,
+                "skipped": false,
+                "duration_ms": 2.0,
+                "message": "returned false",
+                "expected": null,
+                "actual": null,
+                "location": { "file": "b.test.ec", "line": 4 },
+            })
+        );
+        assert_eq!(
+            result["files"][2],
+            json!({
+                "file": "c.test.ec",
+                "status": "errored",
+                "error": "Failed to read c.test.ec",
+                "output": "",
+            })
+        );
+    }
+
+    #[test]
+    fn skipped() {
+        let (files, outcomes) = outcomes();
+        let outputs = vec![String::new(); files.len()];
+        let junit = report(
+            Reporter::Junit,
+            &files[..1],
+            &outcomes[..1],
+            &outputs[..1],
+            false,
+        );
+        assert!(junit.contains(r#"<testsuite name="a.test.ec" tests="4" failures="0" errors="0" skipped="1" time="0.010">"#));
+        assert!(junit.contains(
+            r#"    <testcase classname="Point / x" name="is unset" time="0.002" file="a.test.ec" line="5">
+      <skipped/>
+    </testcase>"#
+        ));
+
+        let tap = report(
+            Reporter::Tap,
+            &files[..1],
+            &outcomes[..1],
+            &outputs[..1],
+            false,
+        );
+        assert!(tap.contains("ok 3 - a.test.ec / Point / x / is unset # SKIP\n"));
+    }
+
+    fn printed() -> (Vec<TestFile>, Vec<Outcome>, Vec<String>) {
+        (
+            vec![file("a.test.ec"), file("c.test.ec")],
+            vec![
+                Outcome::Ran(vec![test(vec![should("is 1", true, 3)])]),
+                Outcome::Errored("Failed to read c.test.ec".to_string()),
+            ],
+            vec!["x is <1>\n\nnext ".to_string(), "partial".to_string()],
+        )
+    }
+
+    #[test]
+    fn tap_with_output() {
+        let (files, outcomes, outputs) = printed();
+        assert_eq!(
+            report(Reporter::Tap, &files, &outcomes, &outputs, false),
+            "TAP version 13
+1..2
+# x is <1>
+#
+# next
+ok 1 - a.test.ec / Point / x / is 1
+# partial
+not ok 2 - c.test.ec
+  ---
+  message: 'Failed to read c.test.ec'
+  ...
+"
+        );
+    }
+
+    #[test]
+    fn junit_with_output() {
+        let (files, outcomes, outputs) = printed();
+        let result = report(Reporter::Junit, &files, &outcomes, &outputs, false);
+        assert!(result.contains(
+            r#"    <testcase classname="Point / x" name="is 1" time="0.002" file="a.test.ec" line="3"/>
+    <system-out>x is &lt;1&gt;
+
+next </system-out>
+  </testsuite>"#
+        ));
+        assert!(result.contains(
+            r#"    </testcase>
+    <system-out>partial</system-out>
+  </testsuite>"#
+        ));
+    }
+
+    #[test]
+    fn json_with_output() {
+        let (files, outcomes, outputs) = printed();
+        let result: serde_json::Value =
+            serde_json::from_str(&report(Reporter::Json, &files, &outcomes, &outputs, false))
+                .unwrap();
+        assert_eq!(result["files"][0]["output"], json!("x is <1>\n\nnext "));
+        assert_eq!(result["files"][1]["output"], json!("partial"));
+    }
+}
@@ -0,0 +1,289 @@
+use std::time::Duration;
+
+use crate::backend::run::value::{ObjectValue, Value};
+use crate::frontend::context::Context;
+
+/// Result of a single `test` block, as recorded by the test runner
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestResult {
+    pub name: String,
+    pub duration: Duration,
+    pub describes: Vec<DescribeResult>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DescribeResult {
+    pub description: String,
+    pub duration: Duration,
+    pub describes: Vec<DescribeResult>,
+    pub shoulds: Vec<ShouldResult>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShouldResult {
+    pub description: String,
+    pub passed: bool,
+    /// Skipped shoulds did not run, either explicitly skipped, not focused or filtered out
+    pub skipped: bool,
+    pub duration: Duration,
+    /// Why the assertion failed, None if it passed
+    pub message: Option<String>,
+    /// Values a failed assertion compared, rendered like the interpreter prints them
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    /// Line of the should call within the test file
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Counts {
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
+impl Counts {
+    pub fn add(&mut self, other: Counts) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.skipped += other.skipped;
+    }
+}
+
+impl TestResult {
+    pub fn counts(&self) -> Counts {
+        let mut result = Counts::default();
+        for describe in &self.describes {
+            result.add(describe.counts());
+        }
+        result
+    }
+
+    pub fn passed(&self) -> bool {
+        self.counts().failed == 0
+    }
+}
+
+impl DescribeResult {
+    pub fn counts(&self) -> Counts {
+        let mut result = Counts::default();
+        for should in &self.shoulds {
+            if should.skipped {
+                result.skipped += 1;
+            } else if should.passed {
+                result.passed += 1;
+            } else {
+                result.failed += 1;
+            }
+        }
+        for describe in &self.describes {
+            result.add(describe.counts());
+        }
+        result
+    }
+
+    /// Fails all shoulds of the describe and its nested describes which passed
+    fn fail(&mut self, message: &str) {
+        for should in &mut self.shoulds {
+            if should.passed && !should.skipped {
+                should.passed = false;
+                should.message = Some(message.to_string());
+            }
+        }
+        for describe in &mut self.describes {
+            describe.fail(message);
+        }
+    }
+}
+
+/// Reads the results the test runner recorded in its `test_results` list
+pub(crate) fn read_results(ctx: &mut Context, results: &Value) -> Vec<TestResult> {
+    let mut reader = Reader { ctx };
+    reader
+        .list(results)
+        .iter()
+        .map(|result| reader.test(result))
+        .collect()
+}
+
+struct Reader<'a> {
+    ctx: &'a mut Context,
+}
+
+impl<'a> Reader<'a> {
+    fn test(&mut self, value: &Value) -> TestResult {
+        let object = object(value);
+        let mut result = TestResult {
+            name: self.string(object, "name"),
+            duration: self.duration(object),
+            describes: self.describes(object),
+        };
+        if let Some(failure) = self.hook_failure(object) {
+            for describe in &mut result.describes {
+                describe.fail(&failure);
+            }
+        }
+        result
+    }
+
+    fn describe(&mut self, value: &Value) -> DescribeResult {
+        let object = object(value);
+        let it_results = self.property(object, "it_results");
+        let mut result = DescribeResult {
+            description: self.string(object, "description"),
+            duration: self.duration(object),
+            describes: self.describes(object),
+            shoulds: self
+                .list(&it_results)
+                .iter()
+                .map(|should| self.should(should))
+                .collect(),
+        };
+        if let Some(failure) = self.hook_failure(object) {
+            result.fail(&failure);
+        }
+        result
+    }
+
+    /// Why the after_all hooks of a block failed, they run once all of its shoulds passed already
+    fn hook_failure(&mut self, object: &ObjectValue) -> Option<String> {
+        Some(self.string(object, "hook_failure")).filter(|failure| !failure.is_empty())
+    }
+
+    fn should(&mut self, value: &Value) -> ShouldResult {
+        let object = object(value);
+        let Value::Bool(passed) = self.property(object, "passed") else {
+            panic!("passed of test result is not a bool")
+        };
+        let Value::Bool(skipped) = self.property(object, "skipped") else {
+            panic!("skipped of test result is not a bool")
+        };
+        let Value::Bool(compared) = self.property(object, "compared") else {
+            panic!("compared of test result is not a bool")
+        };
+        ShouldResult {
+            description: self.string(object, "description"),
+            passed,
+            skipped,
+            duration: self.duration(object),
+            message: if passed {
+                None
+            } else {
+                Some(self.string(object, "message"))
+            },
+            expected: compared.then(|| self.string(object, "expected")),
+            actual: compared.then(|| self.string(object, "actual")),
+            line: None,
+        }
+    }
+
+    fn describes(&mut self, object: &ObjectValue) -> Vec<DescribeResult> {
+        let describe_results = self.property(object, "describe_results");
+        self.list(&describe_results)
+            .iter()
+            .map(|describe| self.describe(describe))
+            .collect()
+    }
+
+    fn duration(&mut self, object: &ObjectValue) -> Duration {
+        match self.property(object, "duration") {
+            Value::Number(millis) => Duration::from_secs_f64(millis.max(0.0) / 1000.0),
+            _ => panic!("duration of test result is not a number"),
+        }
+    }
+
+    fn string(&mut self, object: &ObjectValue, name: &str) -> String {
+        match self.property(object, name) {
+            Value::String(value) => value,
+            value => value.to_string(),
+        }
+    }
+
+    fn property(&mut self, object: &ObjectValue, name: &str) -> Value {
+        let key = self.ctx.string_table.push_str(name);
+        object
+            .get_property(&key)
+            .cloned()
+            .unwrap_or_else(|| panic!("test result without {name}"))
+    }
+
+    fn list(&self, value: &Value) -> Vec<Value> {
+        let Value::List(list) = value else {
+            panic!("test results are not a list")
+        };
+        list.0.borrow().clone()
+    }
+}
+
+fn object(value: &Value) -> &ObjectValue {
+    let Value::Object(object) = value else {
+        panic!("test result is not an object")
+    };
+    object
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::backend::test::result::{Counts, DescribeResult, ShouldResult, TestResult};
+
+    fn should(passed: bool) -> ShouldResult {
+        ShouldResult {
+            description: "works".to_string(),
+            passed,
+            skipped: false,
+            duration: Duration::ZERO,
+            message: if passed {
+                None
+            } else {
+                Some("returned false".to_string())
+            },
+            expected: None,
+            actual: None,
+            line: None,
+        }
+    }
+
+    fn describe(shoulds: Vec<ShouldResult>, describes: Vec<DescribeResult>) -> DescribeResult {
+        DescribeResult {
+            description: "describe".to_string(),
+            duration: Duration::ZERO,
+            describes,
+            shoulds,
+        }
+    }
+
+    #[test]
+    fn counts_nested_results() {
+        let result = TestResult {
+            name: "test".to_string(),
+            duration: Duration::ZERO,
+            describes: vec![
+                describe(vec![should(true), should(true)], vec![]),
+                describe(
+                    vec![
+                        should(true),
+                        ShouldResult {
+                            skipped: true,
+                            ..should(true)
+                        },
+                    ],
+                    vec![describe(vec![should(false)], vec![])],
+                ),
+            ],
+        };
+
+        assert_eq!(
+            result.counts(),
+            Counts {
+                passed: 3,
+                failed: 1,
+                skipped: 1
+            }
+        );
+        assert!(!result.passed());
+        assert_eq!(result.describes[0].counts().failed, 0);
+        assert!(result.describes[1].counts().failed > 0);
+    }
+}
@@ -0,0 +1,132 @@
+use std::path::Path;
+use std::time::Duration;
+use std::{fs, io};
+
+use crate::backend::test::result::{DescribeResult, ShouldResult, TestResult};
+
+const OUT: &str = "// out:";
+
+/// Printed output as it gets compared, print separates its arguments by a trailing space and
+/// colors are kept the way they are written within string literals
+pub(crate) fn normalize(output: &str) -> String {
+    let output = output.replace('\x1b', "\\x1b");
+    let mut lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// Expected output of a file, one // out: comment per line
+pub(crate) fn out_lines(source: &str) -> Vec<(usize, String)> {
+    source
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let (_, expected) = line.split_once(OUT)?;
+            Some((index + 1, expected.to_string()))
+        })
+        .collect()
+}
+
+/// Compares what a file without tests printed with its // out: lines, ignoring whitespace
+/// around lines and blank lines like the smoke tests always did
+pub(crate) fn check_out(
+    expected: &[(usize, String)],
+    output: &str,
+    duration: Duration,
+) -> TestResult {
+    let line = expected.first().map(|(line, _)| *line);
+    let relevant = |line: &str| {
+        let line = line.trim();
+        (!line.is_empty()).then(|| line.to_string())
+    };
+    let expected: Vec<String> = expected
+        .iter()
+        .filter_map(|(_, line)| relevant(&line.replace('\x1b', "\\x1b")))
+        .collect();
+    let actual: Vec<String> = normalize(output).lines().filter_map(relevant).collect();
+
+    let passed = expected == actual;
+    TestResult {
+        name: "output".to_string(),
+        duration,
+        describes: vec![DescribeResult {
+            description: OUT.to_string(),
+            duration,
+            describes: vec![],
+            shoulds: vec![ShouldResult {
+                description: "print the expected output".to_string(),
+                passed,
+                skipped: false,
+                duration,
+                message: (!passed).then(|| "expected to print".to_string()),
+                expected: (!passed).then(|| expected.join("\n")),
+                actual: (!passed).then(|| actual.join("\n")),
+                line,
+            }],
+        }],
+    }
+}
+
+/// Content of a golden file, it gets (re)written with the actual output if it does not exist yet
+/// or snapshots get updated
+pub(crate) fn golden(path: &Path, actual: &str, update: bool) -> io::Result<String> {
+    if update || !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, format!("{actual}\n"))?;
+        return Ok(actual.to_string());
+    }
+    Ok(normalize(&fs::read_to_string(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::Duration;
+
+    use crate::backend::test::snapshot::{check_out, golden, normalize, out_lines};
+
+    #[test]
+    fn normalizes_output() {
+        assert_eq!(normalize("a \nb \n\n"), "a\nb");
+        assert_eq!(normalize("a\\nb"), "a\\nb");
+        assert_eq!(
+            normalize("\x1b[0;32mPass\x1b[0m "),
+            "\\x1b[0;32mPass\\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn checks_out_lines() {
+        let source = "std::io::println('a')\n// out: a\n\n// out:  b\n";
+        let expected = out_lines(source);
+        assert_eq!(
+            expected,
+            vec![(2, " a".to_string()), (4, "  b".to_string())]
+        );
+
+        let result = check_out(&expected, "a \n\nb \n", Duration::ZERO);
+        assert!(result.passed());
+        assert_eq!(result.describes[0].shoulds[0].line, Some(2));
+
+        let result = check_out(&expected, "a \nc \n", Duration::ZERO);
+        let should = &result.describes[0].shoulds[0];
+        assert!(!should.passed);
+        assert_eq!(should.expected, Some("a\nb".to_string()));
+        assert_eq!(should.actual, Some("a\nc".to_string()));
+    }
+
+    #[test]
+    fn writes_missing_golden_files() {
+        let path = std::env::temp_dir().join("elodie_test_golden/output.golden");
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(golden(&path, "first", false).unwrap(), "first");
+        assert_eq!(golden(&path, "second", false).unwrap(), "first");
+        assert_eq!(golden(&path, "second", true).unwrap(), "second");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second\n");
+    }
+}
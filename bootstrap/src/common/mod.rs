@@ -1,10 +1,11 @@
 pub use package::PackagePath;
 pub use span::*;
-pub use string::{StringTable, StringTableId};
+pub use string::{StringNames, StringTable, StringTableId};
 pub use util::*;
 
 pub mod node;
 mod package;
+pub mod panic;
 mod span;
 mod string;
 mod util;
@@ -0,0 +1,41 @@
+use std::any::Any;
+use std::panic::{self, catch_unwind, AssertUnwindSafe};
+
+/// Runs the function like catch_unwind, but keeps the panic hook from printing the panic. The
+/// previous hook is back in place afterwards
+pub fn catch_silently<R>(function: impl FnOnce() -> R) -> Result<R, String> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = catch_unwind(AssertUnwindSafe(function));
+    panic::set_hook(hook);
+    result.map_err(panic_message)
+}
+
+pub fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    let message = panic
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| panic.downcast_ref::<&str>().map(|s| s.to_string()))
+        .unwrap_or_else(|| "unknown error".to_string());
+    // unsupported constructs panic with the debug output of the node, the first line names it
+    message
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches(" {")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::panic::catch_silently;
+
+    #[test]
+    fn catches_first_line_of_panic() {
+        assert_eq!(catch_silently(|| 1), Ok(1));
+        assert_eq!(
+            catch_silently(|| panic!("Node {{\n  value: 1\n}}")),
+            Err::<(), _>("Node".to_string())
+        );
+    }
+}
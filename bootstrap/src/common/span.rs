@@ -1,3 +1,5 @@
+use crate::common::StringTableId;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Span {
     pub start: Position,
@@ -46,11 +48,18 @@ pub struct Position {
     pub row: Row,
     pub column: Column,
     pub index: Index,
+    /// Path of the file the position is in, None for sources which were not loaded from a file
+    pub file: Option<StringTableId>,
 }
 
 impl Position {
     pub fn new(row: Row, column: Column, index: Index) -> Self {
-        Self { row, column, index }
+        Self {
+            row,
+            column,
+            index,
+            file: None,
+        }
     }
 }
 
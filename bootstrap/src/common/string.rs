@@ -1,3 +1,4 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
@@ -14,6 +15,22 @@ impl AsRef<StringTableId> for StringTableId {
 pub struct StringTable {
     indexes: HashMap<Rc<str>, StringTableId>,
     values: Vec<Rc<str>>,
+    names: StringNames,
+}
+
+/// Looks up the strings of a table without borrowing it, for whoever outlives the borrow like
+/// intrinsics. Sees the strings pushed to the table later on as well
+#[derive(Debug, Clone, Default)]
+pub struct StringNames(Rc<RefCell<Vec<Rc<str>>>>);
+
+impl StringNames {
+    pub fn get(&self, idx: impl AsRef<StringTableId>) -> Rc<str> {
+        self.0
+            .borrow()
+            .get(idx.as_ref().0)
+            .expect("StringIdx out of bounds")
+            .clone()
+    }
 }
 
 impl StringTable {
@@ -21,6 +38,7 @@ impl StringTable {
         StringTable {
             indexes: HashMap::new(),
             values: Vec::new(),
+            names: StringNames::default(),
         }
     }
 
@@ -30,6 +48,7 @@ impl StringTable {
             std::collections::hash_map::Entry::Vacant(entry) => {
                 let idx = StringTableId(self.values.len());
                 self.values.push(entry.key().clone());
+                self.names.0.borrow_mut().push(entry.key().clone());
                 entry.insert(idx);
                 idx
             }
@@ -42,6 +61,10 @@ impl StringTable {
             .get(idx.as_ref().0)
             .expect("StringIdx out of bounds")
     }
+
+    pub fn names(&self) -> StringNames {
+        self.names.clone()
+    }
 }
 
 #[cfg(test)]
@@ -62,6 +85,15 @@ mod tests {
         assert_eq!(map.get(idx2), "world");
     }
 
+    #[test]
+    fn names_see_later_strings() {
+        let mut map = StringTable::new();
+        let names = map.names();
+        let idx = map.push_str("hello");
+
+        assert_eq!(&*names.get(idx), "hello");
+    }
+
     #[test]
     #[should_panic(expected = "StringIdx out of bounds")]
     fn get_out_of_bounds() {
@@ -0,0 +1,266 @@
+use crate::common::node::Node as AstNode;
+use crate::common::node::Source;
+use crate::common::{StringTable, WithSpan};
+use crate::dump::Node;
+use crate::frontend::ast::{
+    AstBlockNode, AstDeclareExternalFunctionNode, AstDeclareFunctionNode, AstDeclarePackageNode,
+    AstDefineTypeNode, AstFunctionArgument, AstIdentifier, AstTreeNode, AstType,
+};
+use crate::frontend::lex::token::{LiteralToken, TokenKind};
+use crate::frontend::Ast;
+
+pub(crate) fn dump(strings: &StringTable, ast: &Ast) -> Vec<Node> {
+    dump_nodes(strings, &ast.nodes)
+}
+
+fn dump_nodes(strings: &StringTable, nodes: &[AstTreeNode]) -> Vec<Node> {
+    nodes.iter().map(|node| dump_node(strings, node)).collect()
+}
+
+fn dump_node(strings: &StringTable, node: &AstTreeNode) -> Node {
+    let name = |identifier: &AstIdentifier| strings.get(identifier.0).to_string();
+
+    let result = match node.node() {
+        AstNode::AccessVariable(node) => {
+            Node::new("AccessVariable").attribute("variable", name(&node.variable))
+        }
+        AstNode::AccessVariableOfObject(node) => Node::new("AccessVariableOfObject")
+            .attribute("object", name(&node.object))
+            .attribute("variable", name(&node.variable)),
+        AstNode::AccessVariableOfSelf(node) => {
+            Node::new("AccessVariableOfSelf").attribute("variable", name(&node.variable))
+        }
+        AstNode::Block(node) => {
+            Node::new("Block").children("nodes", dump_nodes(strings, &node.nodes))
+        }
+        AstNode::BreakLoop(node) => Node::new("BreakLoop").optional(
+            "node",
+            node.node.as_ref().map(|node| dump_node(strings, node)),
+        ),
+        AstNode::Calculate(node) => Node::new("Calculate")
+            .attribute("operator", format!("{:?}", node.operator))
+            .child("left", dump_node(strings, &node.left))
+            .child("right", dump_node(strings, &node.right)),
+        AstNode::CallFunction(node) => Node::new("CallFunction")
+            .attribute("function", name(&node.function))
+            .children("arguments", dump_nodes(strings, &node.arguments)),
+        AstNode::CallFunctionWithLambda(node) => Node::new("CallFunctionWithLambda")
+            .attribute("function", name(&node.function))
+            .children("arguments", dump_nodes(strings, &node.arguments))
+            .child("lambda", dump_block(strings, &node.lambda)),
+        AstNode::CallFunctionOfObject(node) => Node::new("CallFunctionOfObject")
+            .attribute("object", name(&node.object))
+            .attribute("function", name(&node.function))
+            .children("arguments", dump_nodes(strings, &node.arguments))
+            .optional(
+                "lambda",
+                node.lambda
+                    .as_ref()
+                    .map(|lambda| dump_block(strings, lambda)),
+            ),
+        AstNode::CallFunctionOfPackage(node) => Node::new("CallFunctionOfPackage")
+            .attribute(
+                "package",
+                node.package
+                    .segments
+                    .iter()
+                    .map(|segment| strings.get(segment))
+                    .collect::<Vec<_>>()
+                    .join("::"),
+            )
+            .attribute("function", name(&node.function))
+            .children("arguments", dump_nodes(strings, &node.arguments)),
+        AstNode::Compare(node) => Node::new("Compare")
+            .attribute("operator", format!("{:?}", node.operator))
+            .child("left", dump_node(strings, &node.left))
+            .child("right", dump_node(strings, &node.right)),
+        AstNode::ContinueLoop(_) => Node::new("ContinueLoop"),
+        AstNode::DeclareExternalFunction(node) => dump_declare_external_function(strings, node),
+        AstNode::DeclareFunction(node) => dump_declare_function(strings, node),
+        AstNode::DeclarePackage(node) => dump_declare_package(strings, node),
+        AstNode::DeclareType(node) => Node::new("DeclareType")
+            .attribute("type", name(&node.r#type))
+            .attribute("exported", node.modifiers.is_exported())
+            .children(
+                "variables",
+                node.variables
+                    .iter()
+                    .map(|variable| {
+                        Node::new("Variable")
+                            .attribute("name", name(&variable.variable))
+                            .attribute("type", type_name(&variable.r#type))
+                    })
+                    .collect(),
+            ),
+        AstNode::DeclareVariable(node) => {
+            let result = Node::new("DeclareVariable").attribute("variable", name(&node.variable));
+            let result = match &node.value_type {
+                Some(value_type) => result.attribute("type", type_name(value_type)),
+                None => result,
+            };
+            result.child("value", dump_node(strings, &node.value))
+        }
+        AstNode::DefineType(node) => dump_define_type(strings, node),
+        AstNode::ExportPackage(node) => {
+            let Source::LocalFile { path } = &node.source;
+            Node::new("ExportPackage")
+                .attribute("package", name(&node.package))
+                .attribute("source", path.as_str())
+        }
+        AstNode::If(node) => Node::new("If")
+            .child("condition", dump_node(strings, &node.condition))
+            .child("then", dump_block(strings, &node.then))
+            .optional(
+                "otherwise",
+                node.otherwise
+                    .as_ref()
+                    .map(|otherwise| dump_block(strings, otherwise)),
+            ),
+        AstNode::InterpolateString(node) => {
+            Node::new("InterpolateString").children("nodes", dump_nodes(strings, &node.nodes))
+        }
+        AstNode::InstantiateType(node) => Node::new("InstantiateType")
+            .attribute("type", name(&node.r#type))
+            .children(
+                "arguments",
+                node.arguments
+                    .iter()
+                    .map(|argument| {
+                        Node::new("NamedArgument")
+                            .attribute("name", name(&argument.identifier))
+                            .child("value", dump_node(strings, &argument.value))
+                    })
+                    .collect(),
+            ),
+        AstNode::LiteralBoolean(node) => Node::new("LiteralBoolean").attribute(
+            "value",
+            node.0.kind == TokenKind::Literal(LiteralToken::True),
+        ),
+        AstNode::LiteralNumber(node) => {
+            Node::new("LiteralNumber").attribute("value", strings.get(node.0.value))
+        }
+        AstNode::LiteralString(node) => {
+            Node::new("LiteralString").attribute("value", strings.get(node.0.value))
+        }
+        AstNode::Loop(node) => {
+            Node::new("Loop").children("nodes", dump_nodes(strings, &node.nodes))
+        }
+        AstNode::ReturnFromFunction(node) => Node::new("ReturnFromFunction").optional(
+            "node",
+            node.node.as_ref().map(|node| dump_node(strings, node)),
+        ),
+        AstNode::Marker(_) => unreachable!(),
+    };
+    result.span(node.span())
+}
+
+fn dump_block(strings: &StringTable, node: &AstBlockNode) -> Node {
+    Node::new("Block").children("nodes", dump_nodes(strings, &node.nodes))
+}
+
+fn dump_arguments(strings: &StringTable, arguments: &[AstFunctionArgument]) -> Vec<Node> {
+    arguments
+        .iter()
+        .map(|argument| {
+            let result = Node::new("Argument").attribute("name", strings.get(argument.argument.0));
+            match &argument.argument_type {
+                Some(argument_type) => result.attribute("type", type_name(argument_type)),
+                None => result,
+            }
+        })
+        .collect()
+}
+
+fn dump_declare_external_function(
+    strings: &StringTable,
+    node: &AstDeclareExternalFunctionNode,
+) -> Node {
+    let result =
+        Node::new("DeclareExternalFunction").attribute("function", strings.get(node.function.0));
+    let result = match &node.return_type {
+        Some(return_type) => result.attribute("return_type", type_name(return_type)),
+        None => result,
+    };
+    result.children("arguments", dump_arguments(strings, &node.arguments))
+}
+
+fn dump_declare_function(strings: &StringTable, node: &AstDeclareFunctionNode) -> Node {
+    let result = Node::new("DeclareFunction").attribute("function", strings.get(node.function.0));
+    let result = match &node.return_type {
+        Some(return_type) => result.attribute("return_type", type_name(return_type)),
+        None => result,
+    };
+    result
+        .children("arguments", dump_arguments(strings, &node.arguments))
+        .child("nodes", dump_block(strings, &node.nodes))
+}
+
+fn dump_declare_package(strings: &StringTable, node: &AstDeclarePackageNode) -> Node {
+    Node::new("DeclarePackage")
+        .attribute("package", strings.get(node.package.0))
+        .attribute("exported", node.modifiers.is_exported())
+        .children(
+            "external_functions",
+            node.external_functions
+                .iter()
+                .map(|node| dump_declare_external_function(strings, node))
+                .collect(),
+        )
+        .children(
+            "functions",
+            node.functions
+                .iter()
+                .map(|node| dump_declare_function(strings, node))
+                .collect(),
+        )
+        .children(
+            "packages",
+            node.packages
+                .iter()
+                .map(|node| dump_declare_package(strings, node))
+                .collect(),
+        )
+        .children(
+            "definitions",
+            node.definitions
+                .iter()
+                .map(|node| dump_define_type(strings, node))
+                .collect(),
+        )
+}
+
+fn dump_define_type(strings: &StringTable, node: &AstDefineTypeNode) -> Node {
+    Node::new("DefineType")
+        .attribute("type", strings.get(node.r#type.0))
+        .attribute("exported", node.modifiers.is_exported())
+        .children(
+            "functions",
+            node.functions
+                .iter()
+                .map(|node| dump_declare_function(strings, node))
+                .collect(),
+        )
+}
+
+fn type_name(r#type: &AstType) -> String {
+    match r#type {
+        AstType::Boolean => "Bool".to_string(),
+        AstType::Object => "Object".to_string(),
+        AstType::Number => "Number".to_string(),
+        AstType::String => "String".to_string(),
+        AstType::Function {
+            arguments,
+            return_type,
+        } => {
+            let arguments = arguments
+                .iter()
+                .map(|argument| type_name(&argument.r#type))
+                .collect::<Vec<_>>()
+                .join(", ");
+            match return_type {
+                Some(return_type) => format!("function({arguments}) -> {}", type_name(return_type)),
+                None => format!("function({arguments})"),
+            }
+        }
+    }
+}
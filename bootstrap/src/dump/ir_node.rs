@@ -0,0 +1,62 @@
+use crate::common::node::Node as IrNodeKind;
+use crate::common::{StringTable, WithSpan};
+use crate::dump::Node;
+use crate::ir::node::{IrNode, IrTreeNode};
+use crate::ir::Ir;
+
+pub(crate) fn dump(strings: &StringTable, ir: &Ir) -> Vec<Node> {
+    ir.nodes
+        .iter()
+        .map(|node| dump_node(strings, node))
+        .collect()
+}
+
+// ir generation only emits literals so far, other nodes carry no data yet
+fn dump_node(strings: &StringTable, node: &IrTreeNode) -> Node {
+    let result = match &node.node {
+        IrNodeKind::LiteralBoolean(node) => Node::new("LiteralBoolean")
+            .attribute("value", node.value)
+            .attribute("type", node.value_type.0),
+        IrNodeKind::LiteralNumber(node) => Node::new("LiteralNumber")
+            .attribute("value", node.value.to_string())
+            .attribute("type", node.value_type.0),
+        IrNodeKind::LiteralString(node) => Node::new("LiteralString")
+            .attribute("value", strings.get(node.value))
+            .attribute("type", node.value_type.0),
+        node => Node::new(kind(node)),
+    };
+    result.span(node.span())
+}
+
+fn kind(node: &IrNode) -> &'static str {
+    match node {
+        IrNodeKind::AccessVariable(_) => "AccessVariable",
+        IrNodeKind::AccessVariableOfObject(_) => "AccessVariableOfObject",
+        IrNodeKind::AccessVariableOfSelf(_) => "AccessVariableOfSelf",
+        IrNodeKind::Block(_) => "Block",
+        IrNodeKind::BreakLoop(_) => "BreakLoop",
+        IrNodeKind::Calculate(_) => "Calculate",
+        IrNodeKind::CallFunction(_) => "CallFunction",
+        IrNodeKind::CallFunctionWithLambda(_) => "CallFunctionWithLambda",
+        IrNodeKind::CallFunctionOfObject(_) => "CallFunctionOfObject",
+        IrNodeKind::CallFunctionOfPackage(_) => "CallFunctionOfPackage",
+        IrNodeKind::Compare(_) => "Compare",
+        IrNodeKind::ContinueLoop(_) => "ContinueLoop",
+        IrNodeKind::DeclareExternalFunction(_) => "DeclareExternalFunction",
+        IrNodeKind::DeclareFunction(_) => "DeclareFunction",
+        IrNodeKind::DeclarePackage(_) => "DeclarePackage",
+        IrNodeKind::DeclareType(_) => "DeclareType",
+        IrNodeKind::DeclareVariable(_) => "DeclareVariable",
+        IrNodeKind::DefineType(_) => "DefineType",
+        IrNodeKind::ExportPackage(_) => "ExportPackage",
+        IrNodeKind::If(_) => "If",
+        IrNodeKind::InterpolateString(_) => "InterpolateString",
+        IrNodeKind::InstantiateType(_) => "InstantiateType",
+        IrNodeKind::LiteralBoolean(_) => "LiteralBoolean",
+        IrNodeKind::LiteralNumber(_) => "LiteralNumber",
+        IrNodeKind::LiteralString(_) => "LiteralString",
+        IrNodeKind::Loop(_) => "Loop",
+        IrNodeKind::ReturnFromFunction(_) => "ReturnFromFunction",
+        IrNodeKind::Marker(_) => unreachable!(),
+    }
+}
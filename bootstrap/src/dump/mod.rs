@@ -0,0 +1,514 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::exit;
+use std::str::FromStr;
+use std::{fmt, io};
+
+use serde_json::{json, Map, Value};
+
+use crate::backend::generate::generate_c_code;
+use crate::backend::intrinsic::Intrinsics;
+use crate::backend::run::run;
+use crate::backend::run::scope::Scope;
+use crate::backend::run::type_definitions::TypeDefinitions;
+use crate::common::panic::catch_silently;
+use crate::common::Span;
+use crate::frontend::lex::lex;
+use crate::frontend::parse::parse;
+use crate::frontend::{ast_from_file, ast_from_source, Context};
+use crate::{backend, frontend, ir};
+
+mod ast;
+mod ir_node;
+mod parse_node;
+mod token;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Frontend(frontend::Error),
+    Ir(ir::Error),
+    Generate(backend::generate::Error),
+    Unsupported(String),
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<frontend::Error> for Error {
+    fn from(value: frontend::Error) -> Self {
+        Self::Frontend(value)
+    }
+}
+
+impl From<frontend::lex::Error> for Error {
+    fn from(value: frontend::lex::Error) -> Self {
+        Self::Frontend(value.into())
+    }
+}
+
+impl From<frontend::parse::Error> for Error {
+    fn from(value: frontend::parse::Error) -> Self {
+        Self::Frontend(value.into())
+    }
+}
+
+impl From<ir::Error> for Error {
+    fn from(value: ir::Error) -> Self {
+        Self::Ir(value)
+    }
+}
+
+impl From<backend::generate::Error> for Error {
+    fn from(value: backend::generate::Error) -> Self {
+        Self::Generate(value)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{err}"),
+            Error::Frontend(err) => write!(f, "{err:?}"),
+            Error::Ir(err) => write!(f, "{err}"),
+            Error::Generate(err) => write!(f, "{err}"),
+            Error::Unsupported(message) => write!(f, "Not supported yet: {message}"),
+        }
+    }
+}
+
+pub(crate) type Result<T, E = Error> = core::result::Result<T, E>;
+
+/// Stage of the compiler pipeline whose output gets dumped
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stage {
+    Tokens,
+    Parse,
+    Ast,
+    Ir,
+    C,
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tokens" => Ok(Stage::Tokens),
+            "parse" => Ok(Stage::Parse),
+            "ast" => Ok(Stage::Ast),
+            "ir" => Ok(Stage::Ir),
+            "c" => Ok(Stage::C),
+            _ => Err(format!(
+                "Unknown stage {s} - supported are tokens, parse, ast, ir and c"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+/// Prints the output of a single compiler stage for a source file
+pub fn dump_file(stage: &str, file: &Path, format: Format) {
+    let stage = match Stage::from_str(stage) {
+        Ok(stage) => stage,
+        Err(message) => {
+            eprintln!("{message}");
+            exit(1)
+        }
+    };
+
+    let result = fs::read_to_string(file)
+        .map_err(Error::from)
+        .and_then(|source| dump(stage, file, &source, format));
+
+    match result {
+        Ok(output) => println!("{output}"),
+        Err(err) => {
+            eprintln!("Failed to dump {} - {err}", file.display());
+            exit(1)
+        }
+    }
+}
+
+/// Imports get resolved relative to the file the source got read from
+pub(crate) fn dump(stage: Stage, file: &Path, source: &str, format: Format) -> Result<String> {
+    // unsupported constructs panic, they get reported like any other error
+    catch_silently(|| dump_stage(stage, file, source, format))
+        .unwrap_or_else(|panic| Err(Error::Unsupported(panic)))
+}
+
+fn dump_stage(stage: Stage, file: &Path, source: &str, format: Format) -> Result<String> {
+    let mut ctx = Context::new();
+
+    let nodes = match stage {
+        Stage::Tokens => {
+            let tokens = lex(&mut ctx, source)?;
+            token::dump(&ctx.string_table, &tokens)
+        }
+        Stage::Parse => {
+            let tokens = lex(&mut ctx, source)?;
+            let nodes = parse(&mut ctx, tokens)?;
+            parse_node::dump(&ctx.string_table, &nodes)
+        }
+        Stage::Ast => {
+            let ast = ast_from_source(&mut ctx, file, source)?;
+            ast::dump(&ctx.string_table, &ast)
+        }
+        Stage::Ir => {
+            let ast = ast_from_source(&mut ctx, file, source)?;
+            let mut ctx = ir::Context::new(ctx);
+            let ir = ir::ir_from_ast(&mut ctx, ast)?;
+            ir_node::dump(&ctx.string_table, &ir)
+        }
+        Stage::C => {
+            let code = c_code(ctx, file, source)?;
+            return Ok(match format {
+                Format::Text => code,
+                Format::Json => json!({ "code": code }).to_string(),
+            });
+        }
+    };
+
+    let lines = Lines::new(source);
+    Ok(match format {
+        Format::Text => text(&lines, &nodes),
+        Format::Json => serde_json::to_string_pretty(&Value::Array(
+            nodes.iter().map(|node| node.json(&lines)).collect(),
+        ))
+        .unwrap(),
+    })
+}
+
+fn c_code(mut ctx: Context, file: &Path, source: &str) -> Result<String> {
+    // generated code relies on the core library, like it does for build
+    let core = ast_from_file(&mut ctx, &crate::library_file_path("core/index.ec"))?;
+    let intrinsics = Intrinsics::new(&mut ctx, true);
+    run(
+        &mut ctx,
+        Scope::new(HashMap::new(), HashMap::new()),
+        TypeDefinitions {
+            definitions: Default::default(),
+        },
+        core,
+        &intrinsics,
+    )
+    .unwrap();
+
+    let ast = ast_from_source(&mut ctx, file, source)?;
+    Ok(generate_c_code(ctx, ast)?)
+}
+
+/// Stage independent representation of a node, which can be rendered as text or json
+#[derive(Debug)]
+pub(crate) struct Node {
+    kind: String,
+    attributes: Vec<(&'static str, Value)>,
+    span: Option<Span>,
+    children: Vec<(&'static str, Children)>,
+}
+
+#[derive(Debug)]
+enum Children {
+    One(Node),
+    Many(Vec<Node>),
+}
+
+impl Node {
+    pub(crate) fn new(kind: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            attributes: vec![],
+            span: None,
+            children: vec![],
+        }
+    }
+
+    pub(crate) fn span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    pub(crate) fn attribute(mut self, name: &'static str, value: impl Into<Value>) -> Self {
+        self.attributes.push((name, value.into()));
+        self
+    }
+
+    pub(crate) fn child(mut self, role: &'static str, node: Node) -> Self {
+        self.children.push((role, Children::One(node)));
+        self
+    }
+
+    pub(crate) fn optional(self, role: &'static str, node: Option<Node>) -> Self {
+        match node {
+            Some(node) => self.child(role, node),
+            None => self,
+        }
+    }
+
+    pub(crate) fn children(mut self, role: &'static str, nodes: Vec<Node>) -> Self {
+        self.children.push((role, Children::Many(nodes)));
+        self
+    }
+
+    fn header(&self, lines: &Lines) -> String {
+        let mut result = self.kind.clone();
+        for (name, value) in &self.attributes {
+            result.push_str(&format!(" {name}={value}"));
+        }
+        if let Some(span) = &self.span {
+            result.push_str(&format!(" {}", lines.span(span)));
+        }
+        result
+    }
+
+    fn write(&self, lines: &Lines, indent: usize, label: &str, result: &mut String) {
+        result.push_str(&format!(
+            "{}{label}{}\n",
+            " ".repeat(indent),
+            self.header(lines)
+        ));
+
+        let indent = indent + 2;
+        for (role, children) in &self.children {
+            match children {
+                Children::One(node) => node.write(lines, indent, &format!("{role}: "), result),
+                Children::Many(nodes) if nodes.is_empty() => {
+                    result.push_str(&format!("{}{role}: []\n", " ".repeat(indent)));
+                }
+                Children::Many(nodes) => {
+                    result.push_str(&format!("{}{role}:\n", " ".repeat(indent)));
+                    for node in nodes {
+                        node.write(lines, indent + 2, "- ", result);
+                    }
+                }
+            }
+        }
+    }
+
+    fn json(&self, lines: &Lines) -> Value {
+        let mut result = Map::new();
+        result.insert("kind".to_string(), Value::String(self.kind.clone()));
+        for (name, value) in &self.attributes {
+            result.insert(name.to_string(), value.clone());
+        }
+        if let Some(span) = &self.span {
+            result.insert("span".to_string(), lines.span_json(span));
+        }
+        for (role, children) in &self.children {
+            let value = match children {
+                Children::One(node) => node.json(lines),
+                Children::Many(nodes) => nodes.iter().map(|node| node.json(lines)).collect(),
+            };
+            result.insert(role.to_string(), value);
+        }
+        Value::Object(result)
+    }
+}
+
+fn text(lines: &Lines, nodes: &[Node]) -> String {
+    let mut result = String::new();
+    for node in nodes {
+        node.write(lines, 0, "", &mut result);
+    }
+    result.trim_end().to_string()
+}
+
+/// Translates character indexes of spans into 1-based line and column numbers of the source
+struct Lines {
+    starts: Vec<usize>,
+}
+
+impl Lines {
+    fn new(source: &str) -> Self {
+        let mut starts = vec![0];
+        for (idx, c) in source.chars().enumerate() {
+            if c == '\n' {
+                starts.push(idx + 1);
+            }
+        }
+        Self { starts }
+    }
+
+    fn position(&self, index: usize) -> (usize, usize) {
+        let line = self.starts.partition_point(|start| *start <= index) - 1;
+        (line + 1, index - self.starts[line] + 1)
+    }
+
+    fn span(&self, span: &Span) -> String {
+        let (start_line, start_column) = self.position(span.start.index.0);
+        let (end_line, end_column) = self.position(span.end.index.0);
+        format!("{start_line}:{start_column}-{end_line}:{end_column}")
+    }
+
+    fn span_json(&self, span: &Span) -> Value {
+        let position = |index: usize| {
+            let (line, column) = self.position(index);
+            json!({ "line": line, "column": column, "index": index })
+        };
+        json!({
+            "start": position(span.start.index.0),
+            "end": position(span.end.index.0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use serde_json::json;
+
+    use crate::dump::{dump, Error, Format, Stage};
+
+    #[test]
+    fn dump_tokens() {
+        let result = dump(
+            Stage::Tokens,
+            Path::new("main.ec"),
+            "let a = 1\n",
+            Format::Text,
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            r#"Keyword(Let) value="let" 1:1-1:4
+Identifier value="a" 1:5-1:6
+Operator(Equal) value="=" 1:7-1:8
+Literal(Number) value="1" 1:9-1:10
+Separator(NewLine) value="\n" 1:10-2:1
+EOF value="" 2:1-2:1"#
+        );
+    }
+
+    #[test]
+    fn dump_parse_tree() {
+        let result = dump(
+            Stage::Parse,
+            Path::new("main.ec"),
+            "let a: Number = 1 + 2",
+            Format::Text,
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            r#"VariableDeclaration name="a" 1:1-1:4
+  type: Type name="Number" 1:8-1:14
+  value: Infix operator="Add" 1:19-1:20
+    left: Literal number="1" 1:17-1:18
+    right: Literal number="2" 1:21-1:22"#
+        );
+    }
+
+    #[test]
+    fn dump_ast() {
+        let result = dump(
+            Stage::Ast,
+            Path::new("main.ec"),
+            "std::io::println('Elodie')",
+            Format::Text,
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            r#"CallFunctionOfPackage package="std::io" function="println" 1:1-1:4
+  arguments:
+    - LiteralString value="Elodie" 1:18-1:26"#
+        );
+    }
+
+    #[test]
+    fn dump_ast_as_json() {
+        let result = dump(
+            Stage::Ast,
+            Path::new("main.ec"),
+            "if true { break }",
+            Format::Json,
+        )
+        .unwrap();
+        let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(result[0]["kind"], json!("If"));
+        assert_eq!(
+            result[0]["span"],
+            json!({
+                "start": {"line": 1, "column": 1, "index": 0},
+                "end": {"line": 1, "column": 3, "index": 2},
+            })
+        );
+        assert_eq!(result[0]["condition"]["value"], json!(true));
+        assert_eq!(result[0]["then"]["nodes"][0]["kind"], json!("BreakLoop"));
+    }
+
+    #[test]
+    fn dump_ir() {
+        let result = dump(Stage::Ir, Path::new("main.ec"), "42", Format::Text).unwrap();
+        assert_eq!(result, r#"LiteralNumber value="42" type=1 1:1-1:3"#);
+    }
+
+    #[test]
+    fn dump_c() {
+        let result = dump(
+            Stage::C,
+            Path::new("main.ec"),
+            "function main() {\n}",
+            Format::Text,
+        )
+        .unwrap();
+        assert!(result.contains(
+            "int main(int argc, char ** argv){\ncore_intrinsics_process_init(argc, argv);\n}"
+        ));
+
+        let result = dump(
+            Stage::C,
+            Path::new("main.ec"),
+            "function main() {\n}",
+            Format::Json,
+        )
+        .unwrap();
+        let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert!(result["code"]
+            .as_str()
+            .unwrap()
+            .contains("int main(int argc, char ** argv)"));
+    }
+
+    #[test]
+    fn report_errors() {
+        let Err(Error::Frontend(_)) = dump(
+            Stage::Parse,
+            Path::new("main.ec"),
+            "function 1()",
+            Format::Text,
+        ) else {
+            panic!()
+        };
+
+        let Err(err @ Error::Ir(_)) =
+            dump(Stage::Ir, Path::new("main.ec"), "'text' + 1", Format::Text)
+        else {
+            panic!()
+        };
+        assert_eq!(
+            err.to_string(),
+            "Calculate is not supported by the analysis yet"
+        );
+
+        let Err(err @ Error::Generate(_)) = dump(
+            Stage::C,
+            Path::new("main.ec"),
+            "loop { break }",
+            Format::Text,
+        ) else {
+            panic!()
+        };
+        assert_eq!(err.to_string(), "Loop is not supported by c generation yet");
+    }
+}
@@ -0,0 +1,241 @@
+use crate::common::StringTable;
+use crate::dump::Node;
+use crate::frontend::parse::{
+    BlockNode, FromNode, FunctionDeclarationArgumentNode, InfixOperator, LiteralNode,
+    Node as ParseNode, PrefixOperator, TypeNode,
+};
+
+pub(crate) fn dump(strings: &StringTable, nodes: &[ParseNode]) -> Vec<Node> {
+    nodes.iter().map(|node| dump_node(strings, node)).collect()
+}
+
+fn dump_node(strings: &StringTable, node: &ParseNode) -> Node {
+    let result = match node {
+        ParseNode::Block(node) => return dump_block(strings, node),
+        ParseNode::Break(node) => Node::new("Break").optional(
+            "result",
+            node.result.as_ref().map(|node| dump_node(strings, node)),
+        ),
+        ParseNode::Call(node) => Node::new("Call")
+            .child("callee", dump_node(strings, &node.callee))
+            .children(
+                "arguments",
+                node.arguments
+                    .iter()
+                    .map(|argument| {
+                        let result = Node::new("Argument");
+                        let result = match &argument.identifier {
+                            Some(identifier) => {
+                                result.attribute("name", strings.get(identifier.value()))
+                            }
+                            None => result,
+                        };
+                        result.child("value", dump_node(strings, &argument.node))
+                    })
+                    .collect(),
+            ),
+        ParseNode::Continue(_) => Node::new("Continue"),
+        ParseNode::From(FromNode::Export(node)) => Node::new("FromExport")
+            .child("from", dump_node(strings, &node.from_node))
+            .child("what", dump_node(strings, &node.what_node)),
+        ParseNode::From(FromNode::Import(node)) => Node::new("FromImport")
+            .child("from", dump_node(strings, &node.from_node))
+            .children(
+                "what",
+                node.what_nodes
+                    .iter()
+                    .map(|node| {
+                        Node::new("Identifier")
+                            .attribute("name", strings.get(node.value()))
+                            .span(node.0.span.clone())
+                    })
+                    .collect(),
+            ),
+        ParseNode::ExternalFunctionDeclaration(node) => Node::new("ExternalFunctionDeclaration")
+            .attribute("name", strings.get(node.identifier.value()))
+            .attribute("exported", node.modifiers.is_exported())
+            .children("arguments", dump_arguments(strings, &node.arguments))
+            .optional(
+                "return_type",
+                node.return_type
+                    .as_ref()
+                    .map(|node| dump_type(strings, node)),
+            ),
+        ParseNode::FunctionDeclaration(node) => Node::new("FunctionDeclaration")
+            .attribute("name", strings.get(node.identifier.value()))
+            .attribute("exported", node.modifiers.is_exported())
+            .children("arguments", dump_arguments(strings, &node.arguments))
+            .optional(
+                "return_type",
+                node.return_type
+                    .as_ref()
+                    .map(|node| dump_type(strings, node)),
+            )
+            .child("block", dump_block(strings, &node.block)),
+        ParseNode::DefineDeclaration(node) => Node::new("DefineDeclaration")
+            .attribute("name", strings.get(node.identifier.value()))
+            .attribute("exported", node.modifiers.is_exported())
+            .child("block", dump_block(strings, &node.block)),
+        ParseNode::Identifier(node) => {
+            Node::new("Identifier").attribute("name", strings.get(node.value()))
+        }
+        ParseNode::If(node) => Node::new("If")
+            .child("condition", dump_node(strings, &node.condition))
+            .child("then", dump_block(strings, &node.then))
+            .optional(
+                "otherwise",
+                node.otherwise
+                    .as_ref()
+                    .map(|otherwise| dump_block(strings, &otherwise.block)),
+            ),
+        // infix nodes start with their left operand, the operator locates them better
+        ParseNode::Import(node) => {
+            Node::new("Import").attribute("source", strings.get(node.source.value()))
+        }
+        ParseNode::Infix(node) => {
+            return Node::new("Infix")
+                .attribute("operator", infix_operator(&node.operator))
+                .span(node.operator.token().span)
+                .child("left", dump_node(strings, &node.left))
+                .child("right", dump_node(strings, &node.right))
+        }
+        ParseNode::Itself(_) => Node::new("Itself"),
+        ParseNode::Literal(LiteralNode::Number(node)) => {
+            Node::new("Literal").attribute("number", strings.get(node.value()))
+        }
+        ParseNode::Literal(LiteralNode::String(node)) => {
+            Node::new("Literal").attribute("string", strings.get(node.value()))
+        }
+        ParseNode::Literal(LiteralNode::Boolean(node)) => {
+            Node::new("Literal").attribute("boolean", node.value())
+        }
+        ParseNode::Loop(node) => Node::new("Loop").child("block", dump_block(strings, &node.block)),
+        ParseNode::Nop => return Node::new("Nop"),
+        ParseNode::PackageDeclaration(node) => Node::new("PackageDeclaration")
+            .attribute("name", strings.get(node.identifier.value()))
+            .attribute("exported", node.modifiers.is_exported())
+            .child("block", dump_block(strings, &node.block)),
+        ParseNode::Prefix(node) => Node::new("Prefix")
+            .attribute(
+                "operator",
+                match node.operator {
+                    PrefixOperator::Plus(_) => "Plus",
+                    PrefixOperator::Negate(_) => "Negate",
+                    PrefixOperator::Not(_) => "Not",
+                },
+            )
+            .child("node", dump_node(strings, &node.node)),
+        ParseNode::Return(node) => Node::new("Return").optional(
+            "result",
+            node.result.as_ref().map(|node| dump_node(strings, node)),
+        ),
+        ParseNode::StringInterpolation(node) => {
+            Node::new("StringInterpolation").children("nodes", dump(strings, &node.nodes))
+        }
+        ParseNode::Tuple(node) => Node::new("Tuple").children("nodes", dump(strings, &node.nodes)),
+        ParseNode::Type(node) => return dump_type(strings, node),
+        ParseNode::TypeDeclaration(node) => Node::new("TypeDeclaration")
+            .attribute("name", strings.get(node.identifier.value()))
+            .attribute("exported", node.modifiers.is_exported())
+            .children("properties", dump(strings, &node.properties.nodes)),
+        ParseNode::VariableDeclaration(node) => Node::new("VariableDeclaration")
+            .attribute("name", strings.get(node.identifier.value()))
+            .optional(
+                "type",
+                node.r#type.as_ref().map(|node| dump_type(strings, node)),
+            )
+            .child("value", dump_node(strings, &node.node)),
+    };
+    result.span(node.token().span)
+}
+
+fn dump_block(strings: &StringTable, node: &BlockNode) -> Node {
+    Node::new("Block")
+        .span(node.token.span.clone())
+        .children("nodes", dump(strings, &node.nodes))
+}
+
+fn dump_arguments(
+    strings: &StringTable,
+    arguments: &[FunctionDeclarationArgumentNode],
+) -> Vec<Node> {
+    arguments
+        .iter()
+        .map(|argument| {
+            Node::new("Argument")
+                .attribute("name", strings.get(argument.identifier.value()))
+                .span(argument.identifier.0.span.clone())
+                .optional(
+                    "type",
+                    argument
+                        .r#type
+                        .as_ref()
+                        .map(|node| dump_type(strings, node)),
+                )
+        })
+        .collect()
+}
+
+fn dump_type(strings: &StringTable, node: &TypeNode) -> Node {
+    match node {
+        TypeNode::Boolean(token) => Node::new("Type")
+            .attribute("name", "Bool")
+            .span(token.span.clone()),
+        TypeNode::Number(token) => Node::new("Type")
+            .attribute("name", "Number")
+            .span(token.span.clone()),
+        TypeNode::String(token) => Node::new("Type")
+            .attribute("name", "String")
+            .span(token.span.clone()),
+        TypeNode::Object(node) => Node::new("Type")
+            .attribute("name", strings.get(node.token.value))
+            .span(node.token.span.clone()),
+        TypeNode::Function(node) => Node::new("FunctionType")
+            .span(node.token.span.clone())
+            .children(
+                "arguments",
+                node.arguments
+                    .iter()
+                    .map(|argument| {
+                        let result = Node::new("Argument");
+                        let result = match &argument.identifier {
+                            Some(identifier) => {
+                                result.attribute("name", strings.get(identifier.value()))
+                            }
+                            None => result,
+                        };
+                        result.child("type", dump_type(strings, &argument.r#type))
+                    })
+                    .collect(),
+            )
+            .optional(
+                "return_type",
+                node.return_type
+                    .as_ref()
+                    .map(|node| dump_type(strings, node)),
+            ),
+    }
+}
+
+fn infix_operator(operator: &InfixOperator) -> &'static str {
+    match operator {
+        InfixOperator::Add(_) => "Add",
+        InfixOperator::Arrow(_) => "Arrow",
+        InfixOperator::AccessPackage(_) => "AccessPackage",
+        InfixOperator::AccessProperty(_) => "AccessProperty",
+        InfixOperator::Assign(_) => "Assign",
+        InfixOperator::Call(_) => "Call",
+        InfixOperator::Subtract(_) => "Subtract",
+        InfixOperator::Multiply(_) => "Multiply",
+        InfixOperator::Divide(_) => "Divide",
+        InfixOperator::Modulo(_) => "Modulo",
+        InfixOperator::Equal(_) => "Equal",
+        InfixOperator::NotEqual(_) => "NotEqual",
+        InfixOperator::LambdaCall(_) => "LambdaCall",
+        InfixOperator::LessThan(_) => "LessThan",
+        InfixOperator::LessThanOrEqual(_) => "LessThanOrEqual",
+        InfixOperator::GreaterThan(_) => "GreaterThan",
+        InfixOperator::GreaterThanOrEqual(_) => "GreaterThanOrEqual",
+        InfixOperator::TypeAscription(_) => "TypeAscription",
+    }
+}
@@ -0,0 +1,14 @@
+use crate::common::StringTable;
+use crate::dump::Node;
+use crate::frontend::lex::token::Token;
+
+pub(crate) fn dump(strings: &StringTable, tokens: &[Token]) -> Vec<Node> {
+    tokens
+        .iter()
+        .map(|token| {
+            Node::new(format!("{:?}", token.kind))
+                .attribute("value", strings.get(token.value))
+                .span(token.span.clone())
+        })
+        .collect()
+}
@@ -19,11 +19,16 @@ impl<'a> Generator<'a> {
             arguments.push(self.generate_declare_function_argument(arg)?)
         }
 
+        let return_type = node
+            .return_type
+            .as_deref()
+            .map(|type_node| self.to_ast_type(type_node));
+
         Ok(AstTreeNode::new(
             Node::DeclareExternalFunction(AstDeclareExternalFunctionNode {
                 function: AstIdentifier(node.identifier.value()),
                 arguments,
-                return_type: None,
+                return_type,
             }),
             SPAN_NOT_IMPLEMENTED.clone(),
         ))
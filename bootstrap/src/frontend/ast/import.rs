@@ -0,0 +1,471 @@
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+use crate::common::node::Node;
+use crate::common::StringTableId;
+use crate::frontend::ast::{
+    AstBlockNode, AstDeclareFunctionNode, AstDeclarePackageNode, AstTreeNode, Error, Generator,
+};
+use crate::frontend::lex::lex;
+use crate::frontend::parse::parse as parse_tokens;
+use crate::frontend::parse::LiteralNode;
+use crate::frontend::{ast, parse, Ast};
+
+impl<'a> Generator<'a> {
+    /// Every declaration of the imported file
+    pub(crate) fn generate_import(
+        &mut self,
+        node: &parse::ImportNode,
+    ) -> ast::Result<Vec<AstTreeNode>> {
+        let source = self.ctx.get_str(node.source.value()).to_string();
+        let path = self.ctx.modules.resolve(&source)?;
+        let ast = self.import_file(path.clone())?;
+
+        self.declarations(path, ast)
+    }
+
+    /// Only the named declarations of the imported file, together with the declarations they use,
+    /// as functions are looked up by name when called
+    pub(crate) fn generate_from_import(
+        &mut self,
+        node: &parse::FromImportNode,
+    ) -> ast::Result<Vec<AstTreeNode>> {
+        let parse::Node::Literal(LiteralNode::String(from)) = node.from_node.deref() else {
+            return Err(Error::InvalidImportSource(node.from_node.token().span));
+        };
+        let source = self.ctx.get_str(from.value()).to_string();
+
+        let path = self.ctx.modules.resolve(&source)?;
+        let ast = self.import_file(path.clone())?;
+        let declarations = self.declarations(path.clone(), ast)?;
+
+        let mut names: Vec<StringTableId> = vec![];
+        for item in &node.what_nodes {
+            let name = item.value();
+            if !declarations
+                .iter()
+                .any(|declaration| declared_name(declaration) == Some(name))
+            {
+                return Err(Error::UnknownImportItem {
+                    item: self.ctx.get_str(name).to_string(),
+                    path,
+                });
+            }
+            names.push(name);
+        }
+
+        let mut idx = 0;
+        while idx < names.len() {
+            for declaration in &declarations {
+                if declared_name(declaration) == Some(names[idx]) {
+                    for used in used_names(declaration) {
+                        if !names.contains(&used) {
+                            names.push(used);
+                        }
+                    }
+                }
+            }
+            idx += 1;
+        }
+
+        Ok(declarations
+            .into_iter()
+            .filter(|declaration| {
+                declared_name(declaration).is_some_and(|name| names.contains(&name))
+            })
+            .collect())
+    }
+
+    /// Statements of an imported file do not run, its variables can not be imported as they would
+    /// be left without value
+    fn declarations(&self, path: PathBuf, ast: Ast) -> ast::Result<Vec<AstTreeNode>> {
+        for node in &ast.nodes {
+            if let Node::DeclareVariable(node) = node.node() {
+                return Err(Error::ImportedVariable {
+                    variable: self.ctx.get_str(node.variable.0).to_string(),
+                    path,
+                });
+            }
+        }
+        Ok(ast.nodes.into_iter().filter(is_declaration).collect())
+    }
+
+    /// Loads a file once, later loads get served from the cache
+    pub(crate) fn load_file(&mut self, path: PathBuf) -> crate::frontend::Result<Ast> {
+        if let Some(ast) = self.ctx.modules.get(&path) {
+            return Ok(ast.clone());
+        }
+
+        self.ctx.modules.enter(path.clone())?;
+        let result = self.generate_file(&path);
+        self.ctx.modules.leave();
+
+        let ast = result?;
+        self.ctx.modules.insert(path, ast.clone());
+        Ok(ast)
+    }
+
+    pub(crate) fn generate_source(&mut self, source: &str) -> crate::frontend::Result<Ast> {
+        let tokens = lex(self.ctx, source)?;
+        let nodes = parse_tokens(self.ctx, tokens)?;
+        Ok(self.generate(nodes)?)
+    }
+
+    fn generate_file(&mut self, path: &Path) -> crate::frontend::Result<Ast> {
+        let source = fs::read_to_string(path).map_err(|error| Error::ReadFailed {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        self.generate_source(&source)
+    }
+
+    // errors of the imported file are reported together with its path
+    pub(crate) fn import_file(&mut self, path: PathBuf) -> ast::Result<Ast> {
+        self.load_file(path.clone()).map_err(|error| match error {
+            crate::frontend::Error::Ast(error) => error,
+            error => Error::ImportFailed {
+                path,
+                error: Box::new(error),
+            },
+        })
+    }
+}
+
+fn is_declaration(node: &AstTreeNode) -> bool {
+    declared_name(node).is_some()
+}
+
+fn declared_name(node: &AstTreeNode) -> Option<StringTableId> {
+    match node.node() {
+        Node::DeclareExternalFunction(node) => Some(node.function.0),
+        Node::DeclareFunction(node) => Some(node.function.0),
+        Node::DeclarePackage(node) => Some(node.package.0),
+        Node::DeclareType(node) => Some(node.r#type.0),
+        Node::DefineType(node) => Some(node.r#type.0),
+        _ => None,
+    }
+}
+
+fn used_names(declaration: &AstTreeNode) -> Vec<StringTableId> {
+    let mut result = vec![];
+    match declaration.node() {
+        Node::DeclareFunction(node) => collect_function(node, &mut result),
+        Node::DeclarePackage(node) => collect_package(node, &mut result),
+        Node::DefineType(node) => {
+            for function in &node.functions {
+                collect_function(function, &mut result);
+            }
+        }
+        _ => {}
+    }
+    result
+}
+
+fn collect_package(node: &AstDeclarePackageNode, result: &mut Vec<StringTableId>) {
+    for function in &node.functions {
+        collect_function(function, result);
+    }
+    for package in &node.packages {
+        collect_package(package, result);
+    }
+    for definition in &node.definitions {
+        for function in &definition.functions {
+            collect_function(function, result);
+        }
+    }
+}
+
+fn collect_function(node: &AstDeclareFunctionNode, result: &mut Vec<StringTableId>) {
+    collect_block(&node.nodes, result);
+}
+
+fn collect_block(node: &AstBlockNode, result: &mut Vec<StringTableId>) {
+    for node in &node.nodes {
+        collect(node, result);
+    }
+}
+
+fn collect_all(nodes: &[AstTreeNode], result: &mut Vec<StringTableId>) {
+    for node in nodes {
+        collect(node, result);
+    }
+}
+
+fn collect(node: &AstTreeNode, result: &mut Vec<StringTableId>) {
+    match node.node() {
+        Node::AccessVariable(node) => result.push(node.variable.0),
+        Node::Block(node) => collect_block(node, result),
+        Node::BreakLoop(node) => {
+            if let Some(node) = &node.node {
+                collect(node, result);
+            }
+        }
+        Node::Calculate(node) => {
+            collect(&node.left, result);
+            collect(&node.right, result);
+        }
+        Node::CallFunction(node) => {
+            result.push(node.function.0);
+            collect_all(&node.arguments, result);
+        }
+        Node::CallFunctionWithLambda(node) => {
+            result.push(node.function.0);
+            collect_all(&node.arguments, result);
+            collect_block(&node.lambda, result);
+        }
+        Node::CallFunctionOfObject(node) => {
+            collect_all(&node.arguments, result);
+            if let Some(lambda) = &node.lambda {
+                collect_block(lambda, result);
+            }
+        }
+        Node::CallFunctionOfPackage(node) => {
+            if let Some(package) = node.package.first() {
+                result.push(package);
+            }
+            collect_all(&node.arguments, result);
+        }
+        Node::Compare(node) => {
+            collect(&node.left, result);
+            collect(&node.right, result);
+        }
+        Node::DeclareFunction(node) => collect_function(node, result),
+        Node::DeclareVariable(node) => collect(&node.value, result),
+        Node::If(node) => {
+            collect(&node.condition, result);
+            collect_block(&node.then, result);
+            if let Some(otherwise) = &node.otherwise {
+                collect_block(otherwise, result);
+            }
+        }
+        Node::InterpolateString(node) => collect_all(&node.nodes, result),
+        Node::InstantiateType(node) => {
+            result.push(node.r#type.0);
+            for argument in &node.arguments {
+                collect(&argument.value, result);
+            }
+        }
+        Node::Loop(node) => collect_all(&node.nodes, result),
+        Node::ReturnFromFunction(node) => {
+            if let Some(node) = &node.node {
+                collect(node, result);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::common::node::Node;
+    use crate::frontend::ast::Error;
+    use crate::frontend::context::Context;
+    use crate::frontend::module::Modules;
+    use crate::frontend::{ast_from_file, ast_from_source, Ast};
+
+    fn test_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (file, content) in files {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    fn context(search_path: Vec<PathBuf>) -> Context {
+        let mut result = Context::new();
+        result.modules = Modules::with_search_path(search_path);
+        result
+    }
+
+    fn declared(ctx: &Context, ast: &Ast) -> Vec<String> {
+        ast.nodes
+            .iter()
+            .map(|node| match node.node() {
+                Node::DeclareFunction(node) => ctx.get_str(node.function.0).to_string(),
+                Node::DeclarePackage(node) => ctx.get_str(node.package.0).to_string(),
+                Node::DeclareType(node) => ctx.get_str(node.r#type.0).to_string(),
+                Node::DefineType(node) => ctx.get_str(node.r#type.0).to_string(),
+                _ => "statement".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn import_declarations_of_file() {
+        let dir = test_dir(
+            "elodie_import_file",
+            &[
+                ("main.ec", "import './util/math'\nadd(1, 2)"),
+                (
+                    "util/math.ec",
+                    "function add(a: Number, b: Number) -> Number { return a + b }\nadd(3, 4)\npackage math {}",
+                ),
+            ],
+        );
+
+        let mut ctx = context(vec![]);
+        let ast = ast_from_file(&mut ctx, &dir.join("main.ec")).unwrap();
+        assert_eq!(declared(&ctx, &ast), vec!["add", "math", "statement"]);
+    }
+
+    #[test]
+    fn import_file_with_variable() {
+        let dir = test_dir(
+            "elodie_import_variable",
+            &[
+                ("main.ec", "import './config'"),
+                ("config.ec", "function name() {}\nlet version = 1"),
+            ],
+        );
+
+        let mut ctx = context(vec![]);
+        let Err(crate::frontend::Error::Ast(err)) = ast_from_file(&mut ctx, &dir.join("main.ec"))
+        else {
+            panic!()
+        };
+        let Error::ImportedVariable { ref variable, .. } = err else {
+            panic!()
+        };
+        assert_eq!(variable, "version");
+        assert!(err
+            .to_string()
+            .ends_with("config.ec declares variable version at the top level, only functions, packages and types can be imported"));
+    }
+
+    #[test]
+    fn import_items_with_their_dependencies() {
+        let dir = test_dir(
+            "elodie_import_items",
+            &[
+                ("main.ec", "from './shapes' import area, Point"),
+                (
+                    "shapes.ec",
+                    "function square(x: Number) -> Number { return x * x }\nfunction area(x: Number) -> Number { return square(x) }\nfunction unused() {}\ntype Point(x: Number)\ndefine Point {\n  function x() -> Number { return 1 }\n}",
+                ),
+            ],
+        );
+
+        let mut ctx = context(vec![]);
+        let ast = ast_from_file(&mut ctx, &dir.join("main.ec")).unwrap();
+        assert_eq!(
+            declared(&ctx, &ast),
+            vec!["square", "area", "Point", "Point"]
+        );
+    }
+
+    #[test]
+    fn import_unknown_item() {
+        let dir = test_dir(
+            "elodie_import_unknown_item",
+            &[("main.ec", "from './lib' import missing"), ("lib.ec", "")],
+        );
+
+        let mut ctx = context(vec![]);
+        let Err(crate::frontend::Error::Ast(Error::UnknownImportItem { item, .. })) =
+            ast_from_file(&mut ctx, &dir.join("main.ec"))
+        else {
+            panic!()
+        };
+        assert_eq!(item, "missing");
+    }
+
+    #[test]
+    fn import_from_search_path() {
+        let lib = test_dir(
+            "elodie_import_search_path",
+            &[("text/index.ec", "function shout() {}")],
+        );
+
+        let mut ctx = context(vec![lib]);
+        let ast = ast_from_source(&mut ctx, &PathBuf::from("/main.ec"), "import 'text'").unwrap();
+        assert_eq!(declared(&ctx, &ast), vec!["shout"]);
+    }
+
+    #[test]
+    fn import_cycle() {
+        let dir = test_dir(
+            "elodie_import_cycle",
+            &[
+                ("a.ec", "import './b'"),
+                ("b.ec", "import './c'"),
+                ("c.ec", "import './a'"),
+            ],
+        );
+
+        let mut ctx = context(vec![]);
+        let Err(crate::frontend::Error::Ast(err)) = ast_from_file(&mut ctx, &dir.join("a.ec"))
+        else {
+            panic!()
+        };
+        let Error::ImportCycle(ref files) = err else {
+            panic!()
+        };
+        let names: Vec<_> = files
+            .iter()
+            .map(|file| file.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["a.ec", "b.ec", "c.ec", "a.ec"]);
+        assert!(err.to_string().starts_with("Import cycle detected: "));
+    }
+
+    #[test]
+    fn parses_file_once() {
+        let dir = test_dir(
+            "elodie_import_once",
+            &[
+                ("main.ec", "import './a'\nimport './b'"),
+                ("a.ec", "import './shared'\nfunction a() {}"),
+                ("b.ec", "import './shared'\nfunction b() {}"),
+                ("shared.ec", "function shared() {}"),
+            ],
+        );
+
+        let mut ctx = context(vec![]);
+        let ast = ast_from_file(&mut ctx, &dir.join("main.ec")).unwrap();
+        assert_eq!(declared(&ctx, &ast), vec!["shared", "a", "shared", "b"]);
+
+        // changes are not picked up, as the file got loaded already
+        fs::write(dir.join("shared.ec"), "function changed() {}").unwrap();
+        let ast = ast_from_file(&mut ctx, &dir.join("b.ec")).unwrap();
+        assert_eq!(declared(&ctx, &ast), vec!["shared", "b"]);
+    }
+
+    #[test]
+    fn nested_import() {
+        let mut ctx = context(vec![]);
+        let Err(crate::frontend::Error::Ast(Error::NestedImport)) = ast_from_source(
+            &mut ctx,
+            &PathBuf::from("/main.ec"),
+            "function f() {\n import './a'\n}",
+        ) else {
+            panic!()
+        };
+    }
+
+    #[test]
+    fn import_from_identifier() {
+        let mut ctx = context(vec![]);
+        let Err(crate::frontend::Error::Ast(err)) =
+            ast_from_source(&mut ctx, &PathBuf::from("/main.ec"), "from math import x")
+        else {
+            panic!()
+        };
+        let Error::InvalidImportSource(ref span) = err else {
+            panic!()
+        };
+        assert_eq!(span.start, (1, 6, 5));
+        assert_eq!(span.end, (1, 10, 9));
+        assert_eq!(
+            err.to_string(),
+            "Imports need the file to import from as string, like from './math' import add"
+        );
+    }
+}
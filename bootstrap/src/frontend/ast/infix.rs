@@ -3,15 +3,16 @@ use std::rc::Rc;
 
 use crate::common::node::Node::{
     AccessVariableOfObject, AccessVariableOfSelf, Block, Calculate, CallFunction,
-    CallFunctionOfObject, CallFunctionOfPackage, CallFunctionWithLambda, Compare, InstantiateType,
+    CallFunctionOfObject, CallFunctionOfPackage, CallFunctionWithLambda, Compare, DeclareVariable,
+    InstantiateType,
 };
 use crate::common::node::{CalculationOperator, CompareOperator};
 use crate::common::PackagePath;
 use crate::frontend::ast::{
-    AStCallFunctionNode, AstAccessVariableOfObjectNode, AstAccessVariableOfSelfNode,
+    AStCallFunctionNode, AstAccessVariableOfObjectNode, AstAccessVariableOfSelfNode, AstBlockNode,
     AstCalculateNode, AstCallFunctionOfObjectNode, AstCallFunctionOfPackageNode,
-    AstCallFunctionWithLambdaNode, AstCompareNode, AstIdentifier, AstInstantiateTypeNode,
-    AstNamedArgument, AstTreeNode, Generator, SPAN_NOT_IMPLEMENTED,
+    AstCallFunctionWithLambdaNode, AstCompareNode, AstDeclareVariableNode, AstIdentifier,
+    AstInstantiateTypeNode, AstNamedArgument, AstTreeNode, Generator, SPAN_NOT_IMPLEMENTED,
 };
 use crate::frontend::parse::Node::Type;
 use crate::frontend::parse::{InfixNode, InfixOperator, Node, TypeNode};
@@ -45,16 +46,37 @@ impl<'a> Generator<'a> {
             ));
         }
 
+        // call function of the result of a call - expect(actual).to_equal(expected)
+        if left.is_infix()
+            && matches!(left.as_infix().operator, InfixOperator::AccessProperty(_))
+            && matches!(operator, InfixOperator::Call(_))
+            && left.as_infix().left.is_infix()
+            && matches!(
+                left.as_infix().left.as_infix().operator,
+                InfixOperator::Call(_)
+            )
+        {
+            return self.generate_call_function_of_result(left.as_infix(), right);
+        }
+
         // call function of object / self
         if left.is_infix()
             && matches!(left.as_infix().operator, InfixOperator::AccessProperty(_))
             && matches!(operator, InfixOperator::Call(_))
         {
-            let AccessVariableOfObject(AstAccessVariableOfObjectNode { object, variable }) = self
+            let (object, variable) = match self
                 .generate_access_variable(left.as_infix())?
                 .node_to_owned()
-            else {
-                panic!()
+            {
+                AccessVariableOfObject(AstAccessVariableOfObjectNode { object, variable }) => {
+                    (object, variable)
+                }
+                // self.other() calls another function of the same type
+                AccessVariableOfSelf(AstAccessVariableOfSelfNode { variable }) => (
+                    AstIdentifier(self.ctx.string_table.push_str("self")),
+                    variable,
+                ),
+                _ => panic!(),
             };
 
             let arguments = self.generate_arguments(right.as_tuple())?;
@@ -65,6 +87,7 @@ impl<'a> Generator<'a> {
                     object: ast::AstIdentifier(object.0.clone()),
                     function: ast::AstIdentifier(variable.0.clone()),
                     arguments,
+                    lambda: None,
                 }),
                 SPAN_NOT_IMPLEMENTED.clone(),
             ));
@@ -75,21 +98,29 @@ impl<'a> Generator<'a> {
             let left = self.generate_node(left.deref())?;
             let right = self.generate_node(right.deref())?;
 
-            let CallFunction(call_function) = left.node_to_owned() else {
-                panic!()
-            };
             let Block(lambda) = right.node_to_owned() else {
                 panic!()
             };
 
-            return Ok(AstTreeNode::new(
-                CallFunctionWithLambda(AstCallFunctionWithLambdaNode {
-                    function: call_function.function.clone(),
-                    lambda: Rc::new(lambda),
-                    arguments: call_function.arguments.clone(),
-                }),
-                SPAN_NOT_IMPLEMENTED.clone(),
-            ));
+            let node = match left.node_to_owned() {
+                CallFunction(call_function) => {
+                    CallFunctionWithLambda(AstCallFunctionWithLambdaNode {
+                        function: call_function.function.clone(),
+                        lambda: Rc::new(lambda),
+                        arguments: call_function.arguments.clone(),
+                    })
+                }
+                // list.map() { it * 2 }
+                CallFunctionOfObject(call_function) => {
+                    CallFunctionOfObject(AstCallFunctionOfObjectNode {
+                        lambda: Some(Rc::new(lambda)),
+                        ..call_function
+                    })
+                }
+                _ => panic!(),
+            };
+
+            return Ok(AstTreeNode::new(node, SPAN_NOT_IMPLEMENTED.clone()));
         }
 
         // call function of package
@@ -229,6 +260,44 @@ impl<'a> Generator<'a> {
         unimplemented!("{:#?}", node);
     }
 
+    /// Stores the result in a variable of a block of its own, as functions can only be called on
+    /// variables so far
+    fn generate_call_function_of_result(
+        &mut self,
+        left: &parse::InfixNode,
+        arguments: &Node,
+    ) -> ast::Result<AstTreeNode> {
+        let result = AstIdentifier(self.ctx.string_table.push_str("$result"));
+        let value = self.generate_node(left.left.deref())?;
+        let function = left.right.as_identifier();
+        let arguments = self.generate_arguments(arguments.as_tuple())?;
+
+        Ok(AstTreeNode::new(
+            Block(AstBlockNode {
+                nodes: vec![
+                    AstTreeNode::new(
+                        DeclareVariable(AstDeclareVariableNode {
+                            variable: result.clone(),
+                            value: Rc::new(value),
+                            value_type: None,
+                        }),
+                        SPAN_NOT_IMPLEMENTED.clone(),
+                    ),
+                    AstTreeNode::new(
+                        CallFunctionOfObject(AstCallFunctionOfObjectNode {
+                            object: result,
+                            function: AstIdentifier(function.value()),
+                            arguments,
+                            lambda: None,
+                        }),
+                        SPAN_NOT_IMPLEMENTED.clone(),
+                    ),
+                ],
+            }),
+            SPAN_NOT_IMPLEMENTED.clone(),
+        ))
+    }
+
     fn generate_access_variable(&mut self, node: &parse::InfixNode) -> ast::Result<AstTreeNode> {
         let InfixNode {
             left,
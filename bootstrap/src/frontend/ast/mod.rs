@@ -1,3 +1,8 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::common::{Span, WithSpan};
 pub use crate::frontend::ast::node::*;
 use crate::frontend::{parse, Ast, Context};
 
@@ -5,6 +10,7 @@ mod block;
 mod control;
 mod function;
 mod identifier;
+mod import;
 mod infix;
 mod literal;
 pub(crate) mod node;
@@ -14,7 +20,78 @@ mod r#type;
 mod variable;
 
 #[derive(Debug)]
-pub enum Error {}
+pub enum Error {
+    /// Files which import each other, starting and ending with the same file
+    ImportCycle(Vec<PathBuf>),
+    ImportFailed {
+        path: PathBuf,
+        error: Box<crate::frontend::Error>,
+    },
+    /// Variable declared at the top level of an imported file
+    ImportedVariable {
+        variable: String,
+        path: PathBuf,
+    },
+    /// Source of a from import which is not a string literal, like the identifier in from math import x
+    InvalidImportSource(Span),
+    NestedImport,
+    ReadFailed {
+        path: PathBuf,
+        error: io::Error,
+    },
+    UnknownImportItem {
+        item: String,
+        path: PathBuf,
+    },
+    UnresolvedImport {
+        source: String,
+        searched: Vec<PathBuf>,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ImportCycle(files) => {
+                let files: Vec<String> = files
+                    .iter()
+                    .map(|file| file.display().to_string())
+                    .collect();
+                write!(f, "Import cycle detected: {}", files.join(" -> "))
+            }
+            Error::ImportFailed { path, error } => {
+                write!(f, "Failed to import {} - {error}", path.display())
+            }
+            Error::ImportedVariable { variable, path } => write!(
+                f,
+                "{} declares variable {variable} at the top level, only functions, packages and types can be imported",
+                path.display()
+            ),
+            Error::InvalidImportSource(_) => write!(
+                f,
+                "Imports need the file to import from as string, like from './math' import add"
+            ),
+            Error::NestedImport => write!(f, "Imports are only allowed at the top level of a file"),
+            Error::ReadFailed { path, error } => {
+                write!(f, "Failed to read {} - {error}", path.display())
+            }
+            Error::UnknownImportItem { item, path } => {
+                write!(f, "{} does not declare {item}", path.display())
+            }
+            Error::UnresolvedImport { source, searched } => {
+                let searched: Vec<String> = searched
+                    .iter()
+                    .map(|file| file.display().to_string())
+                    .collect();
+                write!(
+                    f,
+                    "Unable to resolve import '{source}', tried {}",
+                    searched.join(", ")
+                )
+            }
+        }
+    }
+}
 
 pub(crate) type Result<T, E = Error> = core::result::Result<T, E>;
 
@@ -23,6 +100,25 @@ pub(crate) fn from(ctx: &mut Context, nodes: Vec<parse::Node>) -> Result<Ast> {
     compiler.generate(nodes)
 }
 
+pub(crate) fn from_file(ctx: &mut Context, path: &Path) -> crate::frontend::Result<Ast> {
+    let path = path.canonicalize().unwrap_or(path.to_path_buf());
+    let mut compiler = Generator::new(ctx);
+    compiler.load_file(path)
+}
+
+pub(crate) fn from_source(
+    ctx: &mut Context,
+    path: &Path,
+    source: &str,
+) -> crate::frontend::Result<Ast> {
+    let path = path.canonicalize().unwrap_or(path.to_path_buf());
+    let mut compiler = Generator::new(ctx);
+    compiler.ctx.modules.enter(path)?;
+    let result = compiler.generate_source(source);
+    compiler.ctx.modules.leave();
+    result
+}
+
 pub(crate) struct Generator<'a> {
     ctx: &'a mut Context,
 }
@@ -37,8 +133,14 @@ impl<'a> Generator<'a> {
     pub(crate) fn generate(&mut self, nodes: Vec<parse::Node>) -> Result<Ast> {
         let mut result = Vec::new();
         for node in &nodes {
-            if !matches!(node, parse::Node::Nop) {
-                result.push(self.generate_node(node)?);
+            match node {
+                parse::Node::Nop => {}
+                // imports declare what they import in the scope of the importing file
+                parse::Node::Import(node) => result.extend(self.generate_import(node)?),
+                parse::Node::From(parse::FromNode::Import(node)) => {
+                    result.extend(self.generate_from_import(node)?)
+                }
+                node => result.push(self.generate_node(node)?),
             }
         }
 
@@ -46,6 +148,15 @@ impl<'a> Generator<'a> {
     }
 
     pub(crate) fn generate_node(&mut self, node: &parse::Node) -> Result<AstTreeNode> {
+        let result = self.generate_node_without_span(node)?;
+        // most nodes do not track their span yet, use the span of the token they start with
+        if result.span() == SPAN_NOT_IMPLEMENTED {
+            return Ok(AstTreeNode::new(result.node_to_owned(), node.token().span));
+        }
+        Ok(result)
+    }
+
+    fn generate_node_without_span(&mut self, node: &parse::Node) -> Result<AstTreeNode> {
         match node {
             parse::Node::Block(block_node) => Ok(self.generate_block(block_node)?),
             parse::Node::Break(break_node) => Ok(self.generate_break(break_node)?),
@@ -65,6 +176,7 @@ impl<'a> Generator<'a> {
                 Ok(self.generate_identifier(identifier_node)?)
             }
             parse::Node::If(if_node) => Ok(self.generate_if(if_node)?),
+            parse::Node::Import(_) => Err(Error::NestedImport),
             parse::Node::Infix(infix_node) => Ok(self.generate_infix(infix_node)?),
             parse::Node::StringInterpolation(node) => self.generate_interpolate_string(node),
             parse::Node::Itself(node) => Ok(self.generate_self(node)?),
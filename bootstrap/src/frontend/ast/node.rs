@@ -68,6 +68,40 @@ impl AstTreeNode {
     pub fn node_to_owned(self) -> AstNode {
         self.node
     }
+
+    /// Name of the node, to report constructs which are not supported yet
+    pub fn kind(&self) -> &'static str {
+        match &self.node {
+            AstNode::AccessVariable(_) => "AccessVariable",
+            AstNode::AccessVariableOfObject(_) => "AccessVariableOfObject",
+            AstNode::AccessVariableOfSelf(_) => "AccessVariableOfSelf",
+            AstNode::Block(_) => "Block",
+            AstNode::BreakLoop(_) => "BreakLoop",
+            AstNode::Calculate(_) => "Calculate",
+            AstNode::CallFunction(_) => "CallFunction",
+            AstNode::CallFunctionWithLambda(_) => "CallFunctionWithLambda",
+            AstNode::CallFunctionOfObject(_) => "CallFunctionOfObject",
+            AstNode::CallFunctionOfPackage(_) => "CallFunctionOfPackage",
+            AstNode::Compare(_) => "Compare",
+            AstNode::ContinueLoop(_) => "ContinueLoop",
+            AstNode::DeclareExternalFunction(_) => "DeclareExternalFunction",
+            AstNode::DeclareFunction(_) => "DeclareFunction",
+            AstNode::DeclarePackage(_) => "DeclarePackage",
+            AstNode::DeclareType(_) => "DeclareType",
+            AstNode::DeclareVariable(_) => "DeclareVariable",
+            AstNode::DefineType(_) => "DefineType",
+            AstNode::ExportPackage(_) => "ExportPackage",
+            AstNode::If(_) => "If",
+            AstNode::InterpolateString(_) => "InterpolateString",
+            AstNode::InstantiateType(_) => "InstantiateType",
+            AstNode::LiteralBoolean(_) => "LiteralBoolean",
+            AstNode::LiteralNumber(_) => "LiteralNumber",
+            AstNode::LiteralString(_) => "LiteralString",
+            AstNode::Loop(_) => "Loop",
+            AstNode::ReturnFromFunction(_) => "ReturnFromFunction",
+            AstNode::Marker(_) => unreachable!(),
+        }
+    }
 }
 
 impl AstTreeNode {
@@ -87,11 +121,13 @@ pub static SPAN_NOT_IMPLEMENTED: Span = Span {
         row: Row(0),
         column: Column(0),
         index: Index(0),
+        file: None,
     },
     end: Position {
         row: Row(0),
         column: Column(0),
         index: Index(0),
+        file: None,
     },
 };
 
@@ -162,6 +198,8 @@ pub struct AstCallFunctionOfObjectNode {
     pub object: AstIdentifier,
     pub function: AstIdentifier,
     pub arguments: Vec<AstTreeNode>,
+    /// Trailing lambda, it gets passed as last argument
+    pub lambda: Option<Rc<AstBlockNode>>,
 }
 
 impl CallFunctionOfObjectNode<AstVariant> for AstCallFunctionOfObjectNode {}
@@ -330,11 +368,18 @@ pub enum AstType {
     Number,
     String,
     Function {
-        arguments: Vec<Box<AstType>>,
+        arguments: Vec<AstTypeFunctionArgument>,
         return_type: Option<Box<AstType>>,
     },
 }
 
+/// Argument of a function type, lambdas passed for it get the named ones as parameters
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstTypeFunctionArgument {
+    pub identifier: Option<AstIdentifier>,
+    pub r#type: Box<AstType>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TypeVariable {
     pub variable: AstIdentifier,
@@ -1,8 +1,4 @@
-use std::fs::File;
-use std::io;
-use std::io::Read;
 use std::ops::Deref;
-use std::path::PathBuf;
 
 use crate::common::node::Node::{Block, ExportPackage};
 use crate::common::node::{Node, Source};
@@ -12,15 +8,14 @@ use crate::frontend::ast::{
     SPAN_NOT_IMPLEMENTED,
 };
 use crate::frontend::parse::LiteralNode;
-use crate::frontend::{ast, ast_from_str, parse};
+use crate::frontend::{ast, parse};
 
 impl<'a> Generator<'a> {
     pub(crate) fn generate_from(&mut self, node: &parse::FromNode) -> ast::Result<AstTreeNode> {
-        if let parse::FromNode::Export(export_node) = node {
-            return self.generate_from_export(export_node);
+        match node {
+            parse::FromNode::Export(export_node) => self.generate_from_export(export_node),
+            parse::FromNode::Import(_) => Err(ast::Error::NestedImport),
         }
-
-        unimplemented!();
     }
 
     pub(crate) fn generate_from_export(
@@ -75,29 +70,10 @@ impl<'a> Generator<'a> {
         for node in compiled_body.into_iter() {
             if let Block(block) = node.node() {
                 for node in &block.nodes {
-                    if let ExportPackage(AstExportPackageNode { package, .. }) = node.node() {
-                        let package = self.ctx.get_str(package.0).to_string();
-
-                        // FIXME temporary hack to load std packages
+                    if let ExportPackage(AstExportPackageNode { source, .. }) = node.node() {
                         // FIXME compiler needs to track scope so that the parent package can easily be determined
-
-                        match package.as_str() {
-                            "io" => packages.extend(self.load_declared_packages("std/io/index.ec")),
-                            "collection" => packages
-                                .extend(self.load_declared_packages("std/collection/index.ec")),
-                            "list" => packages.extend(
-                                self.load_declared_packages("std/collection/list/index.ec"),
-                            ),
-                            "math" => {
-                                packages.extend(self.load_declared_packages("std/math/index.ec"))
-                            }
-                            "process" => {
-                                packages.extend(self.load_declared_packages("std/process/index.ec"))
-                            }
-                            "intrinsics" => packages
-                                .extend(self.load_declared_packages("core/intrinsics/index.ec")),
-                            _ => unimplemented!(),
-                        }
+                        let Source::LocalFile { path } = source;
+                        packages.extend(self.load_declared_packages(path)?);
                     } else if let Node::DeclareFunction(declare_function) = node.node() {
                         functions.push(declare_function.clone())
                     } else if let Node::DefineType(define_type) = node.node() {
@@ -130,9 +106,9 @@ impl<'a> Generator<'a> {
         ))
     }
 
-    fn load_declared_packages(&mut self, name: &str) -> Vec<AstDeclarePackageNode> {
-        let content = crate::load_library_file(name).unwrap();
-        let ast = ast_from_str(&mut self.ctx, content.as_str()).unwrap();
+    fn load_declared_packages(&mut self, source: &str) -> ast::Result<Vec<AstDeclarePackageNode>> {
+        let path = self.ctx.modules.resolve(source)?;
+        let ast = self.import_file(path)?;
 
         let mut result = vec![];
 
@@ -141,22 +117,6 @@ impl<'a> Generator<'a> {
                 result.push(package_node.clone());
             }
         }
-        result
+        Ok(result)
     }
 }
-
-fn load_library_file(filename: &str) -> io::Result<String> {
-    // Get the path to the project root directory
-    let manifest_dir = "/home/ddymke/repo/elodie/src/lib/";
-
-    // Construct the full path to the file
-    let file_path = PathBuf::from(manifest_dir).join(filename);
-    // println!("{file_path:?}");
-
-    // Read the file's contents
-    let mut file = File::open(file_path)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-    // println!("{contents}");
-    Ok(contents)
-}
@@ -3,8 +3,8 @@ use std::ops::Deref;
 use crate::common::node::Node;
 use crate::common::node::Node::{DeclareType, DefineType};
 use crate::frontend::ast::{
-    AstDeclareTypeNode, AstDefineTypeNode, AstIdentifier, AstTreeNode, AstType, Generator,
-    TypeVariable, SPAN_NOT_IMPLEMENTED,
+    AstDeclareTypeNode, AstDefineTypeNode, AstIdentifier, AstTreeNode, AstType,
+    AstTypeFunctionArgument, Generator, TypeVariable, SPAN_NOT_IMPLEMENTED,
 };
 use crate::frontend::parse::{InfixNode, InfixOperator, TypeFunctionNode, TypeNode};
 use crate::frontend::{ast, parse};
@@ -57,7 +57,10 @@ impl<'a> Generator<'a> {
             }) => AstType::Function {
                 arguments: arguments
                     .iter()
-                    .map(|a| Box::new(self.to_ast_type(a.r#type.deref())))
+                    .map(|a| AstTypeFunctionArgument {
+                        identifier: a.identifier.as_ref().map(|i| AstIdentifier(i.value())),
+                        r#type: Box::new(self.to_ast_type(a.r#type.deref())),
+                    })
                     .collect::<Vec<_>>(),
                 return_type: return_type
                     .as_ref()
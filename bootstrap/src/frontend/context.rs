@@ -1,14 +1,17 @@
 use crate::common::{StringTable, StringTableId};
+use crate::frontend::module::Modules;
 
 #[derive(Debug)]
 pub struct Context {
     pub string_table: StringTable,
+    pub modules: Modules,
 }
 
 impl Context {
     pub fn new() -> Self {
         Self {
             string_table: StringTable::new(),
+            modules: Modules::new(),
         }
     }
 
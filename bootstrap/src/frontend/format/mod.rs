@@ -0,0 +1,543 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+use regex::Regex;
+
+use crate::frontend::context::Context;
+use crate::frontend::lex::lex;
+use crate::frontend::lex::token::OperatorToken::{
+    Bang, CloseBracket, CloseCurly, CloseParen, Colon, Dot, DoubleColon, DoubleDot, Minus,
+    OpenBracket, OpenCurly, OpenParen, Plus,
+};
+use crate::frontend::lex::token::SeparatorToken::{Comma, NewLine, Semicolon};
+use crate::frontend::lex::token::{KeywordToken, LiteralToken, Token, TokenKind};
+
+const INDENT: &str = "    ";
+const MAX_CONSECUTIVE_NEW_LINES: usize = 2;
+
+// The lexer drops whitespace and comments, but every token keeps its span. Everything between
+// two spans is therefore whitespace, optionally followed by a single comment, as a comment
+// always runs until the next new line token.
+pub fn format_str(ctx: &mut Context, str: &str) -> crate::frontend::Result<String> {
+    let tokens = lex(ctx, str)?;
+    Ok(Formatter::new(ctx, str).format(&tokens))
+}
+
+pub fn format_files(paths: Vec<PathBuf>, check: bool) {
+    let mut files = vec![];
+    for path in &paths {
+        collect_files(path, &mut files);
+    }
+
+    let mut unformatted = 0;
+    for file in &files {
+        let content = match fs::read_to_string(file) {
+            Ok(content) => content,
+            Err(err) => {
+                eprintln!("Failed to read {} - {err}", file.display());
+                exit(1)
+            }
+        };
+
+        let mut ctx = Context::new();
+        let formatted = match format_str(&mut ctx, content.as_str()) {
+            Ok(formatted) => formatted,
+            Err(err) => {
+                eprintln!("Failed to format {} - {:?}", file.display(), err);
+                exit(1)
+            }
+        };
+
+        if formatted == content {
+            continue;
+        }
+
+        if check {
+            println!("Not formatted - {}", file.display());
+            unformatted += 1;
+        } else {
+            if let Err(err) = fs::write(file, formatted) {
+                eprintln!("Failed to write {} - {err}", file.display());
+                exit(1)
+            }
+            println!("Formatted - {}", file.display());
+        }
+    }
+
+    if unformatted > 0 {
+        exit(1)
+    }
+}
+
+fn collect_files(path: &Path, result: &mut Vec<PathBuf>) {
+    if path.is_dir() {
+        let mut entries: Vec<PathBuf> = fs::read_dir(path)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        entries.sort();
+
+        for entry in entries {
+            collect_files(&entry, result);
+        }
+    } else if path.extension().is_some_and(|extension| extension == "ec") {
+        result.push(path.to_path_buf());
+    }
+}
+
+struct Opener {
+    line: usize,
+    indents: bool,
+}
+
+struct Formatter<'a> {
+    ctx: &'a Context,
+    source: Vec<char>,
+    interpolation: Regex,
+    output: String,
+    openers: Vec<Opener>,
+    line: usize,
+    line_has_content: bool,
+    pending_new_lines: usize,
+    previous: Option<TokenKind>,
+    previous_is_prefix: bool,
+}
+
+impl<'a> Formatter<'a> {
+    fn new(ctx: &'a Context, source: &str) -> Self {
+        Self {
+            ctx,
+            source: source.chars().collect(),
+            interpolation: Regex::new(r"\$\{([^}]*)}").unwrap(),
+            output: String::new(),
+            openers: vec![],
+            line: 0,
+            line_has_content: false,
+            pending_new_lines: 0,
+            previous: None,
+            previous_is_prefix: false,
+        }
+    }
+
+    fn format(mut self, tokens: &[Token]) -> String {
+        let mut previous_end = 0;
+
+        for (idx, token) in tokens.iter().enumerate() {
+            let gap = self.text(previous_end, token.span.start.index.0);
+            if let Some(start) = gap.find("//") {
+                self.write_comment(gap[start..].trim_end());
+            }
+            previous_end = token.span.end.index.0;
+
+            match &token.kind {
+                TokenKind::EOF => break,
+                TokenKind::Separator(NewLine) => {
+                    for _ in self.ctx.get_str(token.value()).chars() {
+                        self.new_line()
+                    }
+                }
+                _ => self.write_token(token, &tokens[idx..]),
+            }
+        }
+
+        let mut result = self.output.trim_end().to_string();
+        if !result.is_empty() {
+            result.push('\n');
+        }
+        result
+    }
+
+    fn new_line(&mut self) {
+        if !self.line_has_content {
+            if !self.output.is_empty() {
+                self.pending_new_lines =
+                    (self.pending_new_lines + 1).min(MAX_CONSECUTIVE_NEW_LINES);
+            }
+            return;
+        }
+
+        // only the last opener which is still open at the end of a line indents the following lines
+        if let Some(opener) = self.openers.last_mut() {
+            if opener.line == self.line {
+                opener.indents = true;
+            }
+        }
+
+        self.line += 1;
+        self.line_has_content = false;
+        self.pending_new_lines = 1;
+    }
+
+    fn write_comment(&mut self, comment: &str) {
+        if self.line_has_content {
+            self.output.push(' ');
+        } else {
+            self.start_line(self.indent(0), false);
+        }
+        self.output.push_str(comment);
+        self.line_has_content = true;
+    }
+
+    fn write_token(&mut self, token: &Token, remaining: &[Token]) {
+        if self.line_has_content {
+            if self.needs_space(&token.kind) {
+                self.output.push(' ');
+            }
+        } else {
+            let closers = remaining
+                .iter()
+                .take_while(|t| Self::is_closer(&t.kind))
+                .count();
+            self.start_line(self.indent(closers), closers > 0);
+        }
+
+        let text = self.token_text(token);
+        self.output.push_str(text.as_str());
+
+        if Self::is_opener(&token.kind) {
+            self.openers.push(Opener {
+                line: self.line,
+                indents: false,
+            });
+        } else if Self::is_closer(&token.kind) {
+            self.openers.pop();
+        }
+
+        self.previous_is_prefix = matches!(token.kind, TokenKind::Operator(Bang))
+            || (matches!(token.kind, TokenKind::Operator(Minus | Plus))
+                && self.previous.as_ref().is_none_or(Self::expects_operand));
+        self.previous = Some(token.kind.clone());
+        self.line_has_content = true;
+    }
+
+    fn start_line(&mut self, indent: usize, closes: bool) {
+        let mut new_lines = self.pending_new_lines;
+        // no blank lines directly after an opening or before a closing bracket
+        if closes || self.previous.as_ref().is_some_and(Self::is_opener) {
+            new_lines = new_lines.min(1);
+        }
+
+        for _ in 0..new_lines {
+            self.output.push('\n');
+        }
+        for _ in 0..indent {
+            self.output.push_str(INDENT);
+        }
+        self.pending_new_lines = 0;
+    }
+
+    fn indent(&self, closers: usize) -> usize {
+        let open = self.openers.len().saturating_sub(closers);
+        self.openers[..open].iter().filter(|o| o.indents).count()
+    }
+
+    fn needs_space(&self, current: &TokenKind) -> bool {
+        use TokenKind::{Identifier, Keyword, Operator, Separator};
+
+        if self.previous_is_prefix {
+            return false;
+        }
+
+        let Some(previous) = &self.previous else {
+            return false;
+        };
+
+        if matches!(
+            previous,
+            Operator(OpenParen | OpenBracket | Dot | DoubleColon | DoubleDot)
+        ) {
+            return false;
+        }
+
+        match current {
+            Operator(CloseParen | CloseBracket | Dot | DoubleColon | DoubleDot | Colon) => false,
+            Separator(Comma | Semicolon) => false,
+            Operator(OpenParen | OpenBracket) => !matches!(
+                previous,
+                Identifier
+                    | Keyword(KeywordToken::Function)
+                    | Keyword(KeywordToken::Itself)
+                    | Operator(CloseParen | CloseBracket)
+            ),
+            Operator(CloseCurly) => !matches!(previous, Operator(OpenCurly)),
+            _ => true,
+        }
+    }
+
+    fn expects_operand(kind: &TokenKind) -> bool {
+        match kind {
+            TokenKind::Operator(CloseParen | CloseBracket | CloseCurly) => false,
+            TokenKind::Operator(_) | TokenKind::Separator(_) => true,
+            TokenKind::Keyword(KeywordToken::Itself) => false,
+            TokenKind::Keyword(_) => true,
+            _ => false,
+        }
+    }
+
+    fn is_opener(kind: &TokenKind) -> bool {
+        matches!(
+            kind,
+            TokenKind::Operator(OpenParen | OpenCurly | OpenBracket)
+        )
+    }
+
+    fn is_closer(kind: &TokenKind) -> bool {
+        matches!(
+            kind,
+            TokenKind::Operator(CloseParen | CloseCurly | CloseBracket)
+        )
+    }
+
+    fn token_text(&self, token: &Token) -> String {
+        let text = self.text(token.span.start.index.0, token.span.end.index.0);
+        if token.is_literal(LiteralToken::String) {
+            return self
+                .interpolation
+                .replace_all(text.as_str(), |captures: &regex::Captures| {
+                    format!("${{{}}}", captures[1].trim())
+                })
+                .to_string();
+        }
+
+        // the span of a new line covers consecutive new lines, but nothing else should be
+        // taken from the source as is
+        if text.is_empty() {
+            return self.ctx.get_str(token.value()).to_string();
+        }
+        text
+    }
+
+    fn text(&self, start: usize, end: usize) -> String {
+        let end = end.min(self.source.len());
+        if start >= end {
+            return String::new();
+        }
+        self.source[start..end].iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::frontend::context::Context;
+    use crate::frontend::format::{collect_files, format_str};
+    use crate::frontend::lex::lex;
+    use crate::frontend::lex::token::LiteralToken;
+    use crate::frontend::lex::token::SeparatorToken::NewLine;
+
+    fn format(str: &str) -> String {
+        let mut ctx = Context::new();
+        format_str(&mut ctx, str).unwrap()
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(format(""), "");
+        assert_eq!(format("\n\n\n"), "");
+    }
+
+    #[test]
+    fn trailing_new_line() {
+        assert_eq!(format("let x = 1"), "let x = 1\n");
+        assert_eq!(format("let x = 1\n\n\n"), "let x = 1\n");
+    }
+
+    #[test]
+    fn normalizes_spacing() {
+        assert_eq!(format("let   x=1+  2"), "let x = 1 + 2\n");
+        assert_eq!(format("let x : Number = 1"), "let x: Number = 1\n");
+        assert_eq!(
+            format("std :: io :: println( 'hi' )"),
+            "std::io::println('hi')\n"
+        );
+        assert_eq!(format("p . x == 1"), "p.x == 1\n");
+        assert_eq!(format("self . name"), "self.name\n");
+    }
+
+    #[test]
+    fn named_arguments() {
+        assert_eq!(
+            format("let p = Point( x = 1,y = 2 )"),
+            "let p = Point(x = 1, y = 2)\n"
+        );
+    }
+
+    #[test]
+    fn prefix_operator() {
+        assert_eq!(format("let x = - 1"), "let x = -1\n");
+        assert_eq!(format("return ! true"), "return !true\n");
+        assert_eq!(format("let x = 2 - 1"), "let x = 2 - 1\n");
+    }
+
+    #[test]
+    fn function_declaration() {
+        assert_eq!(
+            format("function add(a:Number,b:Number)->Number{\nreturn a+b\n}"),
+            "function add(a: Number, b: Number) -> Number {\n    return a + b\n}\n"
+        );
+    }
+
+    #[test]
+    fn function_type() {
+        assert_eq!(
+            format("function should(description: String, body: function ( ) -> Bool){}"),
+            "function should(description: String, body: function() -> Bool) {}\n"
+        );
+    }
+
+    #[test]
+    fn lambda() {
+        assert_eq!(
+            format("receiver(){\nstd::io::println('lambda')\n}"),
+            "receiver() {\n    std::io::println('lambda')\n}\n"
+        );
+        assert_eq!(
+            format("should('p.x == 1'){p.x == 1}"),
+            "should('p.x == 1') { p.x == 1 }\n"
+        );
+        assert_eq!(format("test('empty'){ }"), "test('empty') {}\n");
+    }
+
+    #[test]
+    fn package() {
+        assert_eq!(
+            format("export package std {\n   from './io' export io\n       from './math' export math\n}"),
+            "export package std {\n    from './io' export io\n    from './math' export math\n}\n"
+        );
+    }
+
+    #[test]
+    fn nested_blocks() {
+        assert_eq!(
+            format("export package list {\n\n  export define List {\nfunction length() -> Number {\n return intrinsics.list_length(self)\n}\n\n }\n}"),
+            "export package list {\n    export define List {\n        function length() -> Number {\n            return intrinsics.list_length(self)\n        }\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn type_declaration() {
+        assert_eq!(
+            format("type Point (\n  x: Number,\n      y: Number\n)"),
+            "type Point(\n    x: Number,\n    y: Number\n)\n"
+        );
+        assert_eq!(
+            format("type Person ( name : String )"),
+            "type Person(name: String)\n"
+        );
+    }
+
+    #[test]
+    fn multiple_openers_on_one_line_indent_once() {
+        assert_eq!(
+            format("outer(inner() {\nx\n})"),
+            "outer(inner() {\n    x\n})\n"
+        );
+    }
+
+    #[test]
+    fn if_else() {
+        assert_eq!(
+            format("if x == 1 {\n1\n}   else{\n2\n}"),
+            "if x == 1 {\n    1\n} else {\n    2\n}\n"
+        );
+    }
+
+    #[test]
+    fn interpolated_string() {
+        assert_eq!(
+            format("std::io::println('Hi, I am ${ self.name }!')"),
+            "std::io::println('Hi, I am ${self.name}!')\n"
+        );
+    }
+
+    #[test]
+    fn keeps_escaped_strings() {
+        assert_eq!(
+            format("std::io::println('But you can\\'t see this\\n')"),
+            "std::io::println('But you can\\'t see this\\n')\n"
+        );
+    }
+
+    #[test]
+    fn keeps_comments() {
+        assert_eq!(
+            format("// leading\nfunction some_fn() {\n  // comment before\n  42   // trailing\n      // comment after\n}\n// out: 42"),
+            "// leading\nfunction some_fn() {\n    // comment before\n    42 // trailing\n    // comment after\n}\n// out: 42\n"
+        );
+    }
+
+    #[test]
+    fn collapses_blank_lines() {
+        assert_eq!(
+            format("let x = 1\n\n\n\nlet y = 2\n   \n  \nlet z = 3"),
+            "let x = 1\n\nlet y = 2\n\nlet z = 3\n"
+        );
+    }
+
+    #[test]
+    fn removes_blank_lines_around_block_content() {
+        assert_eq!(format("loop {\n\n  break\n\n}"), "loop {\n    break\n}\n");
+    }
+
+    #[test]
+    fn idempotent_and_lossless_for_every_file_in_repository() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+        let mut files = vec![];
+        collect_files(&root.join("src"), &mut files);
+        collect_files(&root.join("test"), &mut files);
+        assert!(!files.is_empty());
+
+        for file in files {
+            let content = fs::read_to_string(&file).unwrap();
+
+            let once = format(content.as_str());
+            let twice = format(once.as_str());
+            assert_eq!(
+                once,
+                twice,
+                "{} is not formatted idempotent",
+                file.display()
+            );
+
+            let mut ctx = Context::new();
+            let before = lex(&mut ctx, content.as_str()).unwrap();
+            let after = lex(&mut ctx, once.as_str()).unwrap();
+
+            let significant = |tokens: Vec<crate::frontend::lex::token::Token>| {
+                tokens
+                    .into_iter()
+                    .filter(|t| !t.is_separator(NewLine))
+                    .map(|t| {
+                        if t.is_literal(LiteralToken::String) {
+                            (t.kind, None)
+                        } else {
+                            (t.kind, Some(t.value))
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                significant(before),
+                significant(after),
+                "{} lost tokens while formatting",
+                file.display()
+            );
+
+            let comments = |str: &str| {
+                str.lines()
+                    .filter_map(|l| l.find("//").map(|idx| l[idx..].trim_end().to_string()))
+                    .filter(|l| !l.contains('\''))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                comments(content.as_str()),
+                comments(once.as_str()),
+                "{} lost comments while formatting",
+                file.display()
+            );
+        }
+    }
+}
@@ -1,12 +1,23 @@
 use crate::common::Span;
 use crate::frontend::lex::token::{Token, TokenKind};
-use crate::frontend::lex::Lexer;
+use crate::frontend::lex::{Error, Lexer};
 
 impl Lexer<'_> {
     pub(crate) fn consume_identifier(&mut self) -> crate::frontend::lex::Result<Token> {
         let start = self.position();
 
         let mut text = self.consume_while(|c| c.is_alphanumeric() || c == '_')?;
+        // nothing else starts with the char, without consuming it the lexer would not move on
+        if text.is_empty() {
+            let character = self.consume_next()?;
+            return Err(Error::UnknownCharacter(
+                character,
+                Span {
+                    start,
+                    end: self.position(),
+                },
+            ));
+        }
 
         Ok(Token {
             kind: TokenKind::Identifier,
@@ -23,7 +34,7 @@ impl Lexer<'_> {
 mod test {
     use crate::frontend::context::Context;
     use crate::frontend::lex::token::{LiteralToken, OperatorToken, TokenKind};
-    use crate::frontend::lex::Lexer;
+    use crate::frontend::lex::{Error, Lexer};
 
     #[test]
     fn some_var() {
@@ -104,4 +115,18 @@ mod test {
         assert_eq!(token_seven.span.end, (1, 20, 19));
         assert_eq!(ctx.get_str(token_seven.value()), "");
     }
+
+    #[test]
+    fn unknown_character() {
+        for text in ["\"", "@", "#", "$", "`", "\\"] {
+            let mut ctx = Context::new();
+            let mut lexer = Lexer::new(&mut ctx, text);
+            let Err(Error::UnknownCharacter(character, span)) = lexer.advance() else {
+                panic!("{text} lexed")
+            };
+            assert_eq!(character.to_string(), text);
+            assert_eq!(span.start, (1, 1, 0));
+            assert_eq!(span.end, (1, 2, 1));
+        }
+    }
 }
@@ -67,32 +67,17 @@ impl Lexer<'_> {
         let mut text = String::from("");
         let next = String::from(self.consume_next()?);
 
-        if next == "0" {
-            if let Some(c) = self.peek_next() {
-                match c {
-                    'x' | 'X' => {
-                        self.consume_next()?;
-                        text.push_str("0x");
-                        text.push_str(&*self.consume_while(|c| c.is_digit(16))?);
-                    }
-                    'o' | 'O' => {
-                        self.consume_next()?;
-                        text.push_str("0o");
-                        text.push_str(&*self.consume_while(|c| c.is_digit(8))?);
-                    }
-                    'b' | 'B' => {
-                        self.consume_next()?;
-                        text.push_str("0b");
-                        text.push_str(&*self.consume_while(|c| c.is_digit(2))?);
-                    }
-                    c if c.is_digit(10) => {
-                        text.push_str(&*self.consume_while(|c| c.is_digit(10))?);
-                    }
-                    _ => {
-                        text.push_str("0");
-                    }
-                }
-            }
+        let radix = match self.peek_next() {
+            Some('x' | 'X') if next == "0" => Some(("0x", 16)),
+            Some('o' | 'O') if next == "0" => Some(("0o", 8)),
+            Some('b' | 'B') if next == "0" => Some(("0b", 2)),
+            _ => None,
+        };
+
+        if let Some((prefix, radix)) = radix {
+            self.consume_next()?;
+            text.push_str(prefix);
+            text.push_str(&*self.consume_while(|c| c.is_digit(radix))?);
         } else {
             text.push_str(&next);
             let next = self.consume_while(|c| c.is_digit(10))?;
@@ -231,6 +216,29 @@ mod test {
         assert_eq!(ctx.get_str(result.value()), "42.24");
     }
 
+    #[test]
+    fn float_below_one() {
+        let text = "0.001";
+        let mut ctx = Context::new();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert_eq!(result.kind, TokenKind::Literal(Number));
+        assert_eq!(result.span.start, (1, 1, 0));
+        assert_eq!(result.span.end, (1, 6, 5));
+        assert_eq!(ctx.get_str(result.value()), "0.001");
+    }
+
+    #[test]
+    fn zero() {
+        let text = "0";
+        let mut ctx = Context::new();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert_eq!(result.kind, TokenKind::Literal(Number));
+        assert_eq!(result.span.end, (1, 2, 1));
+        assert_eq!(ctx.get_str(result.value()), "0");
+    }
+
     #[test]
     fn hex() {
         let text = "0xDEADBEEF";
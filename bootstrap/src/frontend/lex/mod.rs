@@ -1,6 +1,7 @@
 use std::cell::RefCell;
+use std::rc::Rc;
 
-use crate::common::{Column, Index, Position, Row, Span};
+use crate::common::{Column, Index, Position, Row, Span, StringTableId};
 use crate::frontend::context::Context;
 use crate::frontend::lex::token::Token;
 use crate::frontend::lex::token::TokenKind::EOF;
@@ -20,37 +21,35 @@ pub enum Error {
     UnknownKeyword(String),
     UnknownOperator(String),
     UnknownSeparator(String),
+    UnknownCharacter(char, Span),
 }
 
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
+// chars get collected once, looking them up by position within the str is linear
 #[derive(Clone)]
-pub struct Reader<'a> {
-    content: &'a str,
+pub struct Reader {
+    chars: Rc<[char]>,
     pos: RefCell<usize>,
 }
 
-impl<'a> Reader<'a> {
-    pub(crate) fn new(content: &'a str) -> Self {
+impl Reader {
+    pub(crate) fn new(content: &str) -> Self {
         Reader {
-            content,
+            chars: content.chars().collect(),
             pos: RefCell::new(0),
         }
     }
 
     pub(crate) fn consume_next(&self) -> Result<char> {
         let mut pos = self.pos.borrow_mut();
-        if *pos >= self.content.len() {
-            return Err(UnexpectedEndOfFile);
-        }
-
-        let next_char = self.content.chars().nth(*pos).ok_or(UnexpectedEndOfFile)?;
+        let next_char = *self.chars.get(*pos).ok_or(UnexpectedEndOfFile)?;
         *pos += 1;
         Ok(next_char)
     }
 
     pub(crate) fn at_the_end(&self) -> bool {
-        *self.pos.borrow() >= self.content.len()
+        *self.pos.borrow() >= self.chars.len()
     }
 
     pub(crate) fn consume_while(&self, test: impl Fn(char) -> bool) -> Result<String> {
@@ -72,69 +71,34 @@ impl<'a> Reader<'a> {
     }
 
     pub fn consume_if(&self, sequence: &str) -> Option<String> {
-        let pos = *self.pos.borrow();
-        if pos >= self.content.len() {
-            return None;
-        }
-
-        let result: String = self
-            .content
-            .chars()
-            .skip(pos)
-            .take(sequence.len())
-            .collect();
-        if result.is_empty() {
-            return None;
-        }
-
-        if result == sequence {
-            let mut pos = self.pos.borrow_mut();
-            *pos += result.len();
-            Some(result)
-        } else {
-            None
-        }
+        let result = self.peek_if(sequence)?;
+        let mut pos = self.pos.borrow_mut();
+        *pos += result.chars().count();
+        Some(result)
     }
 
     pub fn peek_next(&self) -> Option<char> {
-        let pos = *self.pos.borrow();
-        if pos >= self.content.len() {
-            return None;
-        }
-
-        self.content
-            .chars()
-            .nth(pos)
-            .map(|c| Some(c))
-            .unwrap_or(None)
+        self.chars.get(*self.pos.borrow()).copied()
     }
 
     pub fn peek_many(&self, window: usize) -> Option<String> {
         let pos = *self.pos.borrow();
-        if pos >= self.content.len() {
-            return None;
-        }
-
-        let chars: Vec<char> = self.content.chars().skip(pos).take(window).collect();
-        if chars.is_empty() {
+        if pos >= self.chars.len() {
             return None;
         }
 
-        Some(chars.into_iter().collect())
+        let end = (pos + window).min(self.chars.len());
+        Some(self.chars[pos..end].iter().collect())
     }
 
     pub fn peek_if(&self, sequence: &str) -> Option<String> {
         let pos = *self.pos.borrow();
-        if pos >= self.content.len() {
+        if pos >= self.chars.len() {
             return None;
         }
 
-        let chars: String = self
-            .content
-            .chars()
-            .skip(pos)
-            .take(sequence.len())
-            .collect();
+        let end = (pos + sequence.chars().count()).min(self.chars.len());
+        let chars: String = self.chars[pos..end].iter().collect();
         if chars.is_empty() {
             return None;
         }
@@ -160,18 +124,25 @@ pub fn lex(ctx: &mut Context, str: &str) -> Result<Vec<Token>> {
 
 pub(crate) struct Lexer<'a> {
     ctx: &'a mut Context,
-    reader: Reader<'a>,
+    reader: Reader,
     current_line: RefCell<Row>,
     current_column: RefCell<Column>,
+    file: Option<StringTableId>,
 }
 
 impl<'a> Lexer<'a> {
     pub(crate) fn new(ctx: &'a mut Context, str: &'a str) -> Self {
+        let file = ctx
+            .modules
+            .current()
+            .map(|path| path.to_string_lossy().to_string())
+            .map(|path| ctx.string_table.push_str(&path));
         Self {
             ctx,
             reader: Reader::new(str),
             current_line: RefCell::new(Row(1)),
             current_column: RefCell::new(Column(1)),
+            file,
         }
     }
 
@@ -243,6 +214,7 @@ impl<'a> Lexer<'a> {
             row: self.current_line.borrow().clone(),
             column: self.current_column.borrow().clone(),
             index: Index(*self.reader.pos.borrow()),
+            file: self.file,
         }
     }
 
@@ -271,13 +243,13 @@ impl<'a> Lexer<'a> {
 
     pub(crate) fn consume_while(&self, test: impl Fn(char) -> bool) -> Result<String> {
         let result = self.reader.consume_while(test)?;
-        self.current_column.borrow_mut().0 += result.len();
+        self.current_column.borrow_mut().0 += result.chars().count();
         Ok(result)
     }
 
     pub(crate) fn consume_if(&self, sequence: &str) -> Option<String> {
         if let Some(result) = self.reader.consume_if(sequence) {
-            self.current_column.borrow_mut().0 += result.len();
+            self.current_column.borrow_mut().0 += result.chars().count();
             return Some(result);
         }
         None
@@ -287,3 +259,57 @@ impl<'a> Lexer<'a> {
         self.peek_while(|c| c.is_ascii_alphanumeric() || c == '_')
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::frontend::context::Context;
+    use crate::frontend::lex::token::LiteralToken::String;
+    use crate::frontend::lex::token::TokenKind;
+    use crate::frontend::lex::{Lexer, Reader};
+
+    #[test]
+    fn reader_ends_after_last_char() {
+        let reader = Reader::new("äö");
+        assert_eq!(reader.consume_next().unwrap(), 'ä');
+        assert!(!reader.at_the_end());
+        assert_eq!(reader.consume_next().unwrap(), 'ö');
+        assert!(reader.at_the_end());
+        assert!(reader.consume_next().is_err());
+        assert_eq!(reader.peek_next(), None);
+    }
+
+    #[test]
+    fn reader_peeks_chars() {
+        let reader = Reader::new("äöü!");
+        assert_eq!(reader.peek_many(3).unwrap(), "äöü");
+        assert_eq!(reader.peek_many(10).unwrap(), "äöü!");
+        assert_eq!(reader.peek_if("äö").unwrap(), "äö");
+        assert_eq!(reader.peek_if("äöx"), None);
+    }
+
+    #[test]
+    fn reader_consumes_sequence_of_chars() {
+        let reader = Reader::new("äöü!");
+        assert_eq!(reader.consume_if("äö").unwrap(), "äö");
+        assert_eq!(reader.peek_next(), Some('ü'));
+        assert_eq!(reader.consume_while(|c| c != '!').unwrap(), "ü");
+        assert_eq!(reader.peek_next(), Some('!'));
+    }
+
+    #[test]
+    fn positions_count_chars() {
+        let text = "'äöü' 'x'";
+        let mut ctx = Context::new();
+        let mut lexer = Lexer::new(&mut ctx, text);
+
+        let result = lexer.advance().unwrap();
+        assert_eq!(result.kind, TokenKind::Literal(String));
+        assert_eq!(result.span.start, (1, 1, 0));
+        assert_eq!(result.span.end, (1, 6, 5));
+
+        let result = lexer.advance().unwrap();
+        assert_eq!(result.span.start, (1, 7, 6));
+        assert_eq!(result.span.end, (1, 10, 9));
+        assert_eq!(ctx.get_str(result.value()), "x");
+    }
+}
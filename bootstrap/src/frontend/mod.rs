@@ -1,4 +1,6 @@
+use std::fmt;
 use std::ops::Index;
+use std::path::Path;
 
 use crate::frontend::ast::AstTreeNode;
 pub use crate::frontend::context::Context;
@@ -7,8 +9,10 @@ use crate::frontend::parse::parse;
 
 pub mod ast;
 pub mod context;
+pub mod format;
 pub mod lex;
 pub mod modifier;
+pub mod module;
 pub mod parse;
 
 #[derive(Debug)]
@@ -36,9 +40,19 @@ impl From<ast::Error> for Error {
     }
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Lexer(err) => write!(f, "{err:?}"),
+            Error::Parser(err) => write!(f, "{err:?}"),
+            Error::Ast(err) => write!(f, "{err}"),
+        }
+    }
+}
+
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Ast {
     pub nodes: Vec<AstTreeNode>,
 }
@@ -67,3 +81,13 @@ pub fn ast_from_str(ctx: &mut Context, str: &str) -> Result<Ast> {
     let nodes = parse(ctx, lexed)?;
     Ok(ast::from(ctx, nodes)?)
 }
+
+/// Loads a file, imports get resolved relative to it
+pub fn ast_from_file(ctx: &mut Context, path: &Path) -> Result<Ast> {
+    ast::from_file(ctx, path)
+}
+
+/// Generates the ast of source code whose imports get resolved as if it was stored at the given path
+pub fn ast_from_source(ctx: &mut Context, path: &Path, source: &str) -> Result<Ast> {
+    ast::from_source(ctx, path, source)
+}
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::frontend::ast::Error;
+use crate::frontend::Ast;
+
+/// Tracks which files got loaded, so that every file gets parsed once and import cycles get detected
+#[derive(Debug)]
+pub struct Modules {
+    search_path: Vec<PathBuf>,
+    loaded: HashMap<PathBuf, Ast>,
+    loading: Vec<PathBuf>,
+}
+
+impl Modules {
+    /// Library roots are taken from ELODIE_PATH, followed by the bundled library
+    pub fn new() -> Self {
+        let mut search_path: Vec<PathBuf> = env::var_os("ELODIE_PATH")
+            .map(|paths| env::split_paths(&paths).collect())
+            .unwrap_or_default();
+        search_path.push(crate::library_file_path(""));
+        Self::with_search_path(search_path)
+    }
+
+    pub fn with_search_path(search_path: Vec<PathBuf>) -> Self {
+        Self {
+            search_path,
+            loaded: HashMap::new(),
+            loading: vec![],
+        }
+    }
+
+    /// Resolves the source of an import, sources starting with ./ or ../ are relative to the importing file,
+    /// all others are looked up in the search path
+    pub(crate) fn resolve(&self, source: &str) -> crate::frontend::ast::Result<PathBuf> {
+        let roots = if source.starts_with("./") || source.starts_with("../") {
+            vec![self.current_dir()]
+        } else {
+            self.search_path.clone()
+        };
+
+        let mut searched = vec![];
+        for root in roots {
+            for candidate in candidates(&root.join(source)) {
+                if candidate.is_file() {
+                    return Ok(candidate.canonicalize().unwrap_or(candidate));
+                }
+                searched.push(candidate);
+            }
+        }
+
+        Err(Error::UnresolvedImport {
+            source: source.to_string(),
+            searched,
+        })
+    }
+
+    /// File which gets loaded right now
+    pub(crate) fn current(&self) -> Option<&Path> {
+        self.loading.last().map(PathBuf::as_path)
+    }
+
+    /// All files loaded so far
+    pub(crate) fn loaded(&self) -> impl Iterator<Item = (&PathBuf, &Ast)> {
+        self.loaded.iter()
+    }
+
+    pub(crate) fn get(&self, path: &Path) -> Option<&Ast> {
+        self.loaded.get(path)
+    }
+
+    pub(crate) fn insert(&mut self, path: PathBuf, ast: Ast) {
+        self.loaded.insert(path, ast);
+    }
+
+    /// Marks the file as being loaded, fails if the file is already on its way of getting loaded
+    pub(crate) fn enter(&mut self, path: PathBuf) -> crate::frontend::ast::Result<()> {
+        if let Some(idx) = self.loading.iter().position(|loading| *loading == path) {
+            let mut cycle = self.loading[idx..].to_vec();
+            cycle.push(path);
+            return Err(Error::ImportCycle(cycle));
+        }
+        self.loading.push(path);
+        Ok(())
+    }
+
+    pub(crate) fn leave(&mut self) {
+        self.loading.pop();
+    }
+
+    fn current_dir(&self) -> PathBuf {
+        match self.loading.last().and_then(|file| file.parent()) {
+            Some(dir) => dir.to_path_buf(),
+            None => env::current_dir().unwrap_or_default(),
+        }
+    }
+}
+
+fn candidates(path: &Path) -> Vec<PathBuf> {
+    if path.extension().is_some_and(|extension| extension == "ec") {
+        return vec![path.to_path_buf()];
+    }
+    let mut file = path.as_os_str().to_os_string();
+    file.push(".ec");
+    vec![PathBuf::from(file), path.join("index.ec")]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::frontend::ast::Error;
+    use crate::frontend::module::Modules;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("lib/text")).unwrap();
+        fs::write(dir.join("math.ec"), "").unwrap();
+        fs::write(dir.join("lib/text/index.ec"), "").unwrap();
+        dir.canonicalize().unwrap()
+    }
+
+    #[test]
+    fn resolves_relative_to_importing_file() {
+        let dir = test_dir("elodie_module_relative");
+        let mut modules = Modules::with_search_path(vec![]);
+        modules.enter(dir.join("main.ec")).unwrap();
+
+        assert_eq!(modules.resolve("./math").unwrap(), dir.join("math.ec"));
+        assert_eq!(modules.resolve("./math.ec").unwrap(), dir.join("math.ec"));
+        assert_eq!(
+            modules.resolve("./lib/text").unwrap(),
+            dir.join("lib/text/index.ec")
+        );
+    }
+
+    #[test]
+    fn resolves_from_search_path() {
+        let dir = test_dir("elodie_module_search_path");
+        let modules = Modules::with_search_path(vec![dir.join("unknown"), dir.join("lib")]);
+
+        assert_eq!(modules.resolve("text").unwrap(), dir.join("lib/text/index.ec"));
+
+        let Err(Error::UnresolvedImport { searched, .. }) = modules.resolve("math") else {
+            panic!()
+        };
+        assert_eq!(searched.len(), 4);
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let mut modules = Modules::with_search_path(vec![]);
+        modules.enter(PathBuf::from("/a.ec")).unwrap();
+        modules.enter(PathBuf::from("/b.ec")).unwrap();
+
+        let Err(Error::ImportCycle(cycle)) = modules.enter(PathBuf::from("/a.ec")) else {
+            panic!()
+        };
+        assert_eq!(
+            cycle,
+            vec![
+                PathBuf::from("/a.ec"),
+                PathBuf::from("/b.ec"),
+                PathBuf::from("/a.ec")
+            ]
+        );
+
+        modules.leave();
+        modules.enter(PathBuf::from("/b.ec")).unwrap();
+    }
+}
@@ -1,7 +1,7 @@
-use crate::frontend::lex::token::KeywordToken;
-use crate::frontend::lex::token::KeywordToken::Export;
+use crate::frontend::lex::token::KeywordToken::{Export, Import};
+use crate::frontend::lex::token::{KeywordToken, SeparatorToken, TokenKind};
 use crate::frontend::parse::precedence::Precedence;
-use crate::frontend::parse::{FromExportNode, FromNode, Parser};
+use crate::frontend::parse::{FromExportNode, FromImportNode, FromNode, IdentifierNode, Parser};
 
 impl<'a> Parser<'a> {
     pub(crate) fn parse_from(&mut self) -> crate::frontend::parse::Result<FromNode> {
@@ -21,6 +21,27 @@ impl<'a> Parser<'a> {
             }));
         }
 
+        let is_import = self.current()?.is_keyword(KeywordToken::Import);
+
+        if is_import {
+            let _ = self.consume_keyword(Import)?;
+
+            // imported items can be packages, functions or types, which differ in their case
+            let mut what_nodes = vec![IdentifierNode(self.consume(TokenKind::Identifier)?)];
+            while self
+                .consume_if(TokenKind::Separator(SeparatorToken::Comma))?
+                .is_some()
+            {
+                what_nodes.push(IdentifierNode(self.consume(TokenKind::Identifier)?));
+            }
+
+            return Ok(FromNode::Import(FromImportNode {
+                token,
+                from_node: where_node,
+                what_nodes,
+            }));
+        }
+
         unimplemented!();
     }
 }
@@ -38,7 +59,9 @@ mod tests {
         let result = parse(&mut ctx, tokens).unwrap();
         assert_eq!(result.len(), 1);
 
-        let FromNode::Export(node) = result[0].as_from();
+        let FromNode::Export(node) = result[0].as_from() else {
+            panic!("not export")
+        };
 
         let LiteralNode::String(literal) = &node.from_node.as_literal() else {
             panic!("not string literal")
@@ -48,4 +71,25 @@ mod tests {
         let identifier = node.what_node.as_identifier();
         assert_eq!(ctx.get_str(identifier.value()), "io");
     }
+
+    #[test]
+    fn import_items_from_local_file() {
+        let mut ctx = Context::new();
+        let tokens = lex(&mut ctx, "from './math' import add, Point").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let FromNode::Import(node) = result[0].as_from() else {
+            panic!("not import")
+        };
+
+        let LiteralNode::String(literal) = &node.from_node.as_literal() else {
+            panic!("not string literal")
+        };
+        assert_eq!(ctx.get_str(literal.value()), "./math");
+
+        assert_eq!(node.what_nodes.len(), 2);
+        assert_eq!(ctx.get_str(node.what_nodes[0].value()), "add");
+        assert_eq!(ctx.get_str(node.what_nodes[1].value()), "Point");
+    }
 }
@@ -0,0 +1,39 @@
+use crate::frontend::lex::token::{KeywordToken, LiteralToken};
+use crate::frontend::parse::node::{ImportNode, LiteralStringNode};
+use crate::frontend::parse::Parser;
+
+impl<'a> Parser<'a> {
+    pub(crate) fn parse_import(&mut self) -> crate::frontend::parse::Result<ImportNode> {
+        let token = self.consume_keyword(KeywordToken::Import)?;
+        let source = LiteralStringNode(self.consume_literal(LiteralToken::String)?);
+        Ok(ImportNode { token, source })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frontend::context::Context;
+    use crate::frontend::lex::lex;
+    use crate::frontend::parse::{parse, Error};
+
+    #[test]
+    fn import_local_file() {
+        let mut ctx = Context::new();
+        let tokens = lex(&mut ctx, "import './math'").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_import();
+        assert_eq!(ctx.get_str(node.source.value()), "./math");
+    }
+
+    #[test]
+    fn import_requires_string() {
+        let mut ctx = Context::new();
+        let tokens = lex(&mut ctx, "import math").unwrap();
+        let result = parse(&mut ctx, tokens);
+        let Err(Error::UnexpectedToken { .. }) = result else {
+            panic!()
+        };
+    }
+}
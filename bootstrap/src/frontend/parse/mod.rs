@@ -18,6 +18,7 @@ mod from;
 mod function;
 mod identifier;
 mod r#if;
+mod import;
 mod infix;
 mod literal;
 mod r#loop;
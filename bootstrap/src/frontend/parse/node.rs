@@ -16,6 +16,7 @@ pub(crate) enum Node {
     DefineDeclaration(DefineDeclarationNode),
     Identifier(IdentifierNode),
     If(IfNode),
+    Import(ImportNode),
     Infix(InfixNode),
     Itself(ItselfNode),
     Literal(LiteralNode),
@@ -40,12 +41,14 @@ impl Node {
             Node::Continue(n) => n.token.clone(),
             Node::From(n) => match n {
                 FromNode::Export(n) => n.token.clone(),
+                FromNode::Import(n) => n.token.clone(),
             },
             Node::ExternalFunctionDeclaration(n) => n.token.clone(),
             Node::FunctionDeclaration(n) => n.token.clone(),
             Node::DefineDeclaration(n) => n.token.clone(),
             Node::Identifier(n) => n.0.clone(),
             Node::If(n) => n.token.clone(),
+            Node::Import(n) => n.token.clone(),
             Node::Infix(n) => n.token.clone(),
             Node::Itself(n) => n.0.clone(),
             Node::Literal(n) => match n {
@@ -188,6 +191,17 @@ impl Node {
         }
     }
 
+    pub(crate) fn is_import(&self) -> bool {
+        matches!(self, Node::Import(_))
+    }
+    pub(crate) fn as_import(&self) -> &ImportNode {
+        if let Node::Import(result) = self {
+            result
+        } else {
+            panic!("not import")
+        }
+    }
+
     pub(crate) fn is_infix(&self) -> bool {
         matches!(self, Node::Infix(_))
     }
@@ -366,9 +380,17 @@ pub(crate) struct FromExportNode {
     pub(crate) what_node: Box<Node>,
 }
 
+#[derive(Debug, PartialEq)]
+pub(crate) struct FromImportNode {
+    pub(crate) token: Token,
+    pub(crate) from_node: Box<Node>,
+    pub(crate) what_nodes: Vec<IdentifierNode>,
+}
+
 #[derive(Debug, PartialEq)]
 pub(crate) enum FromNode {
     Export(FromExportNode),
+    Import(FromImportNode),
 }
 
 #[derive(Debug, PartialEq)]
@@ -439,6 +461,12 @@ pub(crate) struct ElseNode {
     pub(crate) block: BlockNode,
 }
 
+#[derive(Debug, PartialEq)]
+pub(crate) struct ImportNode {
+    pub(crate) token: Token,
+    pub(crate) source: LiteralStringNode,
+}
+
 #[derive(Debug, PartialEq)]
 pub(crate) struct InfixNode {
     pub(crate) token: Token,
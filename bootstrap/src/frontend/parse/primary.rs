@@ -54,6 +54,7 @@ impl<'a> Parser<'a> {
                     Ok(FunctionDeclaration(self.parse_function_declaration()?))
                 }
                 KeywordToken::If => Ok(If(self.parse_if()?)),
+                KeywordToken::Import => Ok(Node::Import(self.parse_import()?)),
                 KeywordToken::Itself => Ok(Itself(self.parse_self()?)),
                 KeywordToken::Let => Ok(VariableDeclaration(self.parse_variable_declaration()?)),
                 KeywordToken::Loop => Ok(Loop(self.parse_loop()?)),
@@ -1,6 +1,6 @@
 use regex::Regex;
 
-use crate::common::Span;
+use crate::common::{Column, Index, Position, Row, Span};
 use crate::frontend::lex::lex;
 use crate::frontend::lex::token::{LiteralToken, Token, TokenKind};
 use crate::frontend::parse::{
@@ -15,24 +15,39 @@ impl<'a> Parser<'a> {
             let parts = Self::extract_and_split_interpolations(value);
             let mut nodes = Vec::with_capacity(parts.len());
 
+            // parts follow the opening quote, positions within them are relative to the literal
+            let mut start = advance(token.span.start.clone(), "'");
             for part in parts {
+                let end = advance(start.clone(), &part);
                 if part.starts_with("${") {
                     let interest = &part[2..part.len() - 1];
-                    let lexed = lex(self.ctx, interest).unwrap();
+                    let base = advance(start, "${");
+                    let lexed = lex(self.ctx, interest)
+                        .unwrap()
+                        .into_iter()
+                        .map(|token| Token {
+                            span: Span {
+                                start: shift(token.span.start, &base),
+                                end: shift(token.span.end, &base),
+                            },
+                            ..token
+                        })
+                        .collect();
                     let parsed = parse(self.ctx, lexed)?;
                     nodes.extend(parsed);
                 } else {
                     let token = Token {
                         kind: TokenKind::Literal(LiteralToken::String),
                         span: Span {
-                            start: token.span.start.clone(),
-                            end: token.span.end.clone(),
+                            start,
+                            end: end.clone(),
                         },
                         value: self.ctx.string_table.push_str(part.as_str()),
                     };
 
                     nodes.push(Node::Literal(LiteralNode::String(LiteralStringNode(token))))
                 }
+                start = end;
             }
 
             return Ok(Node::StringInterpolation(StringInterpolationNode {
@@ -67,13 +82,42 @@ impl<'a> Parser<'a> {
     }
 }
 
+/// Position after the text, which starts at the given position
+fn advance(mut position: Position, text: &str) -> Position {
+    for c in text.chars() {
+        position.index.0 += 1;
+        if c == '\n' {
+            position.row.0 += 1;
+            position.column = Column(1);
+        } else {
+            position.column.0 += 1;
+        }
+    }
+    position
+}
+
+/// Moves a position of a source which starts at base into the source around it
+fn shift(position: Position, base: &Position) -> Position {
+    let column = if position.row == Row(1) {
+        Column(base.column.0 + position.column.0 - 1)
+    } else {
+        position.column
+    };
+    Position {
+        row: Row(base.row.0 + position.row.0 - 1),
+        column,
+        index: Index(base.index.0 + position.index.0),
+        file: base.file,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::frontend::context::Context;
     use crate::frontend::lex::lex;
     use crate::frontend::parse::node::LiteralNode;
     use crate::frontend::parse::node::Node::Literal;
-    use crate::frontend::parse::Node::StringInterpolation;
+    use crate::frontend::parse::Node::{Infix, StringInterpolation};
     use crate::frontend::parse::{
         parse, InfixNode, InfixOperator, StringInterpolationNode, TupleNode,
     };
@@ -112,6 +156,34 @@ mod tests {
         assert_eq!(ctx.get_str(node.value()), "value")
     }
 
+    #[test]
+    fn interpolation_spans() {
+        let mut ctx = Context::new();
+        let tokens = lex(&mut ctx, "'x ${value} ${1 + 2}'").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+
+        let StringInterpolation(StringInterpolationNode { nodes, .. }) = &result[0] else {
+            panic!()
+        };
+        assert_eq!(nodes.len(), 4);
+
+        let Literal(LiteralNode::String(node)) = &nodes[0] else {
+            panic!()
+        };
+        assert_eq!(node.0.span.start, (1, 2, 1));
+        assert_eq!(node.0.span.end, (1, 4, 3));
+
+        let node = nodes[1].as_identifier();
+        assert_eq!(node.0.span.start, (1, 6, 5));
+        assert_eq!(node.0.span.end, (1, 11, 10));
+
+        let Infix(InfixNode { left, right, .. }) = &nodes[3] else {
+            panic!()
+        };
+        assert_eq!(left.token().span.start, (1, 15, 14));
+        assert_eq!(right.token().span.end, (1, 20, 19));
+    }
+
     #[test]
     fn interpolation_with_number() {
         let mut ctx = Context::new();
@@ -4,7 +4,7 @@ use crate::common::node::Node::{DeclareVariable, LiteralBoolean, LiteralNumber,
 use crate::common::{StringTable, WithSpan};
 use crate::frontend;
 use crate::frontend::ast::AstTreeNode;
-use crate::ir::analyse::AnalyseTreeNode;
+use crate::ir::analyse::{AnalyseTreeNode, Error};
 use crate::ir::context::Context;
 use crate::ir::symbol::{SymbolId, SymbolName, SymbolTable};
 
@@ -42,7 +42,7 @@ impl<'a> Inference<'a> {
             LiteralBoolean(node) => self.infer_literal_boolean(ast.span(), node),
             LiteralNumber(node) => self.infer_literal_number(ast.span(), node),
             LiteralString(node) => self.infer_literal_string(ast.span(), node),
-            _ => unimplemented!("{ast:#?}"),
+            _ => Err(Error::Unsupported(ast.kind())),
         }
     }
 
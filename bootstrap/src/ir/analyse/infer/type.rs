@@ -1,6 +1,6 @@
 use crate::frontend::ast;
 use crate::ir::analyse::infer::Inference;
-use crate::ir::analyse::InferredType;
+use crate::ir::analyse::{Error, InferredType};
 
 impl<'a> Inference<'a> {
     pub(crate) fn type_from_type_node(
@@ -11,7 +11,8 @@ impl<'a> Inference<'a> {
             ast::AstType::Boolean => Ok(InferredType::Boolean),
             ast::AstType::Number => Ok(InferredType::Number),
             ast::AstType::String => Ok(InferredType::String),
-            _ => unimplemented!("{node:#?}"),
+            ast::AstType::Object => Err(Error::Unsupported("Object type")),
+            ast::AstType::Function { .. } => Err(Error::Unsupported("Function type")),
         }
     }
 }
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::fmt;
 use std::ops::Index;
 
 pub use node::*;
@@ -28,7 +29,17 @@ pub enum InferredType {
 impl InferredType {}
 
 #[derive(Debug)]
-pub enum Error {}
+pub enum Error {
+    Unsupported(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Unsupported(kind) => write!(f, "{kind} is not supported by the analysis yet"),
+        }
+    }
+}
 
 pub(crate) type Result<T, E = Error> = core::result::Result<T, E>;
 
@@ -1,21 +1,35 @@
-use std::ops::Index;
-
-use crate::common::node::Node::LiteralNumber;
-use crate::frontend::ast::SPAN_NOT_IMPLEMENTED;
-use crate::ir::{Context, TypeId};
-use crate::ir::analyse::{Analyse, AnalyseLiteralNumberNode};
-use crate::ir::Ir;
+use crate::common::node::Node::{DeclareVariable, LiteralBoolean, LiteralNumber, LiteralString};
+use crate::common::WithSpan;
+use crate::ir::analyse::{Analyse, AnalyseLiteralNumberNode, AnalyseTreeNode};
 use crate::ir::node::{IrLiteralNumberNode, IrTreeNode};
+use crate::ir::Ir;
+use crate::ir::{Context, Error, TypeId};
 
 mod literal;
 
 pub(crate) fn generate(ctx: &mut Context, analysed: Analyse) -> crate::ir::Result<Ir> {
-    let AnalyseLiteralNumberNode { value } = analysed.nodes.index(0).as_literal_number() else { panic!() };
-    Ok(
-        Ir {
-            nodes: vec![
-                IrTreeNode::new(LiteralNumber(IrLiteralNumberNode { value: value.clone(), value_type: TypeId(1) }), SPAN_NOT_IMPLEMENTED.clone())
-            ]
-        }
-    )
+    let mut nodes = vec![];
+    for node in &analysed.nodes {
+        let LiteralNumber(AnalyseLiteralNumberNode { value }) = node.node() else {
+            return Err(Error::Unsupported(kind(node)));
+        };
+        nodes.push(IrTreeNode::new(
+            LiteralNumber(IrLiteralNumberNode {
+                value: value.clone(),
+                value_type: TypeId(1),
+            }),
+            node.span(),
+        ));
+    }
+    Ok(Ir { nodes })
+}
+
+fn kind(node: &AnalyseTreeNode) -> &'static str {
+    match node.node() {
+        DeclareVariable(_) => "DeclareVariable",
+        LiteralBoolean(_) => "LiteralBoolean",
+        LiteralNumber(_) => "LiteralNumber",
+        LiteralString(_) => "LiteralString",
+        _ => unreachable!("analysis infers nothing else"),
+    }
 }
@@ -1,25 +1,37 @@
+use std::fmt;
 use std::ops::Index;
 
 pub use context::Context;
 pub use r#type::{Type, TypeId, TypeName, TypeTable, TypeVariable};
 
-use crate::{frontend, ir};
-use crate::frontend::ast_from_str;
+use crate::frontend::{ast_from_str, Ast};
 use crate::ir::analyse::analyse;
 use crate::ir::generate::generate;
 use crate::ir::node::IrTreeNode;
+use crate::{frontend, ir};
 
 mod analyse;
 mod context;
 mod generate;
 pub(crate) mod node;
-mod symbol;
+pub(crate) mod symbol;
 mod r#type;
 
 #[derive(Debug)]
 pub enum Error {
     Frontend(frontend::Error),
     Analyse(analyse::Error),
+    Unsupported(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Frontend(err) => write!(f, "{err:?}"),
+            Error::Analyse(err) => write!(f, "{err}"),
+            Error::Unsupported(kind) => write!(f, "{kind} is not supported by ir generation yet"),
+        }
+    }
 }
 
 impl From<frontend::Error> for Error {
@@ -58,7 +70,10 @@ pub fn ir_from_str(str: &str) -> Result<ir::Ir> {
     let ast = ast_from_str(&mut ctx, str)?;
 
     let mut ctx = ir::Context::new(ctx);
-    let analysed = analyse(&mut ctx, ast)?;
+    ir_from_ast(&mut ctx, ast)
+}
 
-    generate(&mut ctx, analysed)
+pub(crate) fn ir_from_ast(ctx: &mut ir::Context, ast: Ast) -> Result<ir::Ir> {
+    let analysed = analyse(ctx, ast)?;
+    generate(ctx, analysed)
 }
@@ -129,7 +129,7 @@ impl Index<SymbolId> for SymbolTable {
     type Output = Symbol;
 
     fn index(&self, index: SymbolId) -> &Self::Output {
-        self.index(index.0)
+        self.index(index.0 - 1)
     }
 }
 
@@ -244,4 +244,16 @@ mod tests {
         assert_eq!(symbol.id(), SymbolId(3));
         assert_eq!(symbol.name_str(&ctx), "variable");
     }
+
+    #[test]
+    fn index_by_symbol_id() {
+        let mut ctx = Context::default();
+        let mut table = SymbolTable::new();
+
+        let arg_id = table.register_argument(SymbolName(ctx.push_str("argument")));
+        let var_id = table.register_variable(SymbolName(ctx.push_str("variable")));
+
+        assert_eq!(table[arg_id].name_str(&ctx), "argument");
+        assert_eq!(table[var_id].name_str(&ctx), "variable");
+    }
 }
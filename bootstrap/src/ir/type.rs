@@ -5,10 +5,10 @@ use crate::common::StringTableId;
 #[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
 pub struct TypeId(pub usize);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypeName(pub String);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Type {
     pub id: TypeId,
     pub name: TypeName,
@@ -18,7 +18,7 @@ pub struct Type {
     // FIXME track declaration like file, position etc..
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypeVariable {}
 
 #[derive(Debug)]
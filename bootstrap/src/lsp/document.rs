@@ -0,0 +1,258 @@
+use serde_json::{json, Value};
+
+use crate::common::panic::catch_silently;
+use crate::common::{Column, Index, Position, Row, Span};
+use crate::frontend;
+use crate::frontend::{ast, ast_from_str, lex, parse, Context};
+use crate::lsp::index;
+
+/// Converts between character indexes used by spans and the line / utf-16 column positions of the protocol
+#[derive(Debug)]
+pub(crate) struct LineIndex {
+    chars: Vec<char>,
+    lines: Vec<usize>,
+}
+
+impl LineIndex {
+    pub(crate) fn new(text: &str) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        let mut lines = vec![0];
+        for (idx, c) in chars.iter().enumerate() {
+            if *c == '\n' {
+                lines.push(idx + 1);
+            }
+        }
+        Self { chars, lines }
+    }
+
+    pub(crate) fn position(&self, index: usize) -> Value {
+        let index = index.min(self.chars.len());
+        let line = self.lines.partition_point(|start| *start <= index) - 1;
+        let character: usize = self.chars[self.lines[line]..index]
+            .iter()
+            .map(|c| c.len_utf16())
+            .sum();
+        json!({ "line": line, "character": character })
+    }
+
+    pub(crate) fn range(&self, span: &Span) -> Value {
+        json!({
+            "start": self.position(span.start.index.0),
+            "end": self.position(span.end.index.0),
+        })
+    }
+
+    pub(crate) fn index(&self, position: &Value) -> usize {
+        let line = position["line"].as_u64().unwrap_or(0) as usize;
+        let character = position["character"].as_u64().unwrap_or(0) as usize;
+
+        let Some(start) = self.lines.get(line).cloned() else {
+            return self.chars.len();
+        };
+
+        let mut index = start;
+        let mut column = 0;
+        while index < self.chars.len() && self.chars[index] != '\n' && column < character {
+            column += self.chars[index].len_utf16();
+            index += 1;
+        }
+        index
+    }
+
+    /// Text of the line up to the given character index
+    pub(crate) fn line_before(&self, index: usize) -> String {
+        let index = index.min(self.chars.len());
+        let line = self.lines.partition_point(|start| *start <= index) - 1;
+        self.chars[self.lines[line]..index].iter().collect()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Diagnostic {
+    pub(crate) span: Option<Span>,
+    pub(crate) message: String,
+}
+
+#[derive(Debug)]
+pub(crate) struct Document {
+    pub(crate) lines: LineIndex,
+    pub(crate) index: Option<index::Index>,
+    pub(crate) diagnostics: Vec<Diagnostic>,
+}
+
+impl Document {
+    pub(crate) fn new(text: &str) -> Self {
+        Self {
+            lines: LineIndex::new(text),
+            index: index::Index::from_str(text).ok(),
+            diagnostics: diagnose(text),
+        }
+    }
+
+    /// Keeps symbols of the previous version around while the document does not parse
+    pub(crate) fn update(self, text: &str) -> Self {
+        let mut result = Self::new(text);
+        if result.index.is_none() {
+            result.index = self.index;
+        }
+        result
+    }
+
+    pub(crate) fn diagnostics(&self) -> Value {
+        self.diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let range = match &diagnostic.span {
+                    Some(span) => self.lines.range(span),
+                    None => json!({
+                        "start": { "line": 0, "character": 0 },
+                        "end": { "line": 0, "character": 0 },
+                    }),
+                };
+                json!({
+                    "range": range,
+                    "severity": 1,
+                    "source": "elodie",
+                    "message": diagnostic.message,
+                })
+            })
+            .collect()
+    }
+}
+
+fn diagnose(text: &str) -> Vec<Diagnostic> {
+    let mut ctx = Context::new();
+    match catch_silently(|| ast_from_str(&mut ctx, text)) {
+        Ok(Ok(_)) => vec![],
+        Ok(Err(err)) => vec![diagnostic(&ctx, err, text)],
+        Err(message) => vec![Diagnostic {
+            span: None,
+            message: format!("Not supported yet: {message}"),
+        }],
+    }
+}
+
+fn diagnostic(ctx: &Context, err: frontend::Error, text: &str) -> Diagnostic {
+    let end_of_file = || {
+        let end = text.chars().count();
+        let position = Position::new(Row(0), Column(0), Index(end));
+        Some(Span::new(position.clone(), position))
+    };
+
+    match err {
+        frontend::Error::Lexer(err) => match err {
+            lex::Error::UnexpectedEndOfFile => Diagnostic {
+                span: end_of_file(),
+                message: "Unexpected end of file".to_string(),
+            },
+            lex::Error::UnknownKeyword(keyword) => Diagnostic {
+                span: None,
+                message: format!("Unknown keyword '{keyword}'"),
+            },
+            lex::Error::UnknownOperator(operator) => Diagnostic {
+                span: None,
+                message: format!("Unknown operator '{operator}'"),
+            },
+            lex::Error::UnknownSeparator(separator) => Diagnostic {
+                span: None,
+                message: format!("Unknown separator '{separator}'"),
+            },
+            lex::Error::UnknownCharacter(character, span) => Diagnostic {
+                span: Some(span),
+                message: format!("Unknown character '{character}'"),
+            },
+        },
+        frontend::Error::Parser(err) => match err {
+            parse::Error::InvalidIdentifier(token) => Diagnostic {
+                message: format!("Invalid identifier '{}'", ctx.get_str(token.value)),
+                span: Some(token.span),
+            },
+            parse::Error::InvalidType(token) => Diagnostic {
+                message: format!("Invalid type '{}'", ctx.get_str(token.value)),
+                span: Some(token.span),
+            },
+            parse::Error::UnexpectedEndOfFile => Diagnostic {
+                span: end_of_file(),
+                message: "Unexpected end of file".to_string(),
+            },
+            parse::Error::UnexpectedToken { expected, got } => Diagnostic {
+                message: format!(
+                    "Expected {expected:?} but got '{}'",
+                    ctx.get_str(got.value).escape_debug()
+                ),
+                span: Some(got.span),
+            },
+            parse::Error::UnsupportedNumber(number) => Diagnostic {
+                span: None,
+                message: format!("Unsupported number '{number}'"),
+            },
+            parse::Error::UnsupportedToken(token) => Diagnostic {
+                message: format!(
+                    "Unsupported token '{}'",
+                    ctx.get_str(token.value).escape_debug()
+                ),
+                span: Some(token.span),
+            },
+            parse::Error::UnknownType(token) => Diagnostic {
+                message: format!("Unknown type '{}'", ctx.get_str(token.value)),
+                span: Some(token.span),
+            },
+        },
+        frontend::Error::Ast(err) => Diagnostic {
+            message: err.to_string(),
+            span: match &err {
+                ast::Error::InvalidImportSource(span) => Some(span.clone()),
+                _ => None,
+            },
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::lsp::document::{Document, LineIndex};
+
+    #[test]
+    fn position_and_index_round_trip() {
+        let lines = LineIndex::new("let a = 1\nlet 'ü' = 2\n");
+        assert_eq!(lines.position(0), json!({"line": 0, "character": 0}));
+        assert_eq!(lines.position(14), json!({"line": 1, "character": 4}));
+        assert_eq!(lines.position(16), json!({"line": 1, "character": 6}));
+        assert_eq!(lines.index(&json!({"line": 1, "character": 6})), 16);
+        assert_eq!(lines.index(&json!({"line": 1, "character": 100})), 21);
+        assert_eq!(lines.index(&json!({"line": 7, "character": 0})), 22);
+        assert_eq!(lines.line_before(16), "let 'ü");
+    }
+
+    #[test]
+    fn valid_document_without_diagnostics() {
+        let document = Document::new("let value = 1\n");
+        assert!(document.diagnostics.is_empty());
+        assert!(document.index.is_some());
+    }
+
+    #[test]
+    fn parse_error_reported_at_token() {
+        let document = Document::new("let value = 1\nfunction 1()");
+        assert_eq!(document.diagnostics.len(), 1);
+        let diagnostics = document.diagnostics();
+        assert_eq!(
+            diagnostics[0]["range"]["start"],
+            json!({"line": 1, "character": 9})
+        );
+        assert_eq!(
+            diagnostics[0]["message"],
+            json!("Expected Identifier but got '1'")
+        );
+    }
+
+    #[test]
+    fn update_keeps_symbols_of_last_valid_version() {
+        let document = Document::new("let value = 1\n");
+        let document = document.update("function f(");
+        assert_eq!(document.diagnostics.len(), 1);
+        assert!(document.index.is_some());
+    }
+}
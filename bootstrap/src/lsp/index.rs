@@ -0,0 +1,957 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use crate::common::{Span, StringTableId};
+use crate::frontend;
+use crate::frontend::lex::lex;
+use crate::frontend::lex::token::Token;
+use crate::frontend::parse::{
+    parse, FromNode, FunctionDeclarationArgumentNode, FunctionDeclarationNode, InfixNode,
+    InfixOperator, LiteralNode, Node, TupleNode, TypeNode,
+};
+use crate::ir;
+use crate::ir::symbol::{Symbol, SymbolId, SymbolName, SymbolTable};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Parent {
+    Symbol(SymbolId),
+    Definition(usize),
+}
+
+#[derive(Debug)]
+pub(crate) struct Declaration {
+    pub(crate) symbol: SymbolId,
+    pub(crate) name: StringTableId,
+    pub(crate) span: Span,
+    pub(crate) range: Span,
+    pub(crate) detail: String,
+    pub(crate) r#type: Option<String>,
+    pub(crate) parent: Option<Parent>,
+    pub(crate) source: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Definition {
+    pub(crate) r#type: String,
+    pub(crate) span: Span,
+    pub(crate) range: Span,
+    pub(crate) parent: Option<Parent>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Reference {
+    pub(crate) symbol: SymbolId,
+    pub(crate) span: Span,
+}
+
+/// Segment of a package path like `std::io::println` whose root is not declared in the document
+#[derive(Debug)]
+pub(crate) struct PathReference {
+    pub(crate) span: Span,
+    pub(crate) path: Vec<String>,
+}
+
+/// Symbols declared and referenced in a single source file
+#[derive(Debug)]
+pub(crate) struct Index {
+    pub(crate) ctx: ir::Context,
+    pub(crate) declarations: Vec<Declaration>,
+    pub(crate) definitions: Vec<Definition>,
+    pub(crate) references: Vec<Reference>,
+    pub(crate) paths: Vec<PathReference>,
+}
+
+impl Index {
+    pub(crate) fn from_str(str: &str) -> frontend::Result<Self> {
+        let mut ctx = frontend::Context::new();
+        let tokens = lex(&mut ctx, str)?;
+        let nodes = parse(&mut ctx, tokens)?;
+
+        let mut builder = Builder {
+            index: Index {
+                ctx: ir::Context::new(ctx),
+                declarations: vec![],
+                definitions: vec![],
+                references: vec![],
+                paths: vec![],
+            },
+            scopes: vec![],
+            parents: vec![],
+            itself: vec![],
+            hoisted: HashMap::new(),
+            members: HashMap::new(),
+        };
+        builder.visit_block(&nodes);
+        Ok(builder.index)
+    }
+
+    pub(crate) fn declaration(&self, id: SymbolId) -> &Declaration {
+        &self.declarations[id.0 - 1]
+    }
+
+    pub(crate) fn symbol(&self, id: SymbolId) -> &Symbol {
+        &self.ctx.symbol_table[id]
+    }
+
+    pub(crate) fn name(&self, declaration: &Declaration) -> &str {
+        self.ctx.get_str(declaration.name)
+    }
+
+    pub(crate) fn children(&self, parent: Option<Parent>) -> impl Iterator<Item = &Declaration> {
+        self.declarations
+            .iter()
+            .filter(move |declaration| declaration.parent == parent)
+    }
+
+    pub(crate) fn reference_at(&self, index: usize) -> Option<&Reference> {
+        self.references
+            .iter()
+            .find(|reference| contains(&reference.span, index))
+    }
+
+    pub(crate) fn path_at(&self, index: usize) -> Option<&PathReference> {
+        self.paths.iter().find(|path| contains(&path.span, index))
+    }
+
+    pub(crate) fn references_of(&self, id: SymbolId) -> impl Iterator<Item = &Reference> {
+        self.references
+            .iter()
+            .filter(move |reference| reference.symbol == id)
+    }
+
+    pub(crate) fn methods(&self) -> impl Iterator<Item = (&str, &Declaration)> {
+        self.declarations
+            .iter()
+            .filter_map(|declaration| match declaration.parent {
+                Some(Parent::Definition(idx)) => {
+                    Some((self.definitions[idx].r#type.as_str(), declaration))
+                }
+                _ => None,
+            })
+    }
+}
+
+fn contains(span: &Span, index: usize) -> bool {
+    span.start.index.0 <= index && index <= span.end.index.0
+}
+
+enum PathTarget {
+    Local(SymbolId),
+    Library(Vec<String>),
+    Unknown,
+}
+
+struct Builder {
+    index: Index,
+    scopes: Vec<HashMap<StringTableId, SymbolId>>,
+    parents: Vec<Parent>,
+    itself: Vec<String>,
+    hoisted: HashMap<usize, SymbolId>,
+    members: HashMap<SymbolId, HashMap<StringTableId, SymbolId>>,
+}
+
+impl Builder {
+    fn visit_block(&mut self, nodes: &[Node]) {
+        self.scopes.push(HashMap::new());
+        self.hoist(nodes);
+        for node in nodes {
+            self.visit(node);
+        }
+        self.scopes.pop();
+    }
+
+    // functions, types, packages and definitions can be used before they are declared
+    fn hoist(&mut self, nodes: &[Node]) {
+        for node in nodes {
+            match node {
+                Node::ExternalFunctionDeclaration(node) => {
+                    let detail = format!(
+                        "external {}",
+                        self.function_signature(
+                            &node.identifier.0,
+                            &node.arguments,
+                            node.return_type.as_deref()
+                        )
+                    );
+                    let r#type = node.return_type.as_deref().map(|t| self.type_name(t));
+                    let id = self.register(
+                        SymbolTable::register_function,
+                        &node.identifier.0,
+                        &node.token,
+                        detail,
+                        r#type,
+                    );
+                    self.hoisted
+                        .insert(node.identifier.0.span.start.index.0, id);
+                }
+                Node::FunctionDeclaration(node) => {
+                    self.hoist_function(node);
+                }
+                Node::From(FromNode::Export(node)) => {
+                    let (Node::Literal(LiteralNode::String(source)), Node::Identifier(identifier)) =
+                        (node.from_node.deref(), node.what_node.deref())
+                    else {
+                        continue;
+                    };
+                    let source = self.index.ctx.get_str(source.value()).to_string();
+                    let detail = format!(
+                        "from '{source}' export {}",
+                        self.index.ctx.get_str(identifier.value())
+                    );
+                    let id = self.register(
+                        SymbolTable::register_package,
+                        &identifier.0,
+                        &node.token,
+                        detail,
+                        None,
+                    );
+                    self.index.declarations[id.0 - 1].source = Some(source);
+                    self.hoisted.insert(identifier.0.span.start.index.0, id);
+                }
+                Node::PackageDeclaration(node) => {
+                    let detail = format!("package {}", self.str(&node.identifier.0));
+                    let id = self.register(
+                        SymbolTable::register_package,
+                        &node.identifier.0,
+                        &node.token,
+                        detail,
+                        None,
+                    );
+                    self.hoisted
+                        .insert(node.identifier.0.span.start.index.0, id);
+
+                    self.scopes.push(HashMap::new());
+                    self.parents.push(Parent::Symbol(id));
+                    self.hoist(&node.block.nodes);
+                    self.parents.pop();
+                    let members = self.scopes.pop().unwrap();
+                    self.members.insert(id, members);
+                }
+                Node::TypeDeclaration(node) => {
+                    let name = self.str(&node.identifier.0).to_string();
+                    let detail = format!("type {name}{}", self.properties(&node.properties));
+                    let id = self.register(
+                        SymbolTable::register_type,
+                        &node.identifier.0,
+                        &node.token,
+                        detail,
+                        Some(name),
+                    );
+                    self.hoisted
+                        .insert(node.identifier.0.span.start.index.0, id);
+
+                    self.parents.push(Parent::Symbol(id));
+                    self.scopes.push(HashMap::new());
+                    for property in &node.properties.nodes {
+                        let Node::Infix(property) = property else {
+                            continue;
+                        };
+                        let (Node::Identifier(identifier), InfixOperator::TypeAscription(_)) =
+                            (property.left.deref(), &property.operator)
+                        else {
+                            continue;
+                        };
+                        let r#type = match property.right.deref() {
+                            Node::Type(r#type) => Some(self.type_name(r#type)),
+                            _ => None,
+                        };
+                        let detail = format!(
+                            "{}: {}",
+                            self.str(&identifier.0),
+                            r#type.clone().unwrap_or_default()
+                        );
+                        let id = self.register(
+                            SymbolTable::register_variable,
+                            &identifier.0,
+                            &identifier.0,
+                            detail,
+                            r#type,
+                        );
+                        self.hoisted.insert(identifier.0.span.start.index.0, id);
+                    }
+                    self.scopes.pop();
+                    self.parents.pop();
+                }
+                Node::DefineDeclaration(node) => {
+                    let definition = self.index.definitions.len();
+                    self.index.definitions.push(Definition {
+                        r#type: self.str(&node.identifier.0).to_string(),
+                        span: node.identifier.0.span.clone(),
+                        range: Span::new(
+                            node.token.span.start.clone(),
+                            node.identifier.0.span.end.clone(),
+                        ),
+                        parent: self.parents.last().cloned(),
+                    });
+
+                    self.parents.push(Parent::Definition(definition));
+                    self.scopes.push(HashMap::new());
+                    for node in &node.block.nodes {
+                        if let Node::FunctionDeclaration(node) = node {
+                            self.hoist_function(node);
+                        }
+                    }
+                    self.scopes.pop();
+                    self.parents.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn hoist_function(&mut self, node: &FunctionDeclarationNode) {
+        let detail = self.function_signature(
+            &node.identifier.0,
+            &node.arguments,
+            node.return_type.as_deref(),
+        );
+        let r#type = node.return_type.as_deref().map(|t| self.type_name(t));
+        let id = self.register(
+            SymbolTable::register_function,
+            &node.identifier.0,
+            &node.token,
+            detail,
+            r#type,
+        );
+        self.hoisted
+            .insert(node.identifier.0.span.start.index.0, id);
+    }
+
+    fn visit(&mut self, node: &Node) {
+        match node {
+            Node::Block(node) => self.visit_block(&node.nodes),
+            Node::Break(node) => {
+                if let Some(result) = &node.result {
+                    self.visit(result)
+                }
+            }
+            Node::Call(node) => {
+                self.visit(&node.callee);
+                for argument in &node.arguments {
+                    self.visit(&argument.node)
+                }
+            }
+            Node::Continue(_)
+            | Node::From(_)
+            | Node::Import(_)
+            | Node::Itself(_)
+            | Node::Literal(_)
+            | Node::Nop => {}
+            Node::ExternalFunctionDeclaration(node) => {
+                for argument in &node.arguments {
+                    self.visit_optional_type(argument.r#type.as_deref());
+                }
+                self.visit_optional_type(node.return_type.as_deref());
+            }
+            Node::FunctionDeclaration(node) => self.visit_function(node),
+            Node::DefineDeclaration(node) => {
+                let r#type = self.str(&node.identifier.0).to_string();
+                if let Some(id) = self.resolve(node.identifier.value()) {
+                    self.reference(id, &node.identifier.0);
+                }
+                self.itself.push(r#type);
+                for node in &node.block.nodes {
+                    self.visit(node);
+                }
+                self.itself.pop();
+            }
+            Node::Identifier(identifier) => {
+                if let Some(id) = self.resolve(identifier.value()) {
+                    self.reference(id, &identifier.0);
+                }
+            }
+            Node::If(node) => {
+                self.visit(&node.condition);
+                self.visit_block(&node.then.nodes);
+                if let Some(otherwise) = &node.otherwise {
+                    self.visit_block(&otherwise.block.nodes);
+                }
+            }
+            Node::Infix(node) => match &node.operator {
+                InfixOperator::AccessPackage(_) => {
+                    self.visit_package_access(node);
+                }
+                InfixOperator::AccessProperty(_) => {
+                    self.visit(&node.left);
+                    if let Node::Identifier(property) = node.right.deref() {
+                        let owner = self.infer(&node.left);
+                        if let Some(id) = self.resolve_member(owner.as_deref(), property.value()) {
+                            self.reference(id, &property.0);
+                        }
+                    } else {
+                        self.visit(&node.right);
+                    }
+                }
+                InfixOperator::Call(_) => {
+                    let callee = self.resolve_node(&node.left);
+                    self.visit(&node.left);
+                    if let Node::Tuple(arguments) = node.right.deref() {
+                        self.visit_arguments(callee, arguments);
+                    } else {
+                        self.visit(&node.right);
+                    }
+                }
+                InfixOperator::LambdaCall(_) => {
+                    self.visit(&node.left);
+                    if let Node::Block(block) = node.right.deref() {
+                        self.visit_block(&block.nodes);
+                    }
+                }
+                _ => {
+                    self.visit(&node.left);
+                    self.visit(&node.right);
+                }
+            },
+            Node::Loop(node) => self.visit_block(&node.block.nodes),
+            Node::PackageDeclaration(node) => {
+                let Some(id) = self.hoisted(&node.identifier.0) else {
+                    return;
+                };
+                let members = self.members.get(&id).cloned().unwrap_or_default();
+                self.scopes.push(members);
+                self.parents.push(Parent::Symbol(id));
+                for node in &node.block.nodes {
+                    self.visit(node);
+                }
+                self.parents.pop();
+                self.scopes.pop();
+            }
+            Node::Prefix(node) => self.visit(&node.node),
+            Node::Return(node) => {
+                if let Some(result) = &node.result {
+                    self.visit(result)
+                }
+            }
+            Node::StringInterpolation(node) => {
+                for node in &node.nodes {
+                    self.visit(node)
+                }
+            }
+            Node::Tuple(node) => {
+                for node in &node.nodes {
+                    self.visit(node)
+                }
+            }
+            Node::Type(node) => self.visit_type(node),
+            Node::TypeDeclaration(node) => {
+                for property in &node.properties.nodes {
+                    if let Node::Infix(property) = property {
+                        self.visit(&property.right);
+                    }
+                }
+            }
+            Node::VariableDeclaration(node) => {
+                self.visit(&node.node);
+                self.visit_optional_type(node.r#type.as_ref());
+
+                let r#type = match &node.r#type {
+                    Some(r#type) => Some(self.type_name(r#type)),
+                    None => self.infer(&node.node),
+                };
+                let detail = match &r#type {
+                    Some(r#type) => format!("let {}: {type}", self.str(&node.identifier.0)),
+                    None => format!("let {}", self.str(&node.identifier.0)),
+                };
+                self.register(
+                    SymbolTable::register_variable,
+                    &node.identifier.0,
+                    &node.token,
+                    detail,
+                    r#type,
+                );
+            }
+        }
+    }
+
+    fn visit_function(&mut self, node: &FunctionDeclarationNode) {
+        let Some(id) = self.hoisted(&node.identifier.0) else {
+            return;
+        };
+        self.visit_optional_type(node.return_type.as_deref());
+
+        self.scopes.push(HashMap::new());
+        self.parents.push(Parent::Symbol(id));
+        for argument in &node.arguments {
+            self.visit_optional_type(argument.r#type.as_deref());
+            let r#type = argument.r#type.as_deref().map(|t| self.type_name(t));
+            let detail = match &r#type {
+                Some(r#type) => format!("{}: {type}", self.str(&argument.identifier.0)),
+                None => self.str(&argument.identifier.0).to_string(),
+            };
+            self.register(
+                SymbolTable::register_argument,
+                &argument.identifier.0,
+                &argument.identifier.0,
+                detail,
+                r#type,
+            );
+        }
+        self.visit_block(&node.block.nodes);
+        self.parents.pop();
+        self.scopes.pop();
+    }
+
+    fn visit_arguments(&mut self, callee: Option<SymbolId>, arguments: &TupleNode) {
+        for argument in &arguments.nodes {
+            if let Node::Infix(named) = argument {
+                if let (InfixOperator::Assign(_), Node::Identifier(identifier)) =
+                    (&named.operator, named.left.deref())
+                {
+                    let parameter = callee.and_then(|callee| {
+                        self.index
+                            .children(Some(Parent::Symbol(callee)))
+                            .find(|declaration| declaration.name == identifier.value())
+                            .map(|declaration| declaration.symbol)
+                    });
+                    if let Some(parameter) = parameter {
+                        self.reference(parameter, &identifier.0);
+                    }
+                    self.visit(&named.right);
+                    continue;
+                }
+            }
+            self.visit(argument);
+        }
+    }
+
+    fn visit_path(&mut self, node: &Node) -> PathTarget {
+        match node {
+            Node::Identifier(identifier) => match self.resolve(identifier.value()) {
+                Some(id) => {
+                    self.reference(id, &identifier.0);
+                    PathTarget::Local(id)
+                }
+                None => {
+                    let path = vec![self.str(&identifier.0).to_string()];
+                    self.index.paths.push(PathReference {
+                        span: identifier.0.span.clone(),
+                        path: path.clone(),
+                    });
+                    PathTarget::Library(path)
+                }
+            },
+            Node::Infix(node) if matches!(node.operator, InfixOperator::AccessPackage(_)) => {
+                self.visit_package_access(node)
+            }
+            node => {
+                self.visit(node);
+                PathTarget::Unknown
+            }
+        }
+    }
+
+    fn visit_package_access(&mut self, node: &InfixNode) -> PathTarget {
+        let left = self.visit_path(&node.left);
+        let Node::Identifier(identifier) = node.right.deref() else {
+            self.visit(&node.right);
+            return PathTarget::Unknown;
+        };
+        match left {
+            PathTarget::Local(package) => {
+                let member = self
+                    .members
+                    .get(&package)
+                    .and_then(|members| members.get(&identifier.value()))
+                    .cloned();
+                match member {
+                    Some(id) => {
+                        self.reference(id, &identifier.0);
+                        PathTarget::Local(id)
+                    }
+                    None => PathTarget::Unknown,
+                }
+            }
+            PathTarget::Library(mut path) => {
+                path.push(self.str(&identifier.0).to_string());
+                self.index.paths.push(PathReference {
+                    span: identifier.0.span.clone(),
+                    path: path.clone(),
+                });
+                PathTarget::Library(path)
+            }
+            PathTarget::Unknown => PathTarget::Unknown,
+        }
+    }
+
+    fn visit_optional_type(&mut self, node: Option<&TypeNode>) {
+        if let Some(node) = node {
+            self.visit_type(node)
+        }
+    }
+
+    fn visit_type(&mut self, node: &TypeNode) {
+        match node {
+            TypeNode::Object(node) => {
+                if let Some(id) = self.resolve(node.token.value) {
+                    self.reference(id, &node.token);
+                }
+            }
+            TypeNode::Function(node) => {
+                for argument in &node.arguments {
+                    self.visit_type(&argument.r#type);
+                }
+                self.visit_optional_type(node.return_type.as_deref());
+            }
+            TypeNode::Boolean(_) | TypeNode::Number(_) | TypeNode::String(_) => {}
+        }
+    }
+
+    fn register(
+        &mut self,
+        register: fn(&mut SymbolTable, SymbolName) -> SymbolId,
+        identifier: &Token,
+        start: &Token,
+        detail: String,
+        r#type: Option<String>,
+    ) -> SymbolId {
+        let id = register(
+            &mut self.index.ctx.symbol_table,
+            SymbolName(identifier.value),
+        );
+        self.index.declarations.push(Declaration {
+            symbol: id,
+            name: identifier.value,
+            span: identifier.span.clone(),
+            range: Span::new(start.span.start.clone(), identifier.span.end.clone()),
+            detail,
+            r#type,
+            parent: self.parents.last().cloned(),
+            source: None,
+        });
+        self.reference(id, identifier);
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(identifier.value, id);
+        }
+        id
+    }
+
+    fn reference(&mut self, id: SymbolId, token: &Token) {
+        let already_known = self
+            .index
+            .references
+            .iter()
+            .any(|reference| reference.span == token.span);
+        if !already_known {
+            self.index.references.push(Reference {
+                symbol: id,
+                span: token.span.clone(),
+            });
+        }
+    }
+
+    fn hoisted(&self, identifier: &Token) -> Option<SymbolId> {
+        self.hoisted.get(&identifier.span.start.index.0).cloned()
+    }
+
+    fn resolve(&self, name: StringTableId) -> Option<SymbolId> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&name).cloned())
+    }
+
+    fn resolve_node(&self, node: &Node) -> Option<SymbolId> {
+        match node {
+            Node::Identifier(identifier) => self.resolve(identifier.value()),
+            Node::Type(TypeNode::Object(node)) => self.resolve(node.token.value),
+            Node::Infix(node) => match &node.operator {
+                InfixOperator::AccessPackage(_) => {
+                    let package = self.resolve_node(&node.left)?;
+                    let Node::Identifier(identifier) = node.right.deref() else {
+                        return None;
+                    };
+                    self.members
+                        .get(&package)?
+                        .get(&identifier.value())
+                        .cloned()
+                }
+                InfixOperator::AccessProperty(_) => {
+                    let Node::Identifier(identifier) = node.right.deref() else {
+                        return None;
+                    };
+                    let owner = self.infer(&node.left);
+                    self.resolve_member(owner.as_deref(), identifier.value())
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn resolve_member(&self, owner: Option<&str>, name: StringTableId) -> Option<SymbolId> {
+        let index = &self.index;
+        if let Some(owner) = owner {
+            let property = index
+                .declarations
+                .iter()
+                .filter(|declaration| {
+                    matches!(index.symbol(declaration.symbol), Symbol::Type { .. })
+                        && index.name(declaration) == owner
+                })
+                .flat_map(|r#type| index.children(Some(Parent::Symbol(r#type.symbol))))
+                .find(|declaration| declaration.name == name);
+            if let Some(property) = property {
+                return Some(property.symbol);
+            }
+
+            let method = index
+                .methods()
+                .find(|(r#type, method)| *r#type == owner && method.name == name);
+            if let Some((_, method)) = method {
+                return Some(method.symbol);
+            }
+        }
+
+        // all definitions share the same namespace at runtime
+        index
+            .methods()
+            .find(|(_, method)| method.name == name)
+            .map(|(_, method)| method.symbol)
+    }
+
+    fn infer(&self, node: &Node) -> Option<String> {
+        match node {
+            Node::Literal(LiteralNode::Boolean(_)) => Some("Bool".to_string()),
+            Node::Literal(LiteralNode::Number(_)) => Some("Number".to_string()),
+            Node::Literal(LiteralNode::String(_)) => Some("String".to_string()),
+            Node::StringInterpolation(_) => Some("String".to_string()),
+            Node::Itself(_) => self.itself.last().cloned(),
+            Node::Identifier(_) => self
+                .resolve_node(node)
+                .and_then(|id| self.index.declaration(id).r#type.clone()),
+            Node::Infix(infix) => match &infix.operator {
+                InfixOperator::Call(_) => match infix.left.deref() {
+                    Node::Type(TypeNode::Object(r#type)) => {
+                        Some(self.str(&r#type.token).to_string())
+                    }
+                    left => self
+                        .resolve_node(left)
+                        .and_then(|id| self.index.declaration(id).r#type.clone()),
+                },
+                InfixOperator::AccessProperty(_) => self
+                    .resolve_node(node)
+                    .and_then(|id| self.index.declaration(id).r#type.clone()),
+                InfixOperator::Equal(_)
+                | InfixOperator::NotEqual(_)
+                | InfixOperator::LessThan(_)
+                | InfixOperator::LessThanOrEqual(_)
+                | InfixOperator::GreaterThan(_)
+                | InfixOperator::GreaterThanOrEqual(_) => Some("Bool".to_string()),
+                InfixOperator::Add(_)
+                | InfixOperator::Subtract(_)
+                | InfixOperator::Multiply(_)
+                | InfixOperator::Divide(_)
+                | InfixOperator::Modulo(_) => self.infer(&infix.left),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn function_signature(
+        &self,
+        identifier: &Token,
+        arguments: &[FunctionDeclarationArgumentNode],
+        return_type: Option<&TypeNode>,
+    ) -> String {
+        let arguments = arguments
+            .iter()
+            .map(|argument| match &argument.r#type {
+                Some(r#type) => format!(
+                    "{}: {}",
+                    self.str(&argument.identifier.0),
+                    self.type_name(r#type)
+                ),
+                None => self.str(&argument.identifier.0).to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        match return_type {
+            Some(return_type) => format!(
+                "function {}({arguments}) -> {}",
+                self.str(identifier),
+                self.type_name(return_type)
+            ),
+            None => format!("function {}({arguments})", self.str(identifier)),
+        }
+    }
+
+    fn properties(&self, properties: &TupleNode) -> String {
+        let properties = properties
+            .nodes
+            .iter()
+            .filter_map(|property| match property {
+                Node::Infix(property) => match (property.left.deref(), property.right.deref()) {
+                    (Node::Identifier(identifier), Node::Type(r#type)) => Some(format!(
+                        "{}: {}",
+                        self.str(&identifier.0),
+                        self.type_name(r#type)
+                    )),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("({properties})")
+    }
+
+    fn type_name(&self, node: &TypeNode) -> String {
+        match node {
+            TypeNode::Boolean(_) => "Bool".to_string(),
+            TypeNode::Number(_) => "Number".to_string(),
+            TypeNode::String(_) => "String".to_string(),
+            TypeNode::Object(node) => self.str(&node.token).to_string(),
+            TypeNode::Function(node) => {
+                let arguments = node
+                    .arguments
+                    .iter()
+                    .map(|argument| self.type_name(&argument.r#type))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                match &node.return_type {
+                    Some(return_type) => {
+                        format!("function({arguments}) -> {}", self.type_name(return_type))
+                    }
+                    None => format!("function({arguments})"),
+                }
+            }
+        }
+    }
+
+    fn str(&self, token: &Token) -> &str {
+        self.index.ctx.get_str(token.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::frontend;
+    use crate::frontend::lex::lex;
+    use crate::frontend::parse::parse;
+    use crate::ir::symbol::Symbol;
+    use crate::lsp::index::{Index, Parent};
+
+    #[test]
+    fn registers_declarations_in_symbol_table() {
+        let index =
+            Index::from_str("let a = 1\nfunction f(b) { }\ntype T()\npackage p { }").unwrap();
+        let symbols: Vec<(&str, &str)> = index
+            .declarations
+            .iter()
+            .map(|declaration| {
+                let kind = match index.symbol(declaration.symbol) {
+                    Symbol::Argument { .. } => "argument",
+                    Symbol::Function { .. } => "function",
+                    Symbol::Package { .. } => "package",
+                    Symbol::Type { .. } => "type",
+                    Symbol::Variable { .. } => "variable",
+                };
+                (index.name(declaration), kind)
+            })
+            .collect();
+        assert_eq!(
+            symbols,
+            vec![
+                ("f", "function"),
+                ("T", "type"),
+                ("p", "package"),
+                ("a", "variable"),
+                ("b", "argument"),
+            ]
+        );
+    }
+
+    #[test]
+    fn infers_variable_types() {
+        let index = Index::from_str(
+            "type Point()\nfunction f() -> Bool { return true }\nlet a = 1\nlet b = 'text ${a}'\nlet c = Point()\nlet d = f()\nlet e = a + 1\nlet g = a < 2\nlet h: Number = a",
+        )
+        .unwrap();
+        let types: Vec<Option<&str>> = index
+            .children(None)
+            .filter(|declaration| {
+                matches!(index.symbol(declaration.symbol), Symbol::Variable { .. })
+            })
+            .map(|declaration| declaration.r#type.as_deref())
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                Some("Number"),
+                Some("String"),
+                Some("Point"),
+                Some("Bool"),
+                Some("Number"),
+                Some("Bool"),
+                Some("Number"),
+            ]
+        );
+    }
+
+    #[test]
+    fn named_arguments_refer_to_properties() {
+        let index = Index::from_str("type Point(x: Number)\nlet p = Point(x = 1)").unwrap();
+        let x = index
+            .children(Some(Parent::Symbol(index.declarations[0].symbol)))
+            .next()
+            .unwrap();
+        assert_eq!(index.references_of(x.symbol).count(), 2);
+    }
+
+    #[test]
+    fn unresolved_package_paths_are_recorded() {
+        let index = Index::from_str("std::io::println('Elodie')").unwrap();
+        let paths: Vec<Vec<String>> = index.paths.iter().map(|path| path.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                vec!["std".to_string()],
+                vec!["std".to_string(), "io".to_string()],
+                vec!["std".to_string(), "io".to_string(), "println".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn indexes_every_file_in_repository() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+        let mut files = vec![];
+        for dir in ["src", "test"] {
+            let mut pending = vec![root.join(dir)];
+            while let Some(path) = pending.pop() {
+                if path.is_dir() {
+                    pending.extend(
+                        fs::read_dir(&path)
+                            .unwrap()
+                            .map(|entry| entry.unwrap().path()),
+                    );
+                } else if path.extension().is_some_and(|extension| extension == "ec") {
+                    files.push(path);
+                }
+            }
+        }
+        assert!(!files.is_empty());
+
+        for file in files {
+            let content = fs::read_to_string(&file).unwrap();
+            let mut ctx = frontend::Context::new();
+            let parses = lex(&mut ctx, &content)
+                .ok()
+                .and_then(|tokens| parse(&mut ctx, tokens).ok())
+                .is_some();
+
+            let index = Index::from_str(&content);
+            assert_eq!(
+                index.is_ok(),
+                parses,
+                "{} can not be indexed",
+                file.display()
+            );
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::ir::symbol::Symbol;
+use crate::lsp::document::LineIndex;
+use crate::lsp::index::{Declaration, Index, Parent};
+
+#[derive(Debug)]
+pub(crate) struct Item {
+    pub(crate) name: String,
+    pub(crate) detail: String,
+    pub(crate) location: Value,
+}
+
+#[derive(Debug)]
+pub(crate) struct Package {
+    pub(crate) item: Item,
+    pub(crate) packages: Vec<Package>,
+    pub(crate) functions: Vec<Item>,
+}
+
+pub(crate) enum Member<'a> {
+    Package(&'a Package),
+    Function(&'a Item),
+}
+
+impl Member<'_> {
+    pub(crate) fn item(&self) -> &Item {
+        match self {
+            Member::Package(package) => &package.item,
+            Member::Function(function) => function,
+        }
+    }
+}
+
+/// Packages of core and std, which every source file can access without importing them
+#[derive(Debug)]
+pub(crate) struct Library {
+    pub(crate) packages: Vec<Package>,
+    pub(crate) methods: Vec<(String, Item)>,
+}
+
+impl Library {
+    pub(crate) fn load() -> Self {
+        Self::load_with(&|file| {
+            let content = crate::load_library_file(file).ok()?;
+            let uri = format!("file://{}", crate::library_file_path(file).display());
+            Some((uri, content))
+        })
+    }
+
+    /// Loads the library by reading files relative to the library root, returning their uri and content
+    pub(crate) fn load_with(read: &dyn Fn(&str) -> Option<(String, String)>) -> Self {
+        let mut result = Self {
+            packages: vec![],
+            methods: vec![],
+        };
+        for file in ["core/index.ec", "std/index.ec"] {
+            let packages = result.load_file(read, file);
+            result.packages.extend(packages);
+        }
+        result
+    }
+
+    pub(crate) fn find(&self, path: &[String]) -> Option<Member<'_>> {
+        let (name, parents) = path.split_last()?;
+        if parents.is_empty() {
+            return self
+                .packages
+                .iter()
+                .find(|package| &package.item.name == name)
+                .map(Member::Package);
+        }
+
+        let Some(Member::Package(parent)) = self.find(parents) else {
+            return None;
+        };
+        parent
+            .packages
+            .iter()
+            .find(|package| &package.item.name == name)
+            .map(Member::Package)
+            .or_else(|| {
+                parent
+                    .functions
+                    .iter()
+                    .find(|function| &function.name == name)
+                    .map(Member::Function)
+            })
+    }
+
+    pub(crate) fn members(&self, path: &[String]) -> Vec<Member<'_>> {
+        let packages = if path.is_empty() {
+            &self.packages
+        } else {
+            match self.find(path) {
+                Some(Member::Package(package)) => {
+                    let mut result: Vec<Member> =
+                        package.packages.iter().map(Member::Package).collect();
+                    result.extend(package.functions.iter().map(Member::Function));
+                    return result;
+                }
+                _ => return vec![],
+            }
+        };
+        packages.iter().map(Member::Package).collect()
+    }
+
+    fn load_file(
+        &mut self,
+        read: &dyn Fn(&str) -> Option<(String, String)>,
+        file: &str,
+    ) -> Vec<Package> {
+        let Some((uri, content)) = read(file) else {
+            return vec![];
+        };
+        let Ok(index) = Index::from_str(&content) else {
+            return vec![];
+        };
+        let lines = LineIndex::new(&content);
+
+        for (r#type, method) in index.methods() {
+            self.methods
+                .push((r#type.to_string(), item(&index, &lines, &uri, method)));
+        }
+
+        let packages: Vec<&Declaration> = index
+            .children(None)
+            .filter(|declaration| {
+                matches!(index.symbol(declaration.symbol), Symbol::Package { .. })
+            })
+            .collect();
+
+        packages
+            .into_iter()
+            .map(|declaration| self.package(read, file, &index, &lines, &uri, declaration))
+            .collect()
+    }
+
+    fn package(
+        &mut self,
+        read: &dyn Fn(&str) -> Option<(String, String)>,
+        file: &str,
+        index: &Index,
+        lines: &LineIndex,
+        uri: &str,
+        declaration: &Declaration,
+    ) -> Package {
+        let name = index.name(declaration);
+
+        if let Some(source) = &declaration.source {
+            let path = Path::new(file)
+                .parent()
+                .unwrap_or(Path::new(""))
+                .join(source.trim_start_matches("./"))
+                .join("index.ec");
+            let exported = self
+                .load_file(read, &path.to_string_lossy())
+                .into_iter()
+                .find(|package| package.item.name == name);
+            if let Some(package) = exported {
+                return package;
+            }
+        }
+
+        let mut packages = vec![];
+        let mut functions = vec![];
+        for child in index.children(Some(Parent::Symbol(declaration.symbol))) {
+            match index.symbol(child.symbol) {
+                Symbol::Package { .. } => {
+                    packages.push(self.package(read, file, index, lines, uri, child))
+                }
+                Symbol::Function { .. } => functions.push(item(index, lines, uri, child)),
+                _ => {}
+            }
+        }
+
+        Package {
+            item: item(index, lines, uri, declaration),
+            packages,
+            functions,
+        }
+    }
+}
+
+fn item(index: &Index, lines: &LineIndex, uri: &str, declaration: &Declaration) -> Item {
+    Item {
+        name: index.name(declaration).to_string(),
+        detail: declaration.detail.clone(),
+        location: json!({ "uri": uri, "range": lines.range(&declaration.span) }),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::HashMap;
+
+    use crate::lsp::library::{Library, Member};
+
+    pub(crate) fn test_library() -> Library {
+        let files = HashMap::from([
+            (
+                "core/index.ec",
+                "export package core {\n  export package intrinsics {\n    export external function print(s: String)\n  }\n}",
+            ),
+            (
+                "std/index.ec",
+                "export package std {\n  from './io' export io\n}",
+            ),
+            (
+                "std/io/index.ec",
+                "export package io {\n  export function println(s: String) {\n    print(s)\n  }\n}\nexport define String {\n  function length() -> Number {\n    return 1\n  }\n}",
+            ),
+        ]);
+        Library::load_with(&|file| {
+            files
+                .get(file)
+                .map(|content| (format!("file:///lib/{file}"), content.to_string()))
+        })
+    }
+
+    #[test]
+    fn resolves_exported_packages() {
+        let library = test_library();
+        let path = vec!["std".to_string(), "io".to_string(), "println".to_string()];
+        let Some(Member::Function(function)) = library.find(&path) else {
+            panic!()
+        };
+        assert_eq!(function.detail, "function println(s: String)");
+        assert_eq!(function.location["uri"], "file:///lib/std/io/index.ec");
+        assert_eq!(function.location["range"]["start"]["line"], 1);
+    }
+
+    #[test]
+    fn members_of_package() {
+        let library = test_library();
+        let names: Vec<String> = library
+            .members(&["core".to_string(), "intrinsics".to_string()])
+            .iter()
+            .map(|member| member.item().name.clone())
+            .collect();
+        assert_eq!(names, vec!["print"]);
+
+        let roots: Vec<String> = library
+            .members(&[])
+            .iter()
+            .map(|member| member.item().name.clone())
+            .collect();
+        assert_eq!(roots, vec!["core", "std"]);
+    }
+
+    #[test]
+    fn collects_methods_of_definitions() {
+        let library = test_library();
+        assert_eq!(library.methods.len(), 1);
+        assert_eq!(library.methods[0].0, "String");
+        assert_eq!(library.methods[0].1.detail, "function length() -> Number");
+    }
+}
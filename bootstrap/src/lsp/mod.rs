@@ -0,0 +1,842 @@
+use std::collections::HashMap;
+use std::io;
+use std::io::{BufRead, Write};
+use std::process::exit;
+
+use regex::Regex;
+use serde_json::{json, Value};
+
+use crate::common::panic::catch_silently;
+use crate::ir::symbol::Symbol;
+use crate::lsp::document::Document;
+use crate::lsp::index::{Index, Parent};
+use crate::lsp::library::{Library, Member};
+use crate::lsp::rpc::{read_message, write_message};
+
+mod document;
+mod index;
+mod library;
+mod rpc;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Json(serde_json::Error),
+    MissingContentLength,
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+pub(crate) type Result<T, E = Error> = core::result::Result<T, E>;
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_REQUEST: i64 = -32600;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// Runs the language server on stdin / stdout until the client sends exit
+pub fn serve() {
+    let mut server = Server::new(Library::load());
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    match server.serve(&mut stdin.lock(), &mut stdout.lock()) {
+        Ok(_) => exit(if server.shutdown { 0 } else { 1 }),
+        Err(Error::Io(err)) => {
+            eprintln!("Language server failed - {err}");
+            exit(1)
+        }
+        Err(Error::Json(err)) => {
+            eprintln!("Language server received invalid message - {err}");
+            exit(1)
+        }
+        Err(Error::MissingContentLength) => {
+            eprintln!("Language server received message without Content-Length");
+            exit(1)
+        }
+    }
+}
+
+pub(crate) struct Server {
+    documents: HashMap<String, Document>,
+    library: Library,
+    shutdown: bool,
+    exit: bool,
+}
+
+impl Server {
+    pub(crate) fn new(library: Library) -> Self {
+        Self {
+            documents: HashMap::new(),
+            library,
+            shutdown: false,
+            exit: false,
+        }
+    }
+
+    pub(crate) fn serve(
+        &mut self,
+        reader: &mut impl BufRead,
+        writer: &mut impl Write,
+    ) -> Result<()> {
+        while !self.exit {
+            let Some(message) = read_message(reader)? else {
+                return Ok(());
+            };
+            for response in self.handle(&message) {
+                write_message(writer, &response)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// A message which panics gets answered with an internal error, the server keeps running
+    pub(crate) fn handle(&mut self, message: &Value) -> Vec<Value> {
+        guard(message, || self.dispatch(message))
+    }
+
+    fn dispatch(&mut self, message: &Value) -> Vec<Value> {
+        let Some(method) = message["method"].as_str() else {
+            // responses to requests of the server are not expected
+            return vec![];
+        };
+        let params = &message["params"];
+
+        let Some(id) = message.get("id") else {
+            return self.notify(method, params);
+        };
+
+        if self.shutdown {
+            return vec![error(id, INVALID_REQUEST, "Server is shutting down")];
+        }
+
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "definitionProvider": true,
+                    "referencesProvider": true,
+                    "hoverProvider": true,
+                    "completionProvider": { "triggerCharacters": [":", "."] },
+                    "documentSymbolProvider": true,
+                },
+                "serverInfo": { "name": "elodie" },
+            }),
+            "shutdown" => {
+                self.shutdown = true;
+                Value::Null
+            }
+            "textDocument/definition" => self.definition(params),
+            "textDocument/references" => self.references(params),
+            "textDocument/hover" => self.hover(params),
+            "textDocument/completion" => self.completion(params),
+            "textDocument/documentSymbol" => self.document_symbols(params),
+            _ => {
+                return vec![error(
+                    id,
+                    METHOD_NOT_FOUND,
+                    &format!("Method not found - {method}"),
+                )]
+            }
+        };
+
+        vec![json!({ "jsonrpc": "2.0", "id": id, "result": result })]
+    }
+
+    fn notify(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        match method {
+            "exit" => {
+                self.exit = true;
+                vec![]
+            }
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.clone(), Document::new(text));
+                vec![self.publish_diagnostics(&uri)]
+            }
+            "textDocument/didChange" => {
+                // documents are synchronized in full, the last change contains the whole text
+                let Some(text) = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                else {
+                    return vec![];
+                };
+                let document = match self.documents.remove(&uri) {
+                    Some(document) => document.update(text),
+                    None => Document::new(text),
+                };
+                self.documents.insert(uri.clone(), document);
+                vec![self.publish_diagnostics(&uri)]
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                vec![json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/publishDiagnostics",
+                    "params": { "uri": uri, "diagnostics": [] },
+                })]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn publish_diagnostics(&self, uri: &str) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": self.documents[uri].diagnostics() },
+        })
+    }
+
+    fn document<'a>(
+        &'a self,
+        params: &'a Value,
+    ) -> Option<(&'a str, &'a Document, &'a Index, usize)> {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        let document = self.documents.get(uri)?;
+        let index = document.index.as_ref()?;
+        let offset = document.lines.index(&params["position"]);
+        Some((uri, document, index, offset))
+    }
+
+    fn definition(&self, params: &Value) -> Value {
+        let Some((uri, document, index, offset)) = self.document(params) else {
+            return Value::Null;
+        };
+
+        if let Some(reference) = index.reference_at(offset) {
+            let declaration = index.declaration(reference.symbol);
+            return json!({ "uri": uri, "range": document.lines.range(&declaration.span) });
+        }
+
+        if let Some(path) = index.path_at(offset) {
+            if let Some(member) = self.library.find(&path.path) {
+                return member.item().location.clone();
+            }
+        }
+
+        Value::Null
+    }
+
+    fn references(&self, params: &Value) -> Value {
+        let Some((uri, document, index, offset)) = self.document(params) else {
+            return Value::Null;
+        };
+        let Some(reference) = index.reference_at(offset) else {
+            return json!([]);
+        };
+
+        let include_declaration = params["context"]["includeDeclaration"]
+            .as_bool()
+            .unwrap_or(true);
+        let declaration = index.declaration(reference.symbol);
+
+        index
+            .references_of(reference.symbol)
+            .filter(|reference| include_declaration || reference.span != declaration.span)
+            .map(|reference| json!({ "uri": uri, "range": document.lines.range(&reference.span) }))
+            .collect()
+    }
+
+    fn hover(&self, params: &Value) -> Value {
+        let Some((_, document, index, offset)) = self.document(params) else {
+            return Value::Null;
+        };
+
+        let (detail, span) = if let Some(reference) = index.reference_at(offset) {
+            let declaration = index.declaration(reference.symbol);
+            (declaration.detail.clone(), &reference.span)
+        } else if let Some(path) = index.path_at(offset) {
+            let Some(member) = self.library.find(&path.path) else {
+                return Value::Null;
+            };
+            (member.item().detail.clone(), &path.span)
+        } else {
+            return Value::Null;
+        };
+
+        json!({
+            "contents": { "kind": "markdown", "value": format!("```elodie\n{detail}\n```") },
+            "range": document.lines.range(span),
+        })
+    }
+
+    fn completion(&self, params: &Value) -> Value {
+        let Some(uri) = params["textDocument"]["uri"].as_str() else {
+            return Value::Null;
+        };
+        let Some(document) = self.documents.get(uri) else {
+            return Value::Null;
+        };
+        let offset = document.lines.index(&params["position"]);
+        let line = document.lines.line_before(offset);
+
+        let package_path = Regex::new(r"([A-Za-z_]\w*(?:::[A-Za-z_]\w*)*)::\w*$").unwrap();
+        let property = Regex::new(r"([A-Za-z_]\w*)\.\w*$").unwrap();
+
+        if let Some(captures) = package_path.captures(&line) {
+            let path: Vec<String> = captures[1].split("::").map(|s| s.to_string()).collect();
+            return json!(self.complete_package(document.index.as_ref(), &path));
+        }
+
+        if let Some(captures) = property.captures(&line) {
+            return json!(self.complete_methods(document.index.as_ref(), &captures[1]));
+        }
+
+        let mut result: Vec<Value> = self
+            .library
+            .members(&[])
+            .iter()
+            .map(|member| completion_item(member.item().name.as_str(), 9, &member.item().detail))
+            .collect();
+
+        if let Some(index) = &document.index {
+            let mut seen = vec![];
+            for declaration in &index.declarations {
+                let name = index.name(declaration);
+                if seen.contains(&name) || matches!(declaration.parent, Some(Parent::Definition(_)))
+                {
+                    continue;
+                }
+                seen.push(name);
+                result.push(completion_item(
+                    name,
+                    completion_kind(index.symbol(declaration.symbol)),
+                    &declaration.detail,
+                ));
+            }
+        }
+        json!(result)
+    }
+
+    fn complete_package(&self, index: Option<&Index>, path: &[String]) -> Vec<Value> {
+        let local = index.and_then(|index| {
+            let mut parent = None;
+            for segment in path {
+                let package = index.children(parent).find(|declaration| {
+                    index.name(declaration) == segment
+                        && matches!(index.symbol(declaration.symbol), Symbol::Package { .. })
+                })?;
+                parent = Some(Parent::Symbol(package.symbol));
+            }
+            let members: Vec<Value> = index
+                .children(parent)
+                .map(|declaration| {
+                    completion_item(
+                        index.name(declaration),
+                        completion_kind(index.symbol(declaration.symbol)),
+                        &declaration.detail,
+                    )
+                })
+                .collect();
+            Some(members)
+        });
+        if let Some(local) = local {
+            return local;
+        }
+
+        self.library
+            .members(path)
+            .iter()
+            .map(|member| match member {
+                Member::Package(package) => {
+                    completion_item(&package.item.name, 9, &package.item.detail)
+                }
+                Member::Function(function) => completion_item(&function.name, 3, &function.detail),
+            })
+            .collect()
+    }
+
+    fn complete_methods(&self, index: Option<&Index>, receiver: &str) -> Vec<Value> {
+        let r#type = index.and_then(|index| {
+            index
+                .declarations
+                .iter()
+                .rev()
+                .filter(|declaration| index.name(declaration) == receiver)
+                .find_map(|declaration| declaration.r#type.clone())
+        });
+
+        let mut methods: Vec<(&str, &str, &str)> = vec![];
+        if let Some(index) = index {
+            methods.extend(
+                index
+                    .methods()
+                    .map(|(r#type, method)| (r#type, index.name(method), method.detail.as_str())),
+            );
+        }
+        methods.extend(self.library.methods.iter().map(|(r#type, method)| {
+            (
+                r#type.as_str(),
+                method.name.as_str(),
+                method.detail.as_str(),
+            )
+        }));
+
+        let mut result = vec![];
+        if let (Some(index), Some(r#type)) = (index, &r#type) {
+            let properties = index
+                .declarations
+                .iter()
+                .filter(|declaration| {
+                    index.name(declaration) == r#type
+                        && matches!(index.symbol(declaration.symbol), Symbol::Type { .. })
+                })
+                .flat_map(|declaration| index.children(Some(Parent::Symbol(declaration.symbol))));
+            for property in properties {
+                result.push(completion_item(index.name(property), 10, &property.detail));
+            }
+        }
+
+        let typed = methods
+            .iter()
+            .any(|(method_type, ..)| Some(*method_type) == r#type.as_deref());
+        for (method_type, name, detail) in methods {
+            if typed && Some(method_type) != r#type.as_deref() {
+                continue;
+            }
+            result.push(completion_item(
+                name,
+                2,
+                &format!("{method_type} - {detail}"),
+            ));
+        }
+        result
+    }
+
+    fn document_symbols(&self, params: &Value) -> Value {
+        let Some(uri) = params["textDocument"]["uri"].as_str() else {
+            return Value::Null;
+        };
+        let Some(document) = self.documents.get(uri) else {
+            return Value::Null;
+        };
+        let Some(index) = &document.index else {
+            return json!([]);
+        };
+        json!(document_symbols(document, index, None))
+    }
+}
+
+fn document_symbols(document: &Document, index: &Index, parent: Option<Parent>) -> Vec<Value> {
+    let mut result: Vec<(usize, Value)> = vec![];
+
+    for declaration in index.children(parent) {
+        let kind = match (index.symbol(declaration.symbol), parent) {
+            (Symbol::Argument { .. }, _) => continue,
+            (Symbol::Function { .. }, Some(Parent::Definition(_))) => 6,
+            (Symbol::Function { .. }, _) => 12,
+            (Symbol::Package { .. }, _) => 4,
+            (Symbol::Type { .. }, _) => 23,
+            (Symbol::Variable { .. }, Some(Parent::Symbol(parent))) => match index.symbol(parent) {
+                Symbol::Type { .. } => 7,
+                _ => 13,
+            },
+            (Symbol::Variable { .. }, _) => 13,
+        };
+
+        result.push((
+            declaration.range.start.index.0,
+            json!({
+                "name": index.name(declaration),
+                "detail": declaration.detail,
+                "kind": kind,
+                "range": document.lines.range(&declaration.range),
+                "selectionRange": document.lines.range(&declaration.span),
+                "children": document_symbols(document, index, Some(Parent::Symbol(declaration.symbol))),
+            }),
+        ));
+    }
+
+    for (idx, definition) in index.definitions.iter().enumerate() {
+        if definition.parent != parent {
+            continue;
+        }
+        result.push((
+            definition.range.start.index.0,
+            json!({
+                "name": definition.r#type,
+                "detail": format!("define {}", definition.r#type),
+                "kind": 5,
+                "range": document.lines.range(&definition.range),
+                "selectionRange": document.lines.range(&definition.span),
+                "children": document_symbols(document, index, Some(Parent::Definition(idx))),
+            }),
+        ));
+    }
+
+    result.sort_by_key(|(start, _)| *start);
+    result.into_iter().map(|(_, symbol)| symbol).collect()
+}
+
+fn completion_kind(symbol: &Symbol) -> u32 {
+    match symbol {
+        Symbol::Argument { .. } | Symbol::Variable { .. } => 6,
+        Symbol::Function { .. } => 3,
+        Symbol::Package { .. } => 9,
+        Symbol::Type { .. } => 22,
+    }
+}
+
+fn completion_item(label: &str, kind: u32, detail: &str) -> Value {
+    json!({ "label": label, "kind": kind, "detail": detail })
+}
+
+fn guard(message: &Value, handle: impl FnOnce() -> Vec<Value>) -> Vec<Value> {
+    // stdout belongs to the protocol, panics get reported to the client and on stderr
+    let panic = match catch_silently(handle) {
+        Ok(responses) => return responses,
+        Err(panic) => panic,
+    };
+    let method = message["method"].as_str().unwrap_or_default();
+    eprintln!("Language server failed to handle {method} - {panic}");
+    match message.get("id") {
+        Some(id) => vec![error(
+            id,
+            INTERNAL_ERROR,
+            &format!("Internal error - {panic}"),
+        )],
+        None => vec![],
+    }
+}
+
+fn error(id: &Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use serde_json::{json, Value};
+
+    use crate::lsp::library::tests::test_library;
+    use crate::lsp::rpc::{read_message, write_message};
+    use crate::lsp::{guard, Server};
+
+    const URI: &str = "file:///tmp/main.ec";
+
+    fn open(text: &str) -> Server {
+        let mut server = Server::new(test_library());
+        server.handle(&json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}));
+        server.handle(&json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": URI, "languageId": "elodie", "version": 1, "text": text } },
+        }));
+        server
+    }
+
+    fn request(server: &mut Server, method: &str, line: u32, character: u32) -> Value {
+        let mut responses = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": method,
+            "params": {
+                "textDocument": { "uri": URI },
+                "position": { "line": line, "character": character },
+                "context": { "includeDeclaration": true },
+            },
+        }));
+        assert_eq!(responses.len(), 1);
+        responses.remove(0)["result"].take()
+    }
+
+    fn labels(result: &Value) -> Vec<String> {
+        result
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["label"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn answers_panics_with_internal_error() {
+        let request = json!({"jsonrpc": "2.0", "id": 7, "method": "textDocument/hover"});
+        let responses = guard(&request, || panic!("index out of bounds"));
+        assert_eq!(
+            responses,
+            vec![json!({
+                "jsonrpc": "2.0",
+                "id": 7,
+                "error": { "code": -32603, "message": "Internal error - index out of bounds" },
+            })]
+        );
+
+        let notification = json!({"jsonrpc": "2.0", "method": "textDocument/didOpen"});
+        assert!(guard(&notification, || panic!("index out of bounds")).is_empty());
+    }
+
+    #[test]
+    fn reports_unknown_characters() {
+        let mut server = Server::new(test_library());
+        server.handle(&json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}));
+        let messages = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": URI, "languageId": "elodie", "version": 1, "text": "let a = \"b\"" } },
+        }));
+        assert_eq!(messages.len(), 1);
+        let diagnostics = messages[0]["params"]["diagnostics"].as_array().unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["message"], "Unknown character '\"'");
+        assert_eq!(
+            diagnostics[0]["range"]["start"],
+            json!({"line": 0, "character": 8})
+        );
+    }
+
+    #[test]
+    fn scripted_session() {
+        let mut input = vec![];
+        for message in [
+            json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+            json!({"jsonrpc": "2.0", "method": "initialized", "params": {}}),
+            json!({"jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+                "textDocument": { "uri": URI, "languageId": "elodie", "version": 1, "text": "let a = 1\na" }
+            }}),
+            json!({"jsonrpc": "2.0", "method": "textDocument/didChange", "params": {
+                "textDocument": { "uri": URI, "version": 2 },
+                "contentChanges": [{ "text": "function f(" }]
+            }}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "textDocument/unknown", "params": {}}),
+            json!({"jsonrpc": "2.0", "id": 3, "method": "shutdown"}),
+            json!({"jsonrpc": "2.0", "method": "exit"}),
+        ] {
+            write_message(&mut input, &message).unwrap();
+        }
+
+        let mut output = vec![];
+        let mut server = Server::new(test_library());
+        server.serve(&mut Cursor::new(input), &mut output).unwrap();
+        assert!(server.shutdown);
+
+        let mut output = Cursor::new(output);
+        let mut messages = vec![];
+        while let Some(message) = read_message(&mut output).unwrap() {
+            messages.push(message);
+        }
+        assert_eq!(messages.len(), 5);
+
+        assert_eq!(messages[0]["id"], 1);
+        assert_eq!(
+            messages[0]["result"]["capabilities"]["definitionProvider"],
+            true
+        );
+
+        assert_eq!(messages[1]["method"], "textDocument/publishDiagnostics");
+        assert_eq!(messages[1]["params"]["diagnostics"], json!([]));
+
+        assert_eq!(messages[2]["method"], "textDocument/publishDiagnostics");
+        let diagnostics = messages[2]["params"]["diagnostics"].as_array().unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["message"], "Unexpected end of file");
+
+        assert_eq!(messages[3]["id"], 2);
+        assert_eq!(messages[3]["error"]["code"], -32601);
+
+        assert_eq!(messages[4]["id"], 3);
+        assert_eq!(messages[4]["result"], Value::Null);
+    }
+
+    #[test]
+    fn definition_of_variable() {
+        let mut server = open("let value = 1\nlet other = value + 2");
+        let result = request(&mut server, "textDocument/definition", 1, 13);
+        assert_eq!(result["uri"], URI);
+        assert_eq!(
+            result["range"],
+            json!({"start": {"line": 0, "character": 4}, "end": {"line": 0, "character": 9}})
+        );
+    }
+
+    #[test]
+    fn definition_respects_shadowing() {
+        let mut server = open("let value = 1\nfunction f(value) {\n  return value\n}");
+        let result = request(&mut server, "textDocument/definition", 2, 10);
+        assert_eq!(
+            result["range"]["start"],
+            json!({"line": 1, "character": 11})
+        );
+    }
+
+    #[test]
+    fn definition_of_function_declared_later() {
+        let mut server = open("function a() {\n  b()\n}\nfunction b() { }");
+        let result = request(&mut server, "textDocument/definition", 1, 2);
+        assert_eq!(result["range"]["start"], json!({"line": 3, "character": 9}));
+    }
+
+    #[test]
+    fn definition_of_package_function() {
+        let mut server =
+            open("package math {\n  function one() -> Number {\n    return 1\n  }\n}\nmath::one()");
+        let result = request(&mut server, "textDocument/definition", 5, 7);
+        assert_eq!(
+            result["range"]["start"],
+            json!({"line": 1, "character": 11})
+        );
+    }
+
+    #[test]
+    fn definition_of_library_function() {
+        let mut server = open("std::io::println('Elodie')");
+        let result = request(&mut server, "textDocument/definition", 0, 10);
+        assert_eq!(result["uri"], "file:///lib/std/io/index.ec");
+        assert_eq!(
+            result["range"]["start"],
+            json!({"line": 1, "character": 18})
+        );
+    }
+
+    #[test]
+    fn definition_of_method() {
+        let mut server = open(
+            "type Point(x: Number)\ndefine Point {\n  function len() -> Number {\n    return self.x\n  }\n}\nlet p = Point(x = 1)\np.len()",
+        );
+        let result = request(&mut server, "textDocument/definition", 7, 2);
+        assert_eq!(
+            result["range"]["start"],
+            json!({"line": 2, "character": 11})
+        );
+
+        let result = request(&mut server, "textDocument/definition", 3, 16);
+        assert_eq!(
+            result["range"]["start"],
+            json!({"line": 0, "character": 11})
+        );
+
+        let result = request(&mut server, "textDocument/definition", 6, 14);
+        assert_eq!(
+            result["range"]["start"],
+            json!({"line": 0, "character": 11})
+        );
+    }
+
+    #[test]
+    fn find_references() {
+        let mut server = open("let value = 1\nlet other = value + value");
+        let result = request(&mut server, "textDocument/references", 0, 5);
+        let starts: Vec<Value> = result
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|location| location["range"]["start"].clone())
+            .collect();
+        assert_eq!(
+            starts,
+            vec![
+                json!({"line": 0, "character": 4}),
+                json!({"line": 1, "character": 12}),
+                json!({"line": 1, "character": 20}),
+            ]
+        );
+    }
+
+    #[test]
+    fn hover_shows_types() {
+        let mut server = open("let value = 'Elodie'\nfunction greet(name: String) -> String {\n  return name\n}\nvalue");
+        let result = request(&mut server, "textDocument/hover", 4, 1);
+        assert_eq!(
+            result["contents"]["value"],
+            "```elodie\nlet value: String\n```"
+        );
+
+        let result = request(&mut server, "textDocument/hover", 1, 10);
+        assert_eq!(
+            result["contents"]["value"],
+            "```elodie\nfunction greet(name: String) -> String\n```"
+        );
+
+        let result = request(&mut server, "textDocument/hover", 2, 10);
+        assert_eq!(result["contents"]["value"], "```elodie\nname: String\n```");
+    }
+
+    #[test]
+    fn hover_library_function() {
+        let mut server = open("std::io::println('Elodie')");
+        let result = request(&mut server, "textDocument/hover", 0, 10);
+        assert_eq!(
+            result["contents"]["value"],
+            "```elodie\nfunction println(s: String)\n```"
+        );
+    }
+
+    #[test]
+    fn complete_std_package_paths() {
+        let mut server = open("std::\nstd::io::pr");
+        let result = request(&mut server, "textDocument/completion", 0, 5);
+        assert_eq!(labels(&result), vec!["io"]);
+
+        let result = request(&mut server, "textDocument/completion", 1, 11);
+        assert_eq!(labels(&result), vec!["println"]);
+    }
+
+    #[test]
+    fn complete_define_methods() {
+        let mut server = open(
+            "type Point(x: Number)\ndefine Point {\n  function len() -> Number {\n    return 1\n  }\n}\nlet p = Point(x = 1)\np.\nlet s = 'Elodie'\ns.",
+        );
+        let result = request(&mut server, "textDocument/completion", 7, 2);
+        assert_eq!(labels(&result), vec!["x", "len"]);
+
+        let result = request(&mut server, "textDocument/completion", 9, 2);
+        assert_eq!(labels(&result), vec!["length"]);
+    }
+
+    #[test]
+    fn document_symbols() {
+        let mut server = open(
+            "package math {\n  function one() -> Number {\n    return 1\n  }\n}\ntype Point(x: Number)\ndefine Point {\n  function len() { }\n}\nlet p = Point(x = 1)",
+        );
+        let mut responses = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "textDocument/documentSymbol",
+            "params": { "textDocument": { "uri": URI } },
+        }));
+        let result = responses.remove(0)["result"].take();
+        let symbols = result.as_array().unwrap();
+
+        let names: Vec<(&str, u64)> = symbols
+            .iter()
+            .map(|symbol| {
+                (
+                    symbol["name"].as_str().unwrap(),
+                    symbol["kind"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![("math", 4), ("Point", 23), ("Point", 5), ("p", 13)]
+        );
+
+        assert_eq!(symbols[0]["children"][0]["name"], "one");
+        assert_eq!(symbols[0]["children"][0]["kind"], 12);
+        assert_eq!(symbols[1]["children"][0]["name"], "x");
+        assert_eq!(symbols[1]["children"][0]["kind"], 7);
+        assert_eq!(symbols[2]["children"][0]["name"], "len");
+        assert_eq!(symbols[2]["children"][0]["kind"], 6);
+        assert_eq!(symbols[3]["detail"], "let p: Point");
+    }
+}
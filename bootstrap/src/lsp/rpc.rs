@@ -0,0 +1,83 @@
+use std::io::{BufRead, Write};
+
+use serde_json::Value;
+
+use crate::lsp::Error;
+
+/// Reads the next message, returns None once the client closed the stream
+pub(crate) fn read_message(reader: &mut impl BufRead) -> crate::lsp::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let Some(content_length) = content_length else {
+        return Err(Error::MissingContentLength);
+    };
+
+    let mut content = vec![0; content_length];
+    reader.read_exact(&mut content)?;
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+pub(crate) fn write_message(writer: &mut impl Write, message: &Value) -> crate::lsp::Result<()> {
+    let content = serde_json::to_string(message)?;
+    write!(
+        writer,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use serde_json::json;
+
+    use crate::lsp::rpc::{read_message, write_message};
+    use crate::lsp::Error;
+
+    #[test]
+    fn write_and_read_message() {
+        let mut buffer = vec![];
+        write_message(&mut buffer, &json!({"jsonrpc": "2.0", "method": "exit"})).unwrap();
+        write_message(&mut buffer, &json!({"text": "Grüße"})).unwrap();
+
+        let mut reader = Cursor::new(buffer);
+        assert_eq!(
+            read_message(&mut reader).unwrap(),
+            Some(json!({"jsonrpc": "2.0", "method": "exit"}))
+        );
+        assert_eq!(
+            read_message(&mut reader).unwrap(),
+            Some(json!({"text": "Grüße"}))
+        );
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn missing_content_length() {
+        let mut reader = Cursor::new("Content-Type: utf-8\r\n\r\n{}");
+        let Err(Error::MissingContentLength) = read_message(&mut reader) else {
+            panic!()
+        };
+    }
+}
@@ -3,21 +3,30 @@ use std::fs::File;
 use std::io::Read;
 use std::path::PathBuf;
 use std::process::exit;
+use std::time::{SystemTime, UNIX_EPOCH};
 use std::{env, io};
 
+use crate::backend::intrinsic::Intrinsics;
+use crate::backend::repl::repl;
 use crate::backend::run::scope::Scope;
 use crate::backend::run::type_definitions::TypeDefinitions;
 use crate::backend::run::{run, run_file};
-use crate::backend::test::test_files;
+use crate::backend::test::report::Reporter;
+use crate::backend::test::{test_files, TestOptions};
 use crate::backend::{build, generate};
-use crate::frontend::ast_from_str;
+use crate::dump::{dump_file, Format};
+use crate::frontend::ast_from_file;
 use crate::frontend::context::Context;
+use crate::frontend::format::format_files;
+use crate::lsp::serve;
 
 mod backend;
 mod cli;
 mod common;
+mod dump;
 mod frontend;
 mod ir;
+mod lsp;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -29,19 +38,12 @@ fn main() {
     if args.get(1).unwrap() == "build" {
         let file = PathBuf::from(args.get(2).unwrap());
 
-        fn load_text_from_file(path: &str) -> io::Result<String> {
-            let mut file = File::open(path)?;
-            let mut contents = String::new();
-            file.read_to_string(&mut contents)?;
-            Ok(contents)
-        }
-
         let scope = Scope::new(HashMap::new(), HashMap::new());
         let mut ctx = Context::new();
 
+        let intrinsics = Intrinsics::new(&mut ctx, true);
         let (scope, definitions) = {
-            let std_content = load_library_file("core/index.ec").unwrap();
-            let std_file = ast_from_str(&mut ctx, std_content.as_str()).unwrap();
+            let std_file = ast_from_file(&mut ctx, &library_file_path("core/index.ec")).unwrap();
             run(
                 &mut ctx,
                 scope,
@@ -49,25 +51,14 @@ fn main() {
                     definitions: Default::default(),
                 },
                 std_file,
-                true,
+                &intrinsics,
             )
             .unwrap()
         };
 
-        let mut path = PathBuf::from(file.clone());
-        let content = load_text_from_file(path.to_str().unwrap()).unwrap();
-
-        let source_file = ast_from_str(&mut ctx, content.as_str()).unwrap();
-
-        let code = generate::generate_c_code(
-            frontend::Context {
-                string_table: ctx.string_table,
-            },
-            source_file,
-        )
-        .unwrap();
+        let source_file = ast_from_file(&mut ctx, &file).unwrap();
 
-        // println!("{}",code);
+        let code = generate::generate_c_code(ctx, source_file).unwrap();
 
         build::build(
             file.file_name()
@@ -83,20 +74,133 @@ fn main() {
         return;
     }
 
+    if args.get(1).unwrap() == "fmt" {
+        let check = args.iter().skip(2).any(|arg| arg == "--check");
+        let paths = args
+            .iter()
+            .skip(2)
+            .filter(|arg| *arg != "--check")
+            .map(PathBuf::from)
+            .collect();
+        format_files(paths, check);
+        return;
+    }
+
+    if args.get(1).unwrap() == "dump" {
+        let format = if args.iter().skip(2).any(|arg| arg == "--json") {
+            Format::Json
+        } else {
+            Format::Text
+        };
+        let arguments: Vec<&String> = args.iter().skip(2).filter(|arg| *arg != "--json").collect();
+        let [stage, file] = arguments[..] else {
+            eprintln!("Usage: dump tokens|parse|ast|ir|c <file> [--json]");
+            exit(1)
+        };
+        dump_file(stage, &PathBuf::from(file), format);
+        return;
+    }
+
+    if args.get(1).unwrap() == "repl" {
+        repl();
+        return;
+    }
+
+    if args.get(1).unwrap() == "lsp" {
+        serve();
+        return;
+    }
+
     if args.get(1).unwrap() == "test" {
-        test_files(
-            vec![PathBuf::from(args.get(2).unwrap())],
-            args.get(3).unwrap_or(&"true".to_string()) == "true",
-            args.get(4).unwrap_or(&"false".to_string()) == "true",
-        );
+        let usage = "Usage: test <dir-or-file>... [--no-color] [--reporter junit|tap|json|pretty] [--filter <pattern>] [--update-snapshots] [--jobs <n>] [--coverage <lcov-file>] [--coverage-threshold <percent>] [--seed <n>]";
+        let mut options = TestOptions {
+            print_colors: true,
+            reporter: Reporter::Pretty,
+            filter: None,
+            update_snapshots: false,
+            jobs: 1,
+            coverage: None,
+            coverage_threshold: None,
+            seed: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_nanos() as u64)
+                .unwrap_or_default(),
+        };
+        let mut paths = vec![];
+
+        let mut arguments = args.iter().skip(2);
+        while let Some(argument) = arguments.next() {
+            match argument.as_str() {
+                "--no-color" => options.print_colors = false,
+                "--update-snapshots" => options.update_snapshots = true,
+                "--reporter" => {
+                    let Some(reporter) = arguments.next() else {
+                        eprintln!("{usage}");
+                        exit(1)
+                    };
+                    options.reporter = match reporter.parse() {
+                        Ok(reporter) => reporter,
+                        Err(message) => {
+                            eprintln!("{message}");
+                            exit(1)
+                        }
+                    };
+                }
+                "--filter" => {
+                    let Some(filter) = arguments.next() else {
+                        eprintln!("{usage}");
+                        exit(1)
+                    };
+                    options.filter = Some(filter.clone());
+                }
+                "--jobs" => {
+                    let Some(jobs) = arguments.next().and_then(|jobs| jobs.parse().ok()) else {
+                        eprintln!("{usage}");
+                        exit(1)
+                    };
+                    options.jobs = jobs;
+                }
+                "--coverage" => {
+                    let Some(coverage) = arguments.next() else {
+                        eprintln!("{usage}");
+                        exit(1)
+                    };
+                    options.coverage = Some(PathBuf::from(coverage));
+                }
+                "--coverage-threshold" => {
+                    let Some(threshold) = arguments
+                        .next()
+                        .and_then(|threshold| threshold.parse().ok())
+                    else {
+                        eprintln!("{usage}");
+                        exit(1)
+                    };
+                    options.coverage_threshold = Some(threshold);
+                }
+                "--seed" => {
+                    let Some(seed) = arguments.next().and_then(|seed| seed.parse().ok()) else {
+                        eprintln!("{usage}");
+                        exit(1)
+                    };
+                    options.seed = seed;
+                }
+                _ => paths.push(PathBuf::from(argument)),
+            }
+        }
+
+        if paths.is_empty() {
+            eprintln!("{usage}");
+            exit(1)
+        }
+        test_files(paths, options);
     } else {
-        run_file(args.get(1).unwrap(), true);
+        // the program sees its own arguments starting with the file, like argv of a binary
+        run_file(args.get(1).unwrap(), args[1..].to_vec(), true);
     }
 }
 
 fn load_library_file(filename: &str) -> io::Result<String> {
-    let manifest_dir = "/home/ddymke/repo/elodie/src/lib/";
-    let file_path = PathBuf::from(manifest_dir).join(filename);
+    let file_path = library_file_path(filename);
 
     let mut file = File::open(file_path)?;
     let mut contents = String::new();
@@ -104,12 +208,11 @@ fn load_library_file(filename: &str) -> io::Result<String> {
     Ok(contents)
 }
 
-fn load_test_runner() -> io::Result<String> {
-    let manifest_dir = "/home/ddymke/repo/elodie/src/test-runner/index.ec";
-    let file_path = PathBuf::from(manifest_dir);
+fn library_file_path(filename: &str) -> PathBuf {
+    let manifest_dir = "/home/ddymke/repo/elodie/src/lib/";
+    PathBuf::from(manifest_dir).join(filename)
+}
 
-    let mut file = File::open(file_path)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-    Ok(contents)
+fn test_runner_file_path() -> PathBuf {
+    PathBuf::from("/home/ddymke/repo/elodie/src/test-runner/index.ec")
 }
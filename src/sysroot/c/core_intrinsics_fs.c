@@ -0,0 +1,196 @@
+#define _XOPEN_SOURCE 700
+
+#include "core_intrinsics_fs.h"
+
+#include <dirent.h>
+#include <errno.h>
+#include <ftw.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/stat.h>
+#include <unistd.h>
+
+static char error[256] = "";
+
+static bool fail(void) {
+    snprintf(error, sizeof(error), "%s", strerror(errno));
+    return false;
+}
+
+const char *core_intrinsics_fs_error(void) {
+    return error;
+}
+
+static bool write_file(const char *path, const void *content, size_t length, const char *mode) {
+    FILE *file = fopen(path, mode);
+    if (file == NULL) {
+        return fail();
+    }
+    size_t written = fwrite(content, 1, length, file);
+    if (fclose(file) != 0 || written != length) {
+        return fail();
+    }
+    return true;
+}
+
+unsigned char *core_intrinsics_fs_read_bytes(const char *path, size_t *length) {
+    FILE *file = fopen(path, "rb");
+    if (file == NULL) {
+        fail();
+        return NULL;
+    }
+
+    size_t capacity = 4096;
+    unsigned char *bytes = malloc(capacity + 1);
+    *length = 0;
+    size_t read;
+    while ((read = fread(bytes + *length, 1, capacity - *length, file)) > 0) {
+        *length += read;
+        if (*length == capacity) {
+            capacity *= 2;
+            bytes = realloc(bytes, capacity + 1);
+        }
+    }
+
+    if (ferror(file)) {
+        fail();
+        fclose(file);
+        free(bytes);
+        return NULL;
+    }
+    fclose(file);
+    bytes[*length] = '\0';
+    return bytes;
+}
+
+char *core_intrinsics_fs_read_text(const char *path) {
+    size_t length;
+    return (char *) core_intrinsics_fs_read_bytes(path, &length);
+}
+
+bool core_intrinsics_fs_write_text(const char *path, const char *text) {
+    return write_file(path, text, strlen(text), "w");
+}
+
+bool core_intrinsics_fs_append_text(const char *path, const char *text) {
+    return write_file(path, text, strlen(text), "a");
+}
+
+bool core_intrinsics_fs_write_bytes(const char *path, const unsigned char *bytes, size_t length) {
+    return write_file(path, bytes, length, "wb");
+}
+
+bool core_intrinsics_fs_append_bytes(const char *path, const unsigned char *bytes, size_t length) {
+    return write_file(path, bytes, length, "ab");
+}
+
+// creates the missing parents first, like mkdir -p
+bool core_intrinsics_fs_create_directory(const char *path) {
+    char *current = strdup(path);
+    for (char *separator = strchr(current + 1, '/'); separator != NULL; separator = strchr(separator + 1, '/')) {
+        *separator = '\0';
+        if (mkdir(current, 0777) != 0 && errno != EEXIST) {
+            free(current);
+            return fail();
+        }
+        *separator = '/';
+    }
+    free(current);
+
+    if (mkdir(path, 0777) != 0 && !(errno == EEXIST && core_intrinsics_fs_is_directory(path))) {
+        return fail();
+    }
+    return true;
+}
+
+static int compare_names(const void *a, const void *b) {
+    return strcmp(*(char *const *) a, *(char *const *) b);
+}
+
+char **core_intrinsics_fs_list_directory(const char *path, size_t *count) {
+    DIR *directory = opendir(path);
+    if (directory == NULL) {
+        fail();
+        return NULL;
+    }
+
+    size_t capacity = 16;
+    char **names = malloc(capacity * sizeof(char *));
+    *count = 0;
+    struct dirent *entry;
+    while ((entry = readdir(directory)) != NULL) {
+        if (strcmp(entry->d_name, ".") == 0 || strcmp(entry->d_name, "..") == 0) {
+            continue;
+        }
+        if (*count == capacity) {
+            capacity *= 2;
+            names = realloc(names, capacity * sizeof(char *));
+        }
+        names[(*count)++] = strdup(entry->d_name);
+    }
+    closedir(directory);
+
+    qsort(names, *count, sizeof(char *), compare_names);
+    return names;
+}
+
+static int remove_entry(const char *path, const struct stat *stat, int flag, struct FTW *ftw) {
+    (void) stat;
+    (void) flag;
+    (void) ftw;
+    return remove(path);
+}
+
+bool core_intrinsics_fs_remove_directory(const char *path) {
+    if (nftw(path, remove_entry, 16, FTW_DEPTH | FTW_PHYS) != 0) {
+        return fail();
+    }
+    return true;
+}
+
+bool core_intrinsics_fs_remove_file(const char *path) {
+    if (unlink(path) != 0) {
+        return fail();
+    }
+    return true;
+}
+
+bool core_intrinsics_fs_path_exists(const char *path) {
+    struct stat info;
+    return stat(path, &info) == 0;
+}
+
+bool core_intrinsics_fs_is_file(const char *path) {
+    struct stat info;
+    return stat(path, &info) == 0 && S_ISREG(info.st_mode);
+}
+
+bool core_intrinsics_fs_is_directory(const char *path) {
+    struct stat info;
+    return stat(path, &info) == 0 && S_ISDIR(info.st_mode);
+}
+
+bool core_intrinsics_fs_metadata(const char *path, struct core_intrinsics_fs_metadata *metadata) {
+    struct stat info;
+    if (stat(path, &info) != 0) {
+        return fail();
+    }
+    metadata->size = (double) info.st_size;
+    metadata->is_file = S_ISREG(info.st_mode);
+    metadata->is_directory = S_ISDIR(info.st_mode);
+    metadata->modified = (double) info.st_mtime;
+    return true;
+}
+
+// path gets appended to base, unless it is absolute
+char *core_intrinsics_fs_join_path(const char *base, const char *path) {
+    if (path[0] == '/' || base[0] == '\0') {
+        return strdup(path);
+    }
+    size_t base_length = strlen(base);
+    bool separated = base[base_length - 1] == '/';
+    char *result = malloc(base_length + strlen(path) + 2);
+    sprintf(result, separated ? "%s%s" : "%s/%s", base, path);
+    return result;
+}
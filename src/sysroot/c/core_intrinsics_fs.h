@@ -0,0 +1,39 @@
+#ifndef CORE_INTRINSICS_FS_H
+#define CORE_INTRINSICS_FS_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+// operations which can fail return false or NULL, core_intrinsics_fs_error tells why
+const char *core_intrinsics_fs_error(void);
+
+// returned text, bytes, names and paths are allocated and owned by the caller
+char *core_intrinsics_fs_read_text(const char *path);
+bool core_intrinsics_fs_write_text(const char *path, const char *text);
+bool core_intrinsics_fs_append_text(const char *path, const char *text);
+
+unsigned char *core_intrinsics_fs_read_bytes(const char *path, size_t *length);
+bool core_intrinsics_fs_write_bytes(const char *path, const unsigned char *bytes, size_t length);
+bool core_intrinsics_fs_append_bytes(const char *path, const unsigned char *bytes, size_t length);
+
+bool core_intrinsics_fs_create_directory(const char *path);
+char **core_intrinsics_fs_list_directory(const char *path, size_t *count);
+bool core_intrinsics_fs_remove_directory(const char *path);
+bool core_intrinsics_fs_remove_file(const char *path);
+
+bool core_intrinsics_fs_path_exists(const char *path);
+bool core_intrinsics_fs_is_file(const char *path);
+bool core_intrinsics_fs_is_directory(const char *path);
+
+struct core_intrinsics_fs_metadata {
+    double size;
+    bool is_file;
+    bool is_directory;
+    double modified;
+};
+
+bool core_intrinsics_fs_metadata(const char *path, struct core_intrinsics_fs_metadata *metadata);
+
+char *core_intrinsics_fs_join_path(const char *base, const char *path);
+
+#endif
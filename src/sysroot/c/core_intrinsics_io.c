@@ -1,7 +1,101 @@
 #include "core_intrinsics_io.h"
 
+#include <errno.h>
 #include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+static char error[256] = "";
+
+static char* fail(void) {
+    if (ferror(stdin)) {
+        snprintf(error, sizeof(error), "%s", strerror(errno));
+    } else {
+        snprintf(error, sizeof(error), "end of input");
+    }
+    return NULL;
+}
 
 void core_intrinsics_io_print(const char *text) {
     printf("%s", text);
-}
\ No newline at end of file
+}
+
+void core_intrinsics_io_eprint(const char *text) {
+    fprintf(stderr, "%s", text);
+}
+
+const char *core_intrinsics_io_error(void) {
+    return error;
+}
+
+char *core_intrinsics_io_read_line(void) {
+    size_t capacity = 128;
+    size_t length = 0;
+    char *line = malloc(capacity);
+
+    int c;
+    while ((c = getchar()) != EOF && c != '\n') {
+        if (length + 1 == capacity) {
+            capacity *= 2;
+            line = realloc(line, capacity);
+        }
+        line[length++] = (char) c;
+    }
+
+    if (c == EOF && (length == 0 || ferror(stdin))) {
+        free(line);
+        return fail();
+    }
+
+    if (length > 0 && line[length - 1] == '\r') {
+        length--;
+    }
+    line[length] = '\0';
+    return line;
+}
+
+char *core_intrinsics_io_read_all(void) {
+    size_t capacity = 4096;
+    size_t length = 0;
+    char *text = malloc(capacity + 1);
+
+    size_t read;
+    while ((read = fread(text + length, 1, capacity - length, stdin)) > 0) {
+        length += read;
+        if (length == capacity) {
+            capacity *= 2;
+            text = realloc(text, capacity + 1);
+        }
+    }
+
+    if (ferror(stdin)) {
+        free(text);
+        return fail();
+    }
+    text[length] = '\0';
+    return text;
+}
+
+char **core_intrinsics_io_read_lines(size_t *count) {
+    size_t capacity = 16;
+    char **lines = malloc(capacity * sizeof(char *));
+    *count = 0;
+
+    char *line;
+    while ((line = core_intrinsics_io_read_line()) != NULL) {
+        if (*count == capacity) {
+            capacity *= 2;
+            lines = realloc(lines, capacity * sizeof(char *));
+        }
+        lines[(*count)++] = line;
+    }
+
+    if (ferror(stdin)) {
+        for (size_t i = 0; i < *count; i++) {
+            free(lines[i]);
+        }
+        free(lines);
+        return NULL;
+    }
+    return lines;
+}
@@ -1,6 +1,17 @@
 #ifndef CORE_INTRINSICS_IO_H
 #define CORE_INTRINSICS_IO_H
 
+#include <stddef.h>
+
 void core_intrinsics_io_print(const char* text);
+void core_intrinsics_io_eprint(const char* text);
+
+// reading returns NULL once stdin ended or failed, core_intrinsics_io_error tells why
+const char* core_intrinsics_io_error(void);
+
+// returned lines and text are allocated and owned by the caller, lines come without line break
+char* core_intrinsics_io_read_line(void);
+char* core_intrinsics_io_read_all(void);
+char** core_intrinsics_io_read_lines(size_t* count);
 
 #endif
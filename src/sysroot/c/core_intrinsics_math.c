@@ -1,7 +1,104 @@
 #include "core_intrinsics_math.h"
 
 #include <math.h>
+#include <stdint.h>
 
 double core_intrinsics_math_cos_f64(double x) {
     return cos(x);
 }
+
+double core_intrinsics_math_sin_f64(double x) {
+    return sin(x);
+}
+
+double core_intrinsics_math_tan_f64(double x) {
+    return tan(x);
+}
+
+double core_intrinsics_math_sqrt_f64(double x) {
+    return sqrt(x);
+}
+
+double core_intrinsics_math_exp_f64(double x) {
+    return exp(x);
+}
+
+double core_intrinsics_math_log_f64(double x) {
+    return log(x);
+}
+
+double core_intrinsics_math_floor_f64(double x) {
+    return floor(x);
+}
+
+double core_intrinsics_math_ceil_f64(double x) {
+    return ceil(x);
+}
+
+double core_intrinsics_math_round_f64(double x) {
+    return round(x);
+}
+
+double core_intrinsics_math_abs_f64(double x) {
+    return fabs(x);
+}
+
+double core_intrinsics_math_pow_f64(double x, double y) {
+    return pow(x, y);
+}
+
+double core_intrinsics_math_min_f64(double x, double y) {
+    return fmin(x, y);
+}
+
+double core_intrinsics_math_max_f64(double x, double y) {
+    return fmax(x, y);
+}
+
+double core_intrinsics_math_clamp_f64(double x, double min, double max) {
+    return fmin(fmax(x, min), max);
+}
+
+static uint64_t unsigned_abs(double x) {
+    int64_t value = (int64_t) trunc(x);
+    return value < 0 ? -(uint64_t) value : (uint64_t) value;
+}
+
+double core_intrinsics_math_gcd(double a, double b) {
+    uint64_t x = unsigned_abs(a);
+    uint64_t y = unsigned_abs(b);
+    while (y != 0) {
+        uint64_t rest = x % y;
+        x = y;
+        y = rest;
+    }
+    return (double) x;
+}
+
+// integers beyond 2^53 can not be told apart from their neighbours as doubles
+static const double MAX_INTEGER = 9007199254740992.0;
+
+double core_intrinsics_math_mod_pow(double base, double exponent, double modulus) {
+    base = trunc(base);
+    exponent = trunc(exponent);
+    modulus = trunc(modulus);
+    // written so that NAN fails every comparison and gets rejected as well
+    if (!(base >= -MAX_INTEGER && base <= MAX_INTEGER && exponent >= 0 && exponent <= MAX_INTEGER &&
+          modulus >= 1 && modulus <= MAX_INTEGER)) {
+        return NAN;
+    }
+
+    int64_t m = (int64_t) modulus;
+    int64_t b = (int64_t) base % m;
+    unsigned __int128 power = (uint64_t) (b < 0 ? b + m : b);
+    uint64_t e = (uint64_t) exponent;
+    unsigned __int128 result = 1 % (uint64_t) m;
+    while (e > 0) {
+        if (e & 1) {
+            result = result * power % (uint64_t) m;
+        }
+        power = power * power % (uint64_t) m;
+        e >>= 1;
+    }
+    return (double) result;
+}
@@ -2,5 +2,24 @@
 #define CORE_INTRINSICS_MATH_H
 
 double core_intrinsics_math_cos_f64(double x);
+double core_intrinsics_math_sin_f64(double x);
+double core_intrinsics_math_tan_f64(double x);
+double core_intrinsics_math_sqrt_f64(double x);
+double core_intrinsics_math_exp_f64(double x);
+double core_intrinsics_math_log_f64(double x);
+double core_intrinsics_math_floor_f64(double x);
+double core_intrinsics_math_ceil_f64(double x);
+double core_intrinsics_math_round_f64(double x);
+double core_intrinsics_math_abs_f64(double x);
+
+double core_intrinsics_math_pow_f64(double x, double y);
+double core_intrinsics_math_min_f64(double x, double y);
+double core_intrinsics_math_max_f64(double x, double y);
+// max wins if min is larger than max
+double core_intrinsics_math_clamp_f64(double x, double min, double max);
+
+// the integer functions truncate their arguments and return NAN where there is no result
+double core_intrinsics_math_gcd(double a, double b);
+double core_intrinsics_math_mod_pow(double base, double exponent, double modulus);
 
 #endif
@@ -0,0 +1,202 @@
+#define _XOPEN_SOURCE 700
+
+#include "core_intrinsics_process.h"
+
+#include <errno.h>
+#include <fcntl.h>
+#include <poll.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+static int argument_count = 0;
+static char **argument_values = NULL;
+
+static char error[256] = "";
+
+static bool fail(int code) {
+    snprintf(error, sizeof(error), "%s", strerror(code));
+    return false;
+}
+
+void core_intrinsics_process_init(int argc, char **argv) {
+    argument_count = argc;
+    argument_values = argv;
+}
+
+const char *core_intrinsics_process_error(void) {
+    return error;
+}
+
+const char *const *core_intrinsics_process_arguments(size_t *count) {
+    *count = (size_t) argument_count;
+    return (const char *const *) argument_values;
+}
+
+const char *core_intrinsics_process_env_var(const char *name) {
+    const char *value = getenv(name);
+    if (value == NULL) {
+        snprintf(error, sizeof(error), "environment variable %s is not set", name);
+    }
+    return value;
+}
+
+static int exit_code(int status) {
+    return WIFEXITED(status) ? WEXITSTATUS(status) : -1;
+}
+
+// the child reports through a pipe which closes on exec whether the command could not be executed
+static pid_t start(const char *command, const char *const *arguments, size_t count, int out, int err) {
+    int report[2];
+    if (pipe(report) != 0) {
+        fail(errno);
+        return -1;
+    }
+    fcntl(report[1], F_SETFD, FD_CLOEXEC);
+
+    char **argv = malloc((count + 2) * sizeof(char *));
+    argv[0] = (char *) command;
+    for (size_t i = 0; i < count; i++) {
+        argv[i + 1] = (char *) arguments[i];
+    }
+    argv[count + 1] = NULL;
+
+    // output the program buffered so far would show up after the one of the command otherwise
+    fflush(NULL);
+    pid_t pid = fork();
+    if (pid == 0) {
+        close(report[0]);
+        if (out >= 0) {
+            dup2(out, STDOUT_FILENO);
+        }
+        if (err >= 0) {
+            dup2(err, STDERR_FILENO);
+        }
+        execvp(command, argv);
+        int code = errno;
+        ssize_t written = write(report[1], &code, sizeof(code));
+        (void) written;
+        _exit(127);
+    }
+
+    free(argv);
+    close(report[1]);
+    if (pid < 0) {
+        close(report[0]);
+        fail(errno);
+        return -1;
+    }
+
+    int code;
+    ssize_t read_bytes = read(report[0], &code, sizeof(code));
+    close(report[0]);
+    if (read_bytes == sizeof(code)) {
+        waitpid(pid, NULL, 0);
+        fail(code);
+        return -1;
+    }
+    return pid;
+}
+
+static void append(char **text, size_t *length, const char *bytes, size_t count) {
+    *text = realloc(*text, *length + count + 1);
+    memcpy(*text + *length, bytes, count);
+    *length += count;
+    (*text)[*length] = '\0';
+}
+
+bool core_intrinsics_process_run_command(const char *command, const char *const *arguments, size_t count,
+                                         struct core_intrinsics_process_output *output) {
+    int out[2], err[2];
+    if (pipe(out) != 0) {
+        return fail(errno);
+    }
+    if (pipe(err) != 0) {
+        int code = errno;
+        close(out[0]);
+        close(out[1]);
+        return fail(code);
+    }
+    fcntl(out[0], F_SETFD, FD_CLOEXEC);
+    fcntl(err[0], F_SETFD, FD_CLOEXEC);
+
+    pid_t pid = start(command, arguments, count, out[1], err[1]);
+    close(out[1]);
+    close(err[1]);
+    if (pid < 0) {
+        close(out[0]);
+        close(err[0]);
+        return false;
+    }
+
+    char *texts[2] = {malloc(1), malloc(1)};
+    size_t lengths[2] = {0, 0};
+    texts[0][0] = '\0';
+    texts[1][0] = '\0';
+
+    // both pipes get drained together, a command filling one of them would block otherwise
+    struct pollfd fds[2] = {{.fd = out[0], .events = POLLIN}, {.fd = err[0], .events = POLLIN}};
+    int open_fds = 2;
+    char buffer[4096];
+    while (open_fds > 0) {
+        if (poll(fds, 2, -1) < 0) {
+            if (errno == EINTR) {
+                continue;
+            }
+            break;
+        }
+        for (int i = 0; i < 2; i++) {
+            if (fds[i].fd < 0 || fds[i].revents == 0) {
+                continue;
+            }
+            ssize_t read_bytes = read(fds[i].fd, buffer, sizeof(buffer));
+            if (read_bytes > 0) {
+                append(&texts[i], &lengths[i], buffer, (size_t) read_bytes);
+            } else {
+                close(fds[i].fd);
+                fds[i].fd = -1;
+                open_fds--;
+            }
+        }
+    }
+
+    int status;
+    waitpid(pid, &status, 0);
+    output->status = exit_code(status);
+    output->stdout_text = texts[0];
+    output->stderr_text = texts[1];
+    return true;
+}
+
+bool core_intrinsics_process_spawn_command(const char *command, const char *const *arguments, size_t count,
+                                           int *status) {
+    pid_t pid = start(command, arguments, count, -1, -1);
+    if (pid < 0) {
+        return false;
+    }
+    int result;
+    waitpid(pid, &result, 0);
+    *status = exit_code(result);
+    return true;
+}
+
+char *core_intrinsics_process_current_dir(void) {
+    size_t capacity = 256;
+    char *dir = malloc(capacity);
+    while (getcwd(dir, capacity) == NULL) {
+        if (errno != ERANGE) {
+            fail(errno);
+            free(dir);
+            return NULL;
+        }
+        capacity *= 2;
+        dir = realloc(dir, capacity);
+    }
+    return dir;
+}
+
+void core_intrinsics_process_exit(int code) {
+    exit(code);
+}
@@ -0,0 +1,36 @@
+#ifndef CORE_INTRINSICS_PROCESS_H
+#define CORE_INTRINSICS_PROCESS_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+// main hands over its arguments before anything else runs
+void core_intrinsics_process_init(int argc, char **argv);
+
+// operations which can fail return false or NULL, core_intrinsics_process_error tells why
+const char *core_intrinsics_process_error(void);
+
+// the first argument names the program
+const char *const *core_intrinsics_process_arguments(size_t *count);
+
+// NULL if the variable is not set
+const char *core_intrinsics_process_env_var(const char *name);
+
+// captured output is allocated and owned by the caller, status is -1 if a signal terminated the command
+struct core_intrinsics_process_output {
+    int status;
+    char *stdout_text;
+    char *stderr_text;
+};
+
+bool core_intrinsics_process_run_command(const char *command, const char *const *arguments, size_t count,
+                                         struct core_intrinsics_process_output *output);
+bool core_intrinsics_process_spawn_command(const char *command, const char *const *arguments, size_t count,
+                                           int *status);
+
+// allocated and owned by the caller
+char *core_intrinsics_process_current_dir(void);
+
+void core_intrinsics_process_exit(int code);
+
+#endif
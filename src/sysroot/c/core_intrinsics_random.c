@@ -0,0 +1,62 @@
+#define _POSIX_C_SOURCE 200809L
+
+#include "core_intrinsics_random.h"
+
+#include <stdint.h>
+#include <stdio.h>
+#include <time.h>
+#include <unistd.h>
+
+#define GAMMA 0x9E3779B97F4A7C15ULL
+// largest integer a double holds exactly
+#define MAX_EXACT 9007199254740992.0
+
+// truncates towards 0, saturates at the bounds of int64_t and maps nan to 0
+static uint64_t integer(double value) {
+    if (value != value) {
+        return 0;
+    }
+    if (value >= 9223372036854775807.0) {
+        return (uint64_t) INT64_MAX;
+    }
+    if (value <= -9223372036854775808.0) {
+        return (uint64_t) INT64_MIN;
+    }
+    return (uint64_t) (int64_t) value;
+}
+
+static uint64_t next(double seed, double step) {
+    uint64_t z = integer(seed) + (integer(step) + 1) * GAMMA;
+    z = (z ^ (z >> 30)) * 0xBF58476D1CE4E5B9ULL;
+    z = (z ^ (z >> 27)) * 0x94D049BB133111EBULL;
+    return z ^ (z >> 31);
+}
+
+double core_intrinsics_random_float(double seed, double step) {
+    return (double) (next(seed, step) >> 11) / MAX_EXACT;
+}
+
+double core_intrinsics_random_below(double seed, double step, double bound) {
+    if (!(bound >= 1.0)) {
+        bound = 1.0;
+    }
+    if (bound > MAX_EXACT) {
+        bound = MAX_EXACT;
+    }
+    return (double) (next(seed, step) % (uint64_t) bound);
+}
+
+// falls back to the clock and process id if there is no /dev/urandom
+double core_intrinsics_random_seed(void) {
+    uint64_t entropy = 0;
+    FILE *file = fopen("/dev/urandom", "rb");
+    if (file == NULL || fread(&entropy, sizeof(entropy), 1, file) != 1) {
+        struct timespec now;
+        clock_gettime(CLOCK_REALTIME, &now);
+        entropy = next((double) now.tv_nsec, (double) getpid()) ^ (uint64_t) now.tv_sec;
+    }
+    if (file != NULL) {
+        fclose(file);
+    }
+    return (double) (entropy >> 11);
+}
@@ -0,0 +1,14 @@
+#ifndef CORE_INTRINSICS_RANDOM_H
+#define CORE_INTRINSICS_RANDOM_H
+
+// numbers only depend on the seed and how many got drawn before (the step), they follow splitmix64
+// like the interpreter does
+
+// within 0 and 1, excluding 1
+double core_intrinsics_random_float(double seed, double step);
+// integer within 0 and bound, excluding the bound
+double core_intrinsics_random_below(double seed, double step, double bound);
+// seed from the randomness of the os
+double core_intrinsics_random_seed(void);
+
+#endif
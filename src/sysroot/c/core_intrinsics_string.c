@@ -0,0 +1,230 @@
+#include "core_intrinsics_string.h"
+
+#include <ctype.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+static char error[256] = "";
+
+static bool is_continuation(char c) {
+    return ((unsigned char) c & 0xC0) == 0x80;
+}
+
+// bytes of the char text starts with
+static size_t char_length(const char *text) {
+    size_t length = 1;
+    while (text[length] != '\0' && is_continuation(text[length])) {
+        length++;
+    }
+    return length;
+}
+
+static char *copy(const char *text, size_t length) {
+    char *result = malloc(length + 1);
+    memcpy(result, text, length);
+    result[length] = '\0';
+    return result;
+}
+
+double core_intrinsics_string_length(const char *text) {
+    size_t count = 0;
+    for (; *text != '\0'; text++) {
+        if (!is_continuation(*text)) {
+            count++;
+        }
+    }
+    return (double) count;
+}
+
+char *core_intrinsics_string_slice(const char *text, double start, double end) {
+    size_t from = start < 1 ? 1 : (size_t) start;
+    size_t to = end < 0 ? 0 : (size_t) end;
+    if (to < from) {
+        return copy("", 0);
+    }
+
+    const char *begin = text;
+    for (size_t position = 1; position < from && *begin != '\0'; position++) {
+        begin += char_length(begin);
+    }
+    const char *finish = begin;
+    for (size_t position = from; position <= to && *finish != '\0'; position++) {
+        finish += char_length(finish);
+    }
+    return copy(begin, (size_t) (finish - begin));
+}
+
+char **core_intrinsics_string_chars(const char *text, size_t *count) {
+    *count = (size_t) core_intrinsics_string_length(text);
+    char **chars = malloc((*count + 1) * sizeof(char *));
+    for (size_t idx = 0; idx < *count; idx++) {
+        size_t length = char_length(text);
+        chars[idx] = copy(text, length);
+        text += length;
+    }
+    return chars;
+}
+
+char **core_intrinsics_string_split(const char *text, const char *separator, size_t *count) {
+    size_t separator_length = strlen(separator);
+    if (separator_length == 0) {
+        return core_intrinsics_string_chars(text, count);
+    }
+
+    size_t capacity = 8;
+    char **parts = malloc(capacity * sizeof(char *));
+    *count = 0;
+    for (;;) {
+        const char *found = strstr(text, separator);
+        if (*count == capacity) {
+            capacity *= 2;
+            parts = realloc(parts, capacity * sizeof(char *));
+        }
+        if (found == NULL) {
+            parts[(*count)++] = copy(text, strlen(text));
+            return parts;
+        }
+        parts[(*count)++] = copy(text, (size_t) (found - text));
+        text = found + separator_length;
+    }
+}
+
+char *core_intrinsics_string_join(const char *const *parts, size_t count, const char *separator) {
+    size_t separator_length = strlen(separator);
+    size_t length = 0;
+    for (size_t idx = 0; idx < count; idx++) {
+        length += strlen(parts[idx]) + (idx > 0 ? separator_length : 0);
+    }
+
+    char *result = malloc(length + 1);
+    char *end = result;
+    for (size_t idx = 0; idx < count; idx++) {
+        if (idx > 0) {
+            memcpy(end, separator, separator_length);
+            end += separator_length;
+        }
+        size_t part_length = strlen(parts[idx]);
+        memcpy(end, parts[idx], part_length);
+        end += part_length;
+    }
+    *end = '\0';
+    return result;
+}
+
+// the whitespace of rusts is_ascii_whitespace, which knows no vertical tab
+static bool is_whitespace(char c) {
+    return c == ' ' || c == '\t' || c == '\n' || c == '\f' || c == '\r';
+}
+
+char *core_intrinsics_string_trim(const char *text) {
+    while (is_whitespace(*text)) {
+        text++;
+    }
+    size_t length = strlen(text);
+    while (length > 0 && is_whitespace(text[length - 1])) {
+        length--;
+    }
+    return copy(text, length);
+}
+
+bool core_intrinsics_string_starts_with(const char *text, const char *prefix) {
+    return strncmp(text, prefix, strlen(prefix)) == 0;
+}
+
+bool core_intrinsics_string_ends_with(const char *text, const char *suffix) {
+    size_t text_length = strlen(text);
+    size_t suffix_length = strlen(suffix);
+    return suffix_length <= text_length && strcmp(text + text_length - suffix_length, suffix) == 0;
+}
+
+bool core_intrinsics_string_contains(const char *text, const char *part) {
+    return strstr(text, part) != NULL;
+}
+
+bool core_intrinsics_string_equals(const char *text, const char *other) {
+    return strcmp(text, other) == 0;
+}
+
+char *core_intrinsics_string_replace(const char *text, const char *pattern, const char *replacement) {
+    size_t pattern_length = strlen(pattern);
+    if (pattern_length == 0) {
+        return copy(text, strlen(text));
+    }
+
+    size_t count;
+    char **parts = core_intrinsics_string_split(text, pattern, &count);
+    char *result = core_intrinsics_string_join((const char *const *) parts, count, replacement);
+    for (size_t idx = 0; idx < count; idx++) {
+        free(parts[idx]);
+    }
+    free(parts);
+    return result;
+}
+
+char *core_intrinsics_string_to_upper(const char *text) {
+    char *result = copy(text, strlen(text));
+    for (char *c = result; *c != '\0'; c++) {
+        if (*c >= 'a' && *c <= 'z') {
+            *c = (char) toupper((unsigned char) *c);
+        }
+    }
+    return result;
+}
+
+char *core_intrinsics_string_to_lower(const char *text) {
+    char *result = copy(text, strlen(text));
+    for (char *c = result; *c != '\0'; c++) {
+        if (*c >= 'A' && *c <= 'Z') {
+            *c = (char) tolower((unsigned char) *c);
+        }
+    }
+    return result;
+}
+
+static size_t digits(const char *text, size_t *idx) {
+    size_t start = *idx;
+    while (isdigit((unsigned char) text[*idx])) {
+        (*idx)++;
+    }
+    return *idx - start;
+}
+
+// strtod alone would accept whitespace, hex, inf and nan as well
+static bool is_decimal(const char *text) {
+    size_t idx = 0;
+    if (text[idx] == '+' || text[idx] == '-') {
+        idx++;
+    }
+    size_t mantissa = digits(text, &idx);
+    if (text[idx] == '.') {
+        idx++;
+        mantissa += digits(text, &idx);
+    }
+    if (mantissa == 0) {
+        return false;
+    }
+    if (text[idx] == 'e' || text[idx] == 'E') {
+        idx++;
+        if (text[idx] == '+' || text[idx] == '-') {
+            idx++;
+        }
+        if (digits(text, &idx) == 0) {
+            return false;
+        }
+    }
+    return text[idx] == '\0';
+}
+
+bool core_intrinsics_string_to_number(const char *text, double *number) {
+    if (!is_decimal(text)) {
+        snprintf(error, sizeof(error), "'%s' is not a number", text);
+        return false;
+    }
+    *number = strtod(text, NULL);
+    return true;
+}
+
+const char *core_intrinsics_string_error(void) {
+    return error;
+}
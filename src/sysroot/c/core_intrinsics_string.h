@@ -0,0 +1,30 @@
+#ifndef CORE_INTRINSICS_STRING_H
+#define CORE_INTRINSICS_STRING_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+// texts are utf-8, positions count chars starting at 1. Case conversion and trimming only know
+// ascii. Returned strings and lists are allocated and owned by the caller
+
+double core_intrinsics_string_length(const char *text);
+char *core_intrinsics_string_slice(const char *text, double start, double end);
+char **core_intrinsics_string_chars(const char *text, size_t *count);
+char **core_intrinsics_string_split(const char *text, const char *separator, size_t *count);
+char *core_intrinsics_string_join(const char *const *parts, size_t count, const char *separator);
+char *core_intrinsics_string_trim(const char *text);
+
+bool core_intrinsics_string_starts_with(const char *text, const char *prefix);
+bool core_intrinsics_string_ends_with(const char *text, const char *suffix);
+bool core_intrinsics_string_contains(const char *text, const char *part);
+bool core_intrinsics_string_equals(const char *text, const char *other);
+
+char *core_intrinsics_string_replace(const char *text, const char *pattern, const char *replacement);
+char *core_intrinsics_string_to_upper(const char *text);
+char *core_intrinsics_string_to_lower(const char *text);
+
+// returns false for anything but decimal numbers, core_intrinsics_string_error tells why
+bool core_intrinsics_string_to_number(const char *text, double *number);
+const char *core_intrinsics_string_error(void);
+
+#endif
@@ -0,0 +1,139 @@
+#define _POSIX_C_SOURCE 200809L
+
+#include "core_intrinsics_time.h"
+
+#include <errno.h>
+#include <math.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <time.h>
+
+static double nanos_of(struct timespec time) {
+    return (double) time.tv_sec * 1e9 + (double) time.tv_nsec;
+}
+
+// nanoseconds since the first time anything asked for it
+double core_intrinsics_time_monotonic_nanos(void) {
+    static int started = 0;
+    static struct timespec start;
+    struct timespec now;
+    clock_gettime(CLOCK_MONOTONIC, &now);
+    if (!started) {
+        start = now;
+        started = 1;
+    }
+    return (double) (now.tv_sec - start.tv_sec) * 1e9 + (double) (now.tv_nsec - start.tv_nsec);
+}
+
+double core_intrinsics_time_wall_clock_millis(void) {
+    struct timespec now;
+    clock_gettime(CLOCK_REALTIME, &now);
+    return floor(nanos_of(now) / 1e6);
+}
+
+double core_intrinsics_time_subtract_nanos(double nanos, double other) {
+    return nanos - other;
+}
+
+double core_intrinsics_time_divide_nanos(double nanos, double unit) {
+    return nanos / unit;
+}
+
+// up to 3 decimals without trailing zeros
+static void decimals(char *buffer, size_t size, double value) {
+    snprintf(buffer, size, "%.3f", value);
+    char *end = buffer + strlen(buffer) - 1;
+    while (*end == '0') {
+        *end-- = '\0';
+    }
+    if (*end == '.') {
+        *end = '\0';
+    }
+}
+
+char *core_intrinsics_time_format_duration(double nanos) {
+    char *result = malloc(64);
+    if (nanos < 0) {
+        char *positive = core_intrinsics_time_format_duration(-nanos);
+        snprintf(result, 64, "-%s", positive);
+        free(positive);
+        return result;
+    }
+
+    // rounded to what the unit shows before choosing it, 999.9996ms is 1s and not 1000ms
+    char value[48];
+    nanos = round(nanos);
+    double micros = round(nanos / 1e3);
+    double millis = round(nanos / 1e6);
+    if (nanos < 1e3) {
+        snprintf(result, 64, "%.0fns", nanos);
+    } else if (nanos < 1e6) {
+        decimals(value, sizeof(value), nanos / 1e3);
+        snprintf(result, 64, "%s\xc2\xb5s", value);
+    } else if (micros < 1e6) {
+        decimals(value, sizeof(value), micros / 1e3);
+        snprintf(result, 64, "%sms", value);
+    } else if (millis < 60e3) {
+        decimals(value, sizeof(value), millis / 1e3);
+        snprintf(result, 64, "%ss", value);
+    } else {
+        double minutes = floor(millis / 60e3);
+        decimals(value, sizeof(value), (millis - minutes * 60e3) / 1e3);
+        double hours = floor(minutes / 60);
+        minutes = minutes - hours * 60;
+        if (hours > 0) {
+            snprintf(result, 64, "%.0fh %.0fm %ss", hours, minutes, value);
+        } else {
+            snprintf(result, 64, "%.0fm %ss", minutes, value);
+        }
+    }
+    return result;
+}
+
+static int64_t floor_div(int64_t a, int64_t b) {
+    int64_t result = a / b;
+    return (a % b != 0 && (a < 0) != (b < 0)) ? result - 1 : result;
+}
+
+// see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+static void civil_from_days(int64_t days, int64_t *year, int64_t *month, int64_t *day) {
+    days += 719468;
+    int64_t era = floor_div(days, 146097);
+    int64_t day_of_era = days - era * 146097;
+    int64_t year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    int64_t day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    int64_t month_index = (5 * day_of_year + 2) / 153;
+    *day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    *month = month_index < 10 ? month_index + 3 : month_index - 9;
+    *year = year_of_era + era * 400 + (*month <= 2 ? 1 : 0);
+}
+
+char *core_intrinsics_time_format_iso8601(double millis) {
+    int64_t total = (int64_t) floor(millis);
+    int64_t days = floor_div(total, 86400000);
+    int64_t millis_of_day = total - days * 86400000;
+    int64_t year, month, day;
+    civil_from_days(days, &year, &month, &day);
+    int64_t seconds_of_day = millis_of_day / 1000;
+
+    char *result = malloc(64);
+    snprintf(result, 64, "%04lld-%02lld-%02lldT%02lld:%02lld:%02lld.%03lldZ",
+             (long long) year, (long long) month, (long long) day,
+             (long long) (seconds_of_day / 3600), (long long) (seconds_of_day / 60 % 60),
+             (long long) (seconds_of_day % 60), (long long) (millis_of_day % 1000));
+    return result;
+}
+
+void core_intrinsics_time_sleep_millis(double millis) {
+    if (millis <= 0) {
+        return;
+    }
+    struct timespec duration = {
+        .tv_sec = (time_t) (millis / 1000),
+        .tv_nsec = (long) fmod(millis * 1e6, 1e9),
+    };
+    while (nanosleep(&duration, &duration) != 0 && errno == EINTR) {
+    }
+}
@@ -0,0 +1,19 @@
+#ifndef CORE_INTRINSICS_TIME_H
+#define CORE_INTRINSICS_TIME_H
+
+// durations and monotonic instants are nanoseconds, wall clock timestamps milliseconds since the
+// unix epoch in utc. Returned strings are allocated and owned by the caller
+
+double core_intrinsics_time_monotonic_nanos(void);
+double core_intrinsics_time_wall_clock_millis(void);
+double core_intrinsics_time_subtract_nanos(double nanos, double other);
+double core_intrinsics_time_divide_nanos(double nanos, double unit);
+
+// like 1.5s, 250ms or 1h 2m 3.5s
+char *core_intrinsics_time_format_duration(double nanos);
+// like 2024-02-29T13:05:09.042Z
+char *core_intrinsics_time_format_iso8601(double millis);
+
+void core_intrinsics_time_sleep_millis(double millis);
+
+#endif
@@ -0,0 +1,27 @@
+#include <stdio.h>
+#include <stdbool.h>
+#include "core_intrinsics_fs.h"
+#include "core_intrinsics_io.h"
+#include "core_intrinsics_process.h"
+#include "core_intrinsics_random.h"
+#include "core_intrinsics_string.h"
+#include "core_intrinsics_time.h"
+#include "std_io.h"
+#include "core_intrinsics_math.h"
+#include "core_bool.h"
+struct Person;
+struct Person{
+};
+void person_say_name(struct Person * self);
+int main(int argc, char ** argv){
+core_intrinsics_process_init(argc, argv);
+struct Person elodie_1 = {.name = "Elodie", };
+person_say_name(&elodie_1);
+}
+void person_say_name(struct say_name * self){
+const char * temp_2 = self->name;
+char arg_2[100];
+snprintf(arg_2, 100, "%s%s", temp_1, temp_2);
+std_io_println(arg_2);
+}
+
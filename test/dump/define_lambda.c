@@ -0,0 +1 @@
+Failed to dump ./test/smoke/define_lambda.ec - CallFunctionOfObject is not supported by c generation yet
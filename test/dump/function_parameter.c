@@ -0,0 +1 @@
+Failed to dump ./test/smoke/function_parameter.ec - Variable outside of the generated scope is not supported by c generation yet
@@ -0,0 +1 @@
+Failed to dump ./test/smoke/hello_world.ec - LiteralString is not supported by c generation yet
@@ -0,0 +1 @@
+Failed to dump ./test/smoke/if.ec - LiteralString is not supported by c generation yet
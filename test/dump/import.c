@@ -0,0 +1 @@
+Failed to dump ./test/smoke/import.ec - DeclarePackage is not supported by c generation yet
@@ -0,0 +1 @@
+Failed to dump ./test/smoke/lambda.ec - CallFunctionWithLambda is not supported by c generation yet
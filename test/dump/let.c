@@ -0,0 +1 @@
+Failed to dump ./test/smoke/let.ec - AccessVariable is not supported by c generation yet
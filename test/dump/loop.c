@@ -0,0 +1 @@
+Failed to dump ./test/smoke/loop.ec - Loop is not supported by c generation yet
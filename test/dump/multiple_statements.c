@@ -0,0 +1 @@
+Failed to dump ./test/smoke/multiple_statements.ec - LiteralString is not supported by c generation yet
@@ -0,0 +1 @@
+Failed to dump ./test/smoke/package.ec - DeclarePackage is not supported by c generation yet
@@ -0,0 +1,64 @@
+#include <stdio.h>
+#include <stdbool.h>
+#include "core_intrinsics_fs.h"
+#include "core_intrinsics_io.h"
+#include "core_intrinsics_process.h"
+#include "core_intrinsics_random.h"
+#include "core_intrinsics_string.h"
+#include "core_intrinsics_time.h"
+#include "std_io.h"
+#include "core_intrinsics_math.h"
+#include "core_bool.h"
+int main(int argc, char ** argv){
+core_intrinsics_process_init(argc, argv);
+double value_1 = 23;
+{
+double value_2 = 42;
+char arg_2[100];
+snprintf(arg_2, 100, "");
+std_io_println(arg_2);
+}
+char arg_2[100];
+snprintf(arg_2, 100, "");
+std_io_println(arg_2);
+if ( true) {
+double value_2 = 123;
+char arg_2[100];
+snprintf(arg_2, 100, "");
+std_io_println(arg_2);
+}
+char arg_4[100];
+snprintf(arg_4, 100, "");
+std_io_println(arg_4);
+if ( false) {
+double value_2 = 24;
+}
+else {
+double value_2 = 111;
+char arg_2[100];
+snprintf(arg_2, 100, "");
+std_io_println(arg_2);
+}
+char arg_6[100];
+snprintf(arg_6, 100, "");
+std_io_println(arg_6);
+double v_1 = 1;
+{
+{
+{
+{
+{
+double v_2 = 2;
+char arg_2[100];
+snprintf(arg_2, 100, "");
+std_io_println(arg_2);
+}
+}
+}
+}
+}
+char arg_8[100];
+snprintf(arg_8, 100, "");
+std_io_println(arg_8);
+}
+
@@ -0,0 +1,47 @@
+#!/usr/bin/env bash
+
+set -eu
+
+# Compares the tokens, parse tree, ast, ir and c code of every source file with its snapshot,
+# UPDATE=1 rewrites the snapshots instead. Stages which can not handle a file yet snapshot the
+# error they report
+DIFF_TOOL="${DIFF_TOOL:-diff}"
+SOURCE_DIR="${1:-./test/smoke}"
+SNAPSHOT_DIR="${2:-./test/dump}"
+BIN="${3:-../bootstrap/target/debug/bootstrap}"
+UPDATE="${UPDATE:-0}"
+
+EXIT_CODE=0
+OK_COUNT=0
+ERR_COUNT=0
+
+for FILE in "$SOURCE_DIR"/*.ec; do
+    if [[ ! -f "$FILE" ]]; then
+        echo "No source files found in directory $SOURCE_DIR"
+        exit 1
+    fi
+
+    for STAGE in tokens parse ast ir c; do
+        SNAPSHOT="$SNAPSHOT_DIR/$(basename "$FILE" .ec).$STAGE"
+
+        if [[ "$UPDATE" == "1" ]]; then
+            ${BIN} dump "$STAGE" "$FILE" > "$SNAPSHOT" 2>&1 || true
+        fi
+
+        if ! ${DIFF_TOOL} "$SNAPSHOT" <(${BIN} dump "$STAGE" "$FILE" 2>&1); then
+            printf "\e[31mFail\e[0m\t$FILE ($STAGE)\n"
+            ERR_COUNT=$((ERR_COUNT + 1))
+            EXIT_CODE=1
+        else
+            printf "\e[32mPass\e[0m\t$FILE ($STAGE)\n"
+            OK_COUNT=$((OK_COUNT + 1))
+        fi
+    done
+done
+
+# Summary
+echo "----------------------"
+echo -e "bootstrap::dump - Passed:\e[32m $OK_COUNT" "\e[0mFailed:\e[31m $ERR_COUNT\e[0m"
+echo "----------------------"
+
+exit $EXIT_CODE
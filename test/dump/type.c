@@ -0,0 +1,29 @@
+#include <stdio.h>
+#include <stdbool.h>
+#include "core_intrinsics_fs.h"
+#include "core_intrinsics_io.h"
+#include "core_intrinsics_process.h"
+#include "core_intrinsics_random.h"
+#include "core_intrinsics_string.h"
+#include "core_intrinsics_time.h"
+#include "std_io.h"
+#include "core_intrinsics_math.h"
+#include "core_bool.h"
+struct Point;
+struct Point{
+};
+int main(int argc, char ** argv){
+core_intrinsics_process_init(argc, argv);
+struct Point point_1 = {.x = 1, .y = 2, };
+char temp_1[20];
+snprintf(temp_1, 20, "%.0f", point_1.x);
+char arg_2[100];
+snprintf(arg_2, 100, "%s", temp_1);
+std_io_println(arg_2);
+char temp_2[20];
+snprintf(temp_2, 20, "%.0f", point_1.y);
+char arg_4[100];
+snprintf(arg_4, 100, "%s", temp_2);
+std_io_println(arg_4);
+}
+
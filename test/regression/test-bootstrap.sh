@@ -20,10 +20,16 @@ for FILE in $(find "$TEST_DIR" -type f -name "*.ec" ! -name "*.test.ec"); do
         exit 1
     fi
 
+    # A file next to the test with .in instead of .ec becomes its stdin
+    INPUT="${FILE%.ec}.in"
+    if [[ ! -f "$INPUT" ]]; then
+        INPUT=/dev/null
+    fi
+
     # Run the test and compare output
     if ! ${DIFF_TOOL} \
         <(awk -F '// out:' '/out/{print $2}' "$FILE") \
-        <(${BIN} "$FILE" 2> /dev/null); then
+        <(${BIN} "$FILE" 2> /dev/null < "$INPUT"); then
             printf "\e[31mFail\e[0m\t$FILE\n"
             ERR_COUNT=$((ERR_COUNT + 1))
             EXIT_CODE=1
@@ -39,17 +45,8 @@ echo -e "bootstrap::run::regression - Passed:\e[32m $OK_COUNT" "\e[0mFailed:\e[3
 echo "----------------------"
 echo -e "bootstrap::run::regression - self hosted tests"
 
-
-for FILE in "$TEST_DIR"/**/*.test.ec; do
-    if [[ ! -f "$FILE" ]]; then
-        echo "No test files found in directory $TEST_DIR"
-        exit 1
-    fi
-    echo "----------------------"
-    echo -e "$FILE"
-    if ! ${BIN} test "$FILE" true true; then
-        EXIT_CODE=-1
-    fi
-done
+if ! ${BIN} test "$TEST_DIR"; then
+    EXIT_CODE=1
+fi
 
 exit $EXIT_CODE
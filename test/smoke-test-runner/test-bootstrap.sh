@@ -23,7 +23,7 @@ for FILE in "$TEST_DIR"/**/*.ec; do
     # Run the test and compare output
     if ! ${DIFF_TOOL} \
         <(awk -F '// out:' '/out/{gsub(/\\\\033/, "\033"); print $2}' "$FILE") \
-        <(${BIN} test "$FILE" false 2> /dev/null); then
+        <(${BIN} test "$FILE" --no-color 2> /dev/null); then
             printf "\e[31mFail\e[0m\t$FILE\n"
             ERR_COUNT=$((ERR_COUNT + 1))
             EXIT_CODE=1